@@ -7,26 +7,55 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use crate::audio::capture::AudioBuffer;
-use crate::audio::processing::{encode_wav, normalize_gain};
+use crate::audio::mic_test::MicTestHandle;
+use crate::audio::processing::{denoise, encode_wav, normalize_gain, resample, WHISPER_SAMPLE_RATE};
+use crate::audio::vad::{trim_silence, EnergyVad, SegmentationConfig};
 use crate::config::VaaniConfig;
-use crate::enhance::enhance_streaming;
+use crate::enhance::{resolve_llm_provider, LlmProvider};
 use crate::error::VaaniError;
 use crate::keychain::create_secret_storage;
-use crate::output::paste::{paste_text, type_text};
+use crate::output::clipboard::ClipboardType;
+use crate::output::paste::{paste_text, retype_text, type_text, PasteMode};
 use crate::prompts::build_system_prompt;
-use crate::state::StateMachine;
-use crate::transcribe::transcribe;
+use crate::state::{SharedStateMachine, StateMachine};
+use crate::transcribe::{resolve_stt_backend, translate, StreamingSession};
+
+/// Over-subtraction factor passed to [`denoise`] when
+/// `noise_suppression_enabled` is on — within its documented 1.5-2.0 range.
+const DENOISE_ALPHA: f32 = 2.0;
+
+/// Spectral floor passed to [`denoise`] when `noise_suppression_enabled` is
+/// on — its documented default of ~2% of each frame's original magnitude.
+const DENOISE_BETA: f32 = 0.02;
+
+/// VAD chunk size passed to [`EnergyVad::new`] and [`trim_silence`] when
+/// `trim_silence_enabled` is on — matches [`WHISPER_SAMPLE_RATE`] the way
+/// `SileroVad`'s own default chunk size matches its 16 kHz model input.
+const TRIM_SILENCE_CHUNK_SIZE: usize = 512;
 
 /// Shared application state accessible from Tauri commands and the pipeline.
 ///
 /// Note: `AudioRecorder` holds a cpal `Stream` which is not `Send`.
 /// We only store the `AudioBuffer` (which IS Send+Sync) here.
-/// The actual `AudioRecorder` is created and owned on the thread that starts recording.
+/// The actual `AudioRecorder` is created and owned on the thread that starts
+/// recording; for mic tests, that's the dedicated worker thread behind
+/// `mic_test` (see [`crate::audio::mic_test`]).
 pub struct VaaniApp {
-    pub state: Arc<Mutex<StateMachine>>,
+    pub state: Arc<SharedStateMachine>,
     pub config: Arc<Mutex<VaaniConfig>>,
     pub audio_buffer: AudioBuffer,
     pub http_client: reqwest::Client,
+    /// `None` if the mic-test worker thread failed to spawn (e.g. the OS
+    /// refused the thread due to resource limits); mic-test commands then
+    /// report a clear error instead of silently doing nothing.
+    mic_test: Option<MicTestHandle>,
+    /// The in-flight streaming-transcription session, if any; see
+    /// [`Self::start_streaming_transcription`].
+    streaming_session: Mutex<Option<StreamingSession>>,
+    /// The most recent interim transcript typed at the cursor, so the next
+    /// `on_partial` callback knows how much of it to erase before retyping.
+    /// Reset to empty once a segment is finalized.
+    streaming_partial: Arc<Mutex<String>>,
 }
 
 impl VaaniApp {
@@ -39,11 +68,32 @@ impl VaaniApp {
             .build()
             .unwrap_or_else(|_| reqwest::Client::new());
 
+        let audio_buffer = AudioBuffer::new();
+        let mic_test = MicTestHandle::spawn(audio_buffer.clone())
+            .map_err(|e| tracing::error!("Failed to start mic-test worker thread: {e}"))
+            .ok();
+
+        let state = Arc::new(SharedStateMachine::new(StateMachine::new()));
+        let streaming_partial = Arc::new(Mutex::new(String::new()));
+        {
+            let streaming_partial = Arc::clone(&streaming_partial);
+            state.lock().on_partial(Box::new(move |text, is_final| {
+                let mut last = streaming_partial.lock().unwrap_or_else(|e| e.into_inner());
+                if let Err(e) = retype_text(&last, text) {
+                    tracing::warn!("Failed to retype streamed partial: {e}");
+                }
+                *last = if is_final { String::new() } else { text.to_string() };
+            }));
+        }
+
         Self {
-            state: Arc::new(Mutex::new(StateMachine::new())),
+            state,
             config: Arc::new(Mutex::new(config)),
-            audio_buffer: AudioBuffer::new(),
+            audio_buffer,
             http_client,
+            mic_test,
+            streaming_session: Mutex::new(None),
+            streaming_partial,
         }
     }
 
@@ -62,12 +112,7 @@ impl VaaniApp {
         let result = self.process_audio(samples, &config).await;
 
         // Always transition back to idle
-        if let Err(e) = self
-            .state
-            .lock()
-            .unwrap_or_else(|e| e.into_inner())
-            .finish_processing()
-        {
+        if let Err(e) = self.state.finish_processing() {
             tracing::error!("Failed to transition to idle: {e}");
         }
 
@@ -84,26 +129,88 @@ impl VaaniApp {
             return Err(VaaniError::NoSpeechDetected);
         }
 
+        // The device may not have actually captured at `config.sample_rate`
+        // (see `AudioRecorder::negotiate_config`); resample from whatever
+        // rate `audio_buffer` recorded it was given, falling back to the
+        // configured rate if that's somehow unset.
+        let captured_rate = self.audio_buffer.sample_rate().unwrap_or(config.sample_rate);
+
         tracing::info!(
             sample_count = samples.len(),
+            captured_rate,
             "Processing audio ({:.1}s)",
-            samples.len() as f32 / config.sample_rate as f32
+            samples.len() as f32 / captured_rate as f32
         );
 
+        // Optionally suppress stationary background noise before gain
+        // normalization, so a noisy mic doesn't throw off the RMS target.
+        let denoised = if config.noise_suppression_enabled {
+            denoise(&samples, captured_rate, DENOISE_ALPHA, DENOISE_BETA)
+        } else {
+            samples
+        };
+
         // Normalize audio gain
-        let normalized = normalize_gain(&samples, -20.0);
+        let normalized = normalize_gain(&denoised, -20.0);
+
+        // Resample to the rate every STT backend expects, shrinking the
+        // upload ~3x on a typical 48 kHz capture with no quality loss.
+        let resampled = resample(&normalized, captured_rate, WHISPER_SAMPLE_RATE);
+
+        // Optionally trim leading/trailing silence and dead air before
+        // upload, using the pure-Rust energy VAD (no bundled ONNX model
+        // required) with `vad_threshold` as the speech-onset probability.
+        let trimmed = if config.trim_silence_enabled {
+            let segmentation_config = SegmentationConfig {
+                onset: config.vad_threshold,
+                ..SegmentationConfig::default()
+            };
+            let mut vad = EnergyVad::new(TRIM_SILENCE_CHUNK_SIZE);
+            match trim_silence(
+                &resampled,
+                WHISPER_SAMPLE_RATE,
+                TRIM_SILENCE_CHUNK_SIZE,
+                &segmentation_config,
+                &mut vad,
+            ) {
+                Ok(result) if !result.samples.is_empty() => result.samples,
+                Ok(_) => {
+                    tracing::debug!("VAD found no speech segments, keeping audio untrimmed");
+                    resampled
+                }
+                Err(e) => {
+                    tracing::warn!("Silence trimming failed, keeping audio untrimmed: {e}");
+                    resampled
+                }
+            }
+        } else {
+            resampled
+        };
 
         // Encode to WAV
-        let wav_bytes = encode_wav(&normalized, config.sample_rate)?;
-
-        // Transcribe via Whisper API
-        let api_key = resolve_api_key(
-            "openai_api_key",
-            &["VAANI_OPENAI_API_KEY", "OPENAI_API_KEY"],
-        )
-        .ok_or_else(|| VaaniError::MissingApiKey("OpenAI".to_string()))?;
-
-        let text = transcribe(&self.http_client, &api_key, &wav_bytes, &config.stt_model).await?;
+        let wav_bytes = encode_wav(&trimmed, WHISPER_SAMPLE_RATE)?;
+
+        // Transcribe (or translate) via the configured STT backend
+        let api_key = match config.stt_provider.as_str() {
+            "openai" => resolve_api_key("openai_api_key", &["VAANI_OPENAI_API_KEY", "OPENAI_API_KEY"]),
+            "deepgram" => resolve_api_key("deepgram_api_key", &["VAANI_DEEPGRAM_API_KEY", "DEEPGRAM_API_KEY"]),
+            _ => None,
+        };
+        let text = if config.stt_task == "translate" {
+            // Only Whisper exposes a dedicated translation endpoint today;
+            // other providers only ever transcribe (see `transcribe::STT_TASKS`).
+            if config.stt_provider != "openai" {
+                return Err(VaaniError::Config(format!(
+                    "stt_task 'translate' requires stt_provider 'openai', got '{}'",
+                    config.stt_provider
+                )));
+            }
+            let api_key = api_key.ok_or_else(|| VaaniError::MissingApiKey("OpenAI".to_string()))?;
+            translate(&self.http_client, &api_key, &wav_bytes, &config.stt_model).await?
+        } else {
+            let backend = resolve_stt_backend(&config.stt_provider, &config.stt_model, api_key)?;
+            backend.transcribe(&wav_bytes, WHISPER_SAMPLE_RATE).await?
+        };
 
         tracing::info!(chars = text.len(), "Transcription complete");
 
@@ -113,66 +220,221 @@ impl VaaniApp {
         Ok(enhanced)
     }
 
-    /// Enhance transcribed text via Claude and stream it to the cursor.
+    /// Enhance transcribed text via the configured LLM provider and stream
+    /// it to the cursor.
     ///
-    /// If the Anthropic API key is missing, falls back to pasting the raw
+    /// If the provider's API key is missing, falls back to pasting the raw
     /// transcription via clipboard paste instead.
     async fn enhance_and_paste(
         &self,
         text: &str,
         config: &VaaniConfig,
     ) -> Result<String, VaaniError> {
-        let anthropic_key = resolve_api_key(
-            "anthropic_api_key",
-            &["VAANI_ANTHROPIC_API_KEY", "ANTHROPIC_API_KEY"],
-        );
-
-        match anthropic_key {
+        let llm_key = match config.llm_provider.as_str() {
+            "anthropic" => resolve_api_key(
+                "anthropic_api_key",
+                &["VAANI_ANTHROPIC_API_KEY", "ANTHROPIC_API_KEY"],
+            ),
+            "openai" => resolve_api_key("openai_api_key", &["VAANI_OPENAI_API_KEY", "OPENAI_API_KEY"]),
+            _ => None,
+        };
+
+        match llm_key {
             Some(key) => {
-                let system_prompt = build_system_prompt(&config.active_mode);
-                tracing::info!(mode = %config.active_mode, "Enhancing with streaming");
-
-                let enhanced = enhance_streaming(
-                    &self.http_client,
-                    &key,
-                    text,
+                let provider = resolve_llm_provider(
+                    &config.llm_provider,
                     &config.llm_model,
-                    &system_prompt,
-                    |tokens| {
-                        if let Err(e) = type_text(tokens) {
-                            tracing::warn!("Failed to type streamed tokens: {e}");
-                        }
-                    },
-                )
-                .await?;
+                    config.llm_base_url.clone(),
+                    Some(key),
+                )?;
+
+                let system_prompt = match config.custom_mode_prompt(&config.active_mode) {
+                    Some(custom) => custom.to_string(),
+                    None => build_system_prompt(&config.active_mode),
+                };
+                tracing::info!(
+                    mode = %config.active_mode,
+                    provider = provider.name(),
+                    "Enhancing with streaming"
+                );
+
+                let enhanced = provider
+                    .enhance_streaming(
+                        text,
+                        &system_prompt,
+                        Box::new(|tokens| {
+                            if let Err(e) = type_text(tokens) {
+                                tracing::warn!("Failed to type streamed tokens: {e}");
+                            }
+                        }),
+                    )
+                    .await?;
 
                 tracing::info!(
                     original_len = text.len(),
                     enhanced_len = enhanced.len(),
                     "Enhancement complete"
                 );
+                self.speak_result(&enhanced, config).await;
                 Ok(enhanced)
             }
             _ => {
-                tracing::info!("No Anthropic API key, pasting raw transcription");
-                paste_text(text, config.paste_restore_delay_ms as u64)?;
+                tracing::info!("No LLM API key configured, pasting raw transcription");
+                paste_text(text, config, ClipboardType::Clipboard, PasteMode::Auto)?;
+                self.speak_result(text, config).await;
                 Ok(text.to_string())
             }
         }
     }
 
-    /// Returns the current app state.
-    pub fn current_state(&self) -> crate::state::AppState {
-        self.state
+    /// Reads `text` back via TTS if `config.speak_result` is enabled.
+    ///
+    /// Requires an OpenAI API key (the only TTS provider today); failures are
+    /// logged rather than propagated, since read-back is a convenience on top
+    /// of the already-pasted text, not core functionality.
+    async fn speak_result(&self, text: &str, config: &VaaniConfig) {
+        if !config.speak_result {
+            return;
+        }
+        let Some(api_key) =
+            resolve_api_key("openai_api_key", &["VAANI_OPENAI_API_KEY", "OPENAI_API_KEY"])
+        else {
+            tracing::warn!("speak_result is enabled but no OpenAI API key is configured");
+            return;
+        };
+        if let Err(e) = crate::tts::speak(
+            &self.http_client,
+            &api_key,
+            text,
+            &config.tts_model,
+            &config.tts_voice,
+        )
+        .await
+        {
+            tracing::warn!("Failed to speak result: {e}");
+        }
+    }
+
+    /// Starts a streaming-transcription session over Deepgram's real-time
+    /// endpoint: audio already accumulating in [`Self::audio_buffer`] is
+    /// pushed to Deepgram as it's captured, and interim hypotheses are typed
+    /// at the cursor as they arrive via [`crate::output::paste::retype_text`].
+    ///
+    /// Must be called while `Recording` (i.e. after the caller already
+    /// started recording the normal way); transitions the state machine to
+    /// `Streaming`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VaaniError::MissingApiKey`] if no Deepgram key is
+    /// configured, [`VaaniError::InvalidTransition`] if not currently
+    /// `Recording`, or [`VaaniError::Transcribe`] if the connection fails.
+    pub async fn start_streaming_transcription(&self) -> Result<(), VaaniError> {
+        let config = self
+            .config
             .lock()
             .unwrap_or_else(|e| e.into_inner())
-            .current()
+            .clone();
+        let api_key = resolve_api_key(
+            "deepgram_api_key",
+            &["VAANI_DEEPGRAM_API_KEY", "DEEPGRAM_API_KEY"],
+        )
+        .ok_or_else(|| VaaniError::MissingApiKey("Deepgram".to_string()))?;
+
+        self.state.start_streaming()?;
+
+        let state = Arc::clone(&self.state);
+        let session = StreamingSession::start(
+            self.audio_buffer.clone(),
+            api_key,
+            config.stt_model.clone(),
+            config.sample_rate,
+            move |text, is_final| {
+                if let Err(e) = state.emit_partial(text, is_final) {
+                    tracing::warn!("Failed to emit partial transcript: {e}");
+                }
+            },
+        )
+        .await?;
+
+        *self
+            .streaming_session
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(session);
+        Ok(())
+    }
+
+    /// Stops the current streaming session (if any), flushing the Deepgram
+    /// connection for a final transcript, then enhances and pastes it the
+    /// same way [`Self::process_and_paste`] does — transitioning the state
+    /// machine from `Streaming` to `Processing` and back to `Idle`.
+    pub async fn finish_streaming_and_paste(&self) -> Result<String, VaaniError> {
+        let session = self
+            .streaming_session
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+
+        let handle = self.state.stop_streaming()?;
+        let config = self
+            .config
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+
+        let result = async move {
+            let text = match session {
+                Some(session) => session.finish().await?,
+                None => return Err(VaaniError::NoSpeechDetected),
+            };
+            if text.trim().is_empty() {
+                return Err(VaaniError::NoSpeechDetected);
+            }
+            handle.report(0.5, "Transcription finalized");
+            self.enhance_and_paste(&text, &config).await
+        }
+        .await;
+
+        if let Err(e) = self.state.finish_processing() {
+            tracing::error!("Failed to transition to idle: {e}");
+        }
+
+        result
+    }
+
+    /// Returns the current app state.
+    pub fn current_state(&self) -> crate::state::AppState {
+        self.state.lock().current()
     }
 
     /// Returns the current audio input level (0.0 to 1.0).
     pub fn current_mic_level(&self) -> f32 {
         self.audio_buffer.current_level()
     }
+
+    /// Starts a mic-test session on the given device (or the default input
+    /// device if `None`), streaming its rolling level into
+    /// [`Self::current_mic_level`].
+    pub fn start_mic_test(&self, device_index: Option<u32>) -> Result<(), VaaniError> {
+        let config = self.config.lock().unwrap_or_else(|e| e.into_inner());
+        let sample_rate = config.sample_rate;
+        let mic_sensitivity = config.mic_sensitivity;
+        let noise_gate_threshold = config.noise_gate_threshold;
+        drop(config);
+
+        self.mic_test
+            .as_ref()
+            .ok_or_else(|| VaaniError::Audio("Mic-test worker is not available".to_string()))?
+            .start(device_index, sample_rate, mic_sensitivity, noise_gate_threshold)
+    }
+
+    /// Stops the current mic-test session, if any.
+    pub fn stop_mic_test(&self) -> Result<(), VaaniError> {
+        self.mic_test
+            .as_ref()
+            .ok_or_else(|| VaaniError::Audio("Mic-test worker is not available".to_string()))?
+            .stop()
+    }
 }
 
 /// Look up an API key: keychain first, then environment variables.
@@ -226,4 +488,32 @@ mod tests {
         // Just verify the client was created (no panic)
         let _client = &app.http_client;
     }
+
+    #[test]
+    fn stop_mic_test_without_a_running_session_is_ok() {
+        let app = default_app();
+        app.stop_mic_test().expect("stopping an idle mic test should not error");
+    }
+
+    #[tokio::test]
+    async fn start_streaming_transcription_without_a_deepgram_key_fails() {
+        let app = default_app();
+        let err = app
+            .start_streaming_transcription()
+            .await
+            .expect_err("no Deepgram key is configured in the test environment");
+        assert!(matches!(err, VaaniError::MissingApiKey(provider) if provider == "Deepgram"));
+        // The failed key lookup happens before any state transition.
+        assert_eq!(app.current_state(), AppState::Idle);
+    }
+
+    #[tokio::test]
+    async fn finish_streaming_and_paste_outside_streaming_is_rejected() {
+        let app = default_app();
+        let err = app
+            .finish_streaming_and_paste()
+            .await
+            .expect_err("not currently streaming");
+        assert!(matches!(err, VaaniError::InvalidTransition { .. }));
+    }
 }
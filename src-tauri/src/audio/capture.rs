@@ -1,13 +1,17 @@
 //! Audio capture using cpal.
 //!
 //! Provides `AudioRecorder` for recording audio from an input device.
-//! Audio samples are accumulated in a thread-safe buffer via cpal's callback.
+//! Audio samples are accumulated in a thread-safe buffer via cpal's callback,
+//! for consumers that only need the full take at `stop()`; `AudioBuffer::subscribe`
+//! additionally fans each chunk out live, for consumers (partial transcription,
+//! on-the-fly VAD, a moving level meter) that can't wait that long.
 
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::{Device, SampleRate, Stream, StreamConfig};
+use cpal::{Device, SampleFormat, SampleRate, Stream, StreamConfig, SupportedStreamConfig};
 
+use super::processing::rms;
 use crate::error::VaaniError;
 
 /// Lists available audio input devices with their names and indices.
@@ -25,32 +29,140 @@ pub fn list_input_devices() -> Result<Vec<(u32, String)>, VaaniError> {
     Ok(devices)
 }
 
-/// Returns the default input device, or an error if none is available.
-fn get_device(device_index: Option<u32>) -> Result<Device, VaaniError> {
+/// Returns the enumeration index and name of the system's current default
+/// input device, or `None` if no default device is available.
+///
+/// Matches by name against [`list_input_devices`] since cpal devices don't
+/// otherwise compare equal, so the returned index always lines up with what
+/// the UI already displays.
+pub fn default_input_device() -> Result<Option<(u32, String)>, VaaniError> {
     let host = cpal::default_host();
+    let Some(default) = host.default_input_device() else {
+        return Ok(None);
+    };
+    let default_name = default.name().ok();
+
+    let devices = list_input_devices()?;
+    Ok(devices
+        .into_iter()
+        .find(|(_, name)| Some(name) == default_name.as_ref()))
+}
+
+/// Identifies which input device to record from.
+///
+/// [`DeviceSelector::Index`] is an enumeration index into
+/// [`list_input_devices`], which is fragile across a hot-plug event —
+/// unplugging and replugging a USB mic (or any other device change) can
+/// reshuffle indices. [`DeviceSelector::Name`] matches [`Device::name`]
+/// directly instead, which stays stable across reconnects as long as the
+/// OS keeps reporting the same name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceSelector {
+    Index(u32),
+    Name(String),
+}
 
-    match device_index {
-        Some(idx) => {
+/// Finds the input device whose [`Device::name`] exactly matches `name`.
+pub fn get_device_by_name(name: &str) -> Result<Device, VaaniError> {
+    let host = cpal::default_host();
+    host.input_devices()
+        .map_err(|e| VaaniError::Audio(format!("Failed to enumerate input devices: {e}")))?
+        .find(|d| d.name().map(|n| n == name).unwrap_or(false))
+        .ok_or_else(|| VaaniError::Audio(format!("No input device named '{name}' found")))
+}
+
+/// Resolves the device to record from.
+///
+/// Falls back to the live system default whenever `selector` is `None` or
+/// no longer matches an enumerated device (e.g. a previously-selected USB
+/// mic was unplugged), rather than erroring out.
+fn get_device(selector: Option<DeviceSelector>) -> Result<Device, VaaniError> {
+    let host = cpal::default_host();
+
+    match selector {
+        Some(DeviceSelector::Index(idx)) => {
             let devices: Vec<Device> = host
                 .input_devices()
                 .map_err(|e| VaaniError::Audio(format!("Failed to enumerate input devices: {e}")))?
                 .collect();
-            devices
-                .into_iter()
-                .nth(idx as usize)
-                .ok_or_else(|| VaaniError::Audio(format!("No input device found at index {idx}")))
+            if let Some(device) = devices.into_iter().nth(idx as usize) {
+                return Ok(device);
+            }
+            tracing::warn!(idx, "Configured microphone not found, falling back to system default");
         }
-        None => host
-            .default_input_device()
-            .ok_or_else(|| VaaniError::Audio("No default input device found".to_string())),
+        Some(DeviceSelector::Name(name)) => match get_device_by_name(&name) {
+            Ok(device) => return Ok(device),
+            Err(_) => {
+                tracing::warn!(name, "Configured microphone not found, falling back to system default");
+            }
+        },
+        None => {}
+    }
+
+    host.default_input_device()
+        .ok_or_else(|| VaaniError::Audio("No default input device found".to_string()))
+}
+
+/// Picks a stream config for `device` as close as possible to
+/// `desired_rate`, in whatever sample format and channel layout the
+/// device actually supports.
+///
+/// Prefers a supported range that covers `desired_rate` exactly (locked
+/// to that rate); falls back to the device's own default config if none
+/// does, so recording still starts on devices whose default is e.g.
+/// stereo/`i16`/48 kHz rather than failing outright.
+fn negotiate_config(device: &Device, desired_rate: u32) -> Result<SupportedStreamConfig, VaaniError> {
+    let supported_configs = device
+        .supported_input_configs()
+        .map_err(|e| VaaniError::Audio(format!("Failed to query supported input configs: {e}")))?;
+
+    let exact_rate_match = supported_configs
+        .filter(|range| {
+            let range_rate = SampleRate(desired_rate);
+            range.min_sample_rate() <= range_rate && range_rate <= range.max_sample_rate()
+        })
+        .min_by_key(|range| range.channels())
+        .map(|range| range.with_sample_rate(SampleRate(desired_rate)));
+
+    match exact_rate_match {
+        Some(config) => Ok(config),
+        None => device
+            .default_input_config()
+            .map_err(|e| VaaniError::Audio(format!("Failed to get default input config: {e}"))),
     }
 }
 
+/// Averages interleaved multi-channel frames down to mono. A no-op
+/// (aside from the copy) when `channels <= 1`.
+fn downmix_to_mono(data: &[f32], channels: u16) -> Vec<f32> {
+    if channels <= 1 {
+        return data.to_vec();
+    }
+    let channels = channels as usize;
+    data.chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect()
+}
+
 /// Thread-safe buffer that accumulates audio samples from the cpal callback.
 #[derive(Clone)]
 pub struct AudioBuffer {
     samples: Arc<Mutex<Vec<f32>>>,
     level: Arc<Mutex<f32>>,
+    /// Linear gain multiplier applied to every incoming sample; see
+    /// `VaaniConfig::mic_sensitivity`.
+    sensitivity: Arc<Mutex<f32>>,
+    /// Post-gain RMS level below which a chunk is treated as silence; see
+    /// `VaaniConfig::noise_gate_threshold`.
+    noise_gate: Arc<Mutex<f32>>,
+    /// Rate actually negotiated with the device for the samples currently
+    /// accumulating, if recording has started at least once; see
+    /// [`AudioRecorder::negotiated_sample_rate`].
+    sample_rate: Arc<Mutex<Option<u32>>>,
+    /// Live subscribers registered via [`Self::subscribe`]; each gated
+    /// chunk is fanned out to every sender still connected. Dead senders
+    /// (their [`FrameReceiver`] dropped) are pruned on the next push.
+    subscribers: Arc<Mutex<Vec<mpsc::Sender<Vec<f32>>>>>,
 }
 
 impl Default for AudioBuffer {
@@ -64,6 +176,26 @@ impl AudioBuffer {
         Self {
             samples: Arc::new(Mutex::new(Vec::new())),
             level: Arc::new(Mutex::new(0.0)),
+            sensitivity: Arc::new(Mutex::new(1.0)),
+            noise_gate: Arc::new(Mutex::new(0.0)),
+            sample_rate: Arc::new(Mutex::new(None)),
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Sets the gain multiplier applied to samples before they're
+    /// accumulated or measured for the noise gate.
+    pub(crate) fn set_sensitivity(&self, factor: f32) {
+        if let Ok(mut s) = self.sensitivity.lock() {
+            *s = factor;
+        }
+    }
+
+    /// Sets the post-gain RMS threshold below which a chunk is gated to
+    /// silence. `0.0` disables the gate.
+    pub(crate) fn set_noise_gate(&self, threshold: f32) {
+        if let Ok(mut g) = self.noise_gate.lock() {
+            *g = threshold;
         }
     }
 
@@ -72,82 +204,265 @@ impl AudioBuffer {
         *self.level.lock().unwrap_or_else(|e| e.into_inner())
     }
 
+    /// Directly sets the current level without accumulating any samples.
+    ///
+    /// Used by the mic-test worker (see [`crate::audio::mic_test`]), which
+    /// mirrors a separate recorder's rolling level into this buffer purely
+    /// for display — it must never contribute samples that `take_samples`
+    /// would hand to the real recording pipeline.
+    pub(crate) fn set_level(&self, level: f32) {
+        if let Ok(mut l) = self.level.lock() {
+            *l = level;
+        }
+    }
+
+    /// Records the rate the samples now accumulating were actually
+    /// captured at, so downstream processing can resample from the truth
+    /// rather than the rate that was merely requested.
+    ///
+    /// Called by [`AudioRecorder::start`] once the device's config is
+    /// negotiated, which may differ from the rate the recorder was asked
+    /// for — see [`AudioRecorder::negotiated_sample_rate`].
+    pub(crate) fn set_sample_rate(&self, rate: u32) {
+        if let Ok(mut r) = self.sample_rate.lock() {
+            *r = Some(rate);
+        }
+    }
+
+    /// Returns the rate the currently-buffered samples were captured at,
+    /// or `None` if recording hasn't started yet.
+    pub fn sample_rate(&self) -> Option<u32> {
+        *self.sample_rate.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Drains and returns all accumulated samples, clearing the buffer.
     pub fn take_samples(&self) -> Vec<f32> {
         let mut buf = self.samples.lock().unwrap_or_else(|e| e.into_inner());
         std::mem::take(&mut *buf)
     }
 
-    /// Appends samples and updates the RMS level.
+    /// Subscribes to a live feed of captured chunks, for incremental
+    /// consumers (partial transcription, on-the-fly VAD, a moving level
+    /// meter) that can't wait for [`Self::take_samples`] at `stop()`.
+    ///
+    /// Each chunk is handed to every subscriber exactly as the cpal
+    /// callback produced it (after gain/gate, before accumulation), in
+    /// addition to — not instead of — the existing accumulate-and-take
+    /// behavior, so existing callers are unaffected.
+    pub fn subscribe(&self) -> FrameReceiver {
+        let (tx, rx) = mpsc::channel();
+        if let Ok(mut subs) = self.subscribers.lock() {
+            subs.push(tx);
+        }
+        FrameReceiver { rx }
+    }
+
+    /// Applies the sensitivity gain and noise gate, then appends the result
+    /// and updates the RMS level.
+    ///
+    /// A chunk whose post-gain RMS falls below the noise gate is zeroed out
+    /// entirely (level reads `0.0`, and silence — not the quiet input — is
+    /// what lands in the buffer) rather than merely excluded from the level
+    /// meter, so gated noise never reaches transcription either.
     fn push_samples(&self, data: &[f32]) {
-        // Update RMS level
-        if !data.is_empty() {
-            let sum_sq: f64 = data.iter().map(|&s| (s as f64) * (s as f64)).sum();
-            let rms = (sum_sq / data.len() as f64).sqrt() as f32;
-            if let Ok(mut level) = self.level.lock() {
-                *level = rms;
-            }
+        if data.is_empty() {
+            return;
+        }
+
+        let sensitivity = *self.sensitivity.lock().unwrap_or_else(|e| e.into_inner());
+        let gated: Vec<f32> = data
+            .iter()
+            .map(|&s| (s * sensitivity).clamp(-1.0, 1.0))
+            .collect();
+
+        let noise_gate = *self.noise_gate.lock().unwrap_or_else(|e| e.into_inner());
+        let level = rms(&gated);
+        let below_gate = level < noise_gate;
+
+        if let Ok(mut l) = self.level.lock() {
+            *l = if below_gate { 0.0 } else { level };
         }
 
-        // Accumulate samples
+        let to_accumulate = if below_gate {
+            vec![0.0; gated.len()]
+        } else {
+            gated
+        };
+
         if let Ok(mut buf) = self.samples.lock() {
-            buf.extend_from_slice(data);
+            buf.extend_from_slice(&to_accumulate);
+        }
+
+        if let Ok(mut subs) = self.subscribers.lock() {
+            subs.retain(|tx| tx.send(to_accumulate.clone()).is_ok());
         }
     }
 }
 
+/// The receiving end of an [`AudioBuffer::subscribe`] live feed.
+///
+/// Yields chunks exactly as the cpal callback produced them (after gain
+/// and the noise gate, before accumulation into [`AudioBuffer::take_samples`]),
+/// so a consumer can act on audio while recording is still in progress —
+/// partial transcription, on-the-fly VAD, a moving level meter — without
+/// waiting for `stop()`.
+pub struct FrameReceiver {
+    rx: mpsc::Receiver<Vec<f32>>,
+}
+
+impl FrameReceiver {
+    /// Blocks until the next chunk is available, or returns `None` once
+    /// the producing [`AudioBuffer`] (and every clone of it) is dropped.
+    pub fn recv(&self) -> Option<Vec<f32>> {
+        self.rx.recv().ok()
+    }
+
+    /// Returns the next chunk if one is already queued, without blocking.
+    /// `None` means "nothing new yet", not necessarily "disconnected" —
+    /// check [`Self::recv`] if you need to distinguish the two.
+    pub fn try_recv(&self) -> Option<Vec<f32>> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// Notifications about the recording device's health, emitted from the
+/// cpal error callback while a stream is live.
+///
+/// Mirrors [`crate::hotkey::HotkeyEvent`]'s shape: a plain enum delivered
+/// through a caller-supplied callback rather than a channel, since the
+/// cpal-imposed `'static` closure is the only hook available here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioDeviceEvent {
+    /// The device stopped being available mid-stream (e.g. unplugged).
+    /// Rebuilding the stream in place isn't possible from this callback —
+    /// it has no way back to the owning `&mut AudioRecorder` — so the
+    /// caller should prompt the user to pick a device and call
+    /// [`AudioRecorder::start`] again.
+    Disconnected,
+}
+
 /// Records audio from an input device using cpal.
 pub struct AudioRecorder {
     stream: Option<Stream>,
     buffer: AudioBuffer,
     sample_rate: u32,
+    /// Rate/channels actually negotiated with the device on the most
+    /// recent `start()`; `None` until then.
+    negotiated: Option<(u32, u16)>,
 }
 
 impl AudioRecorder {
-    /// Creates a new recorder targeting the specified device and sample rate.
+    /// Creates a new recorder targeting the specified device and sample
+    /// rate, with `mic_sensitivity` gain and `noise_gate_threshold` applied
+    /// to every captured chunk (see `VaaniConfig`).
     ///
     /// Does not start recording — call `start()` to begin.
-    pub fn new(device_index: Option<u32>, sample_rate: u32) -> Result<Self, VaaniError> {
-        let _device = get_device(device_index)?; // Validate device exists
+    pub fn new(
+        device: Option<DeviceSelector>,
+        sample_rate: u32,
+        mic_sensitivity: f32,
+        noise_gate_threshold: f32,
+    ) -> Result<Self, VaaniError> {
+        let _device = get_device(device)?; // Validate device exists
+        let buffer = AudioBuffer::new();
+        buffer.set_sensitivity(mic_sensitivity);
+        buffer.set_noise_gate(noise_gate_threshold);
         Ok(Self {
             stream: None,
-            buffer: AudioBuffer::new(),
+            buffer,
             sample_rate,
+            negotiated: None,
         })
     }
 
     /// Starts recording. Audio samples accumulate in the internal buffer.
-    pub fn start(&mut self, device_index: Option<u32>) -> Result<(), VaaniError> {
-        let device = get_device(device_index)?;
-
-        let config = StreamConfig {
-            channels: 1,
-            sample_rate: SampleRate(self.sample_rate),
-            buffer_size: cpal::BufferSize::Default,
-        };
+    ///
+    /// The device's own `SupportedStreamConfig` is queried rather than
+    /// assuming mono `f32` at the requested rate: the stream is built in
+    /// whatever sample format the device reports (`I16`, `U16`, or
+    /// `F32`), every sample is converted to `f32` in the callback, and
+    /// multi-channel frames are down-mixed to mono by averaging. The
+    /// rate/channels actually used are then available via
+    /// [`AudioRecorder::negotiated_sample_rate`]/[`AudioRecorder::negotiated_channels`].
+    ///
+    /// `on_device_event` is called from cpal's stream error callback when
+    /// the device disconnects mid-recording (see [`AudioDeviceEvent`]); it
+    /// won't fire for any other kind of stream error, which is merely
+    /// logged.
+    pub fn start(
+        &mut self,
+        device: Option<DeviceSelector>,
+        on_device_event: impl Fn(AudioDeviceEvent) + Send + 'static,
+    ) -> Result<(), VaaniError> {
+        let device = get_device(device)?;
+        let supported_config = negotiate_config(&device, self.sample_rate)?;
+
+        let channels = supported_config.channels();
+        let sample_format = supported_config.sample_format();
+        let config: StreamConfig = supported_config.into();
+        let negotiated_rate = config.sample_rate.0;
 
         let buffer = self.buffer.clone();
-        let err_fn = |err: cpal::StreamError| {
+        let err_fn = move |err: cpal::StreamError| {
             tracing::error!("Audio stream error: {err}");
+            if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+                on_device_event(AudioDeviceEvent::Disconnected);
+            }
         };
 
-        let stream = device
-            .build_input_stream(
+        let stream = match sample_format {
+            SampleFormat::F32 => device.build_input_stream(
                 &config,
                 move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    buffer.push_samples(data);
+                    buffer.push_samples(&downmix_to_mono(data, channels));
                 },
                 err_fn,
                 None,
-            )
-            .map_err(|e| VaaniError::Audio(format!("Failed to build audio stream: {e}")))?;
+            ),
+            SampleFormat::I16 => device.build_input_stream(
+                &config,
+                move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                    let as_f32: Vec<f32> =
+                        data.iter().map(|&s| s as f32 / i16::MAX as f32).collect();
+                    buffer.push_samples(&downmix_to_mono(&as_f32, channels));
+                },
+                err_fn,
+                None,
+            ),
+            SampleFormat::U16 => device.build_input_stream(
+                &config,
+                move |data: &[u16], _: &cpal::InputCallbackInfo| {
+                    let as_f32: Vec<f32> = data
+                        .iter()
+                        .map(|&s| (s as f32 - u16::MAX as f32 / 2.0) / (u16::MAX as f32 / 2.0))
+                        .collect();
+                    buffer.push_samples(&downmix_to_mono(&as_f32, channels));
+                },
+                err_fn,
+                None,
+            ),
+            other => {
+                return Err(VaaniError::Audio(format!(
+                    "Unsupported input sample format: {other:?}"
+                )))
+            }
+        }
+        .map_err(|e| VaaniError::Audio(format!("Failed to build audio stream: {e}")))?;
 
         stream
             .play()
             .map_err(|e| VaaniError::Audio(format!("Failed to start audio stream: {e}")))?;
 
-        tracing::info!(sample_rate = self.sample_rate, "Recording started");
+        tracing::info!(
+            requested_rate = self.sample_rate,
+            negotiated_rate,
+            channels,
+            ?sample_format,
+            "Recording started"
+        );
+        self.buffer.set_sample_rate(negotiated_rate);
         self.stream = Some(stream);
+        self.negotiated = Some((negotiated_rate, channels));
         Ok(())
     }
 
@@ -165,15 +480,51 @@ impl AudioRecorder {
         self.buffer.current_level()
     }
 
+    /// Subscribes to a live feed of captured chunks, for consumers that
+    /// need audio as it's recorded rather than waiting for [`Self::stop`];
+    /// see [`FrameReceiver`]. Can be called before or after [`Self::start`] —
+    /// chunks only flow once recording is actually underway.
+    pub fn subscribe(&self) -> FrameReceiver {
+        self.buffer.subscribe()
+    }
+
     /// Returns true if currently recording.
     pub fn is_recording(&self) -> bool {
         self.stream.is_some()
     }
 
-    /// Returns the configured sample rate.
+    /// Returns the sample rate requested in `new`, which is not
+    /// necessarily what the device ended up recording at — see
+    /// [`AudioRecorder::negotiated_sample_rate`].
     pub fn sample_rate(&self) -> u32 {
         self.sample_rate
     }
+
+    /// Returns the sample rate actually negotiated with the device on
+    /// the most recent `start()`, or `None` before the first `start()`.
+    pub fn negotiated_sample_rate(&self) -> Option<u32> {
+        self.negotiated.map(|(rate, _)| rate)
+    }
+
+    /// Returns the channel count actually negotiated with the device on
+    /// the most recent `start()` (always mono downstream — this is what
+    /// the device itself produces before down-mixing), or `None` before
+    /// the first `start()`.
+    pub fn negotiated_channels(&self) -> Option<u16> {
+        self.negotiated.map(|(_, channels)| channels)
+    }
+
+    /// Writes `samples` to `path` as a 16-bit PCM mono WAV file, stamped
+    /// with the rate actually negotiated with the device (falling back to
+    /// the requested rate if recording hasn't started yet).
+    ///
+    /// For archiving/debugging captures or feeding file-based STT backends
+    /// directly — the live transcription pipeline encodes in memory via
+    /// [`super::processing::encode_wav`] instead of going through disk.
+    pub fn save_wav(&self, path: &std::path::Path, samples: &[f32]) -> Result<(), VaaniError> {
+        let sample_rate = self.negotiated_sample_rate().unwrap_or(self.sample_rate);
+        super::processing::save_wav(path, samples, sample_rate)
+    }
 }
 
 #[cfg(test)]
@@ -224,6 +575,116 @@ mod tests {
         assert_eq!(buf.current_level(), 0.0);
     }
 
+    #[test]
+    fn audio_buffer_sensitivity_boosts_level_and_samples() {
+        let buf = AudioBuffer::new();
+        buf.set_sensitivity(2.0);
+        buf.push_samples(&[0.2; 100]);
+        let level = buf.current_level();
+        assert!((level - 0.4).abs() < 0.01, "Expected ~0.4, got {level}");
+
+        let samples = buf.take_samples();
+        assert!((samples[0] - 0.4).abs() < 0.01);
+    }
+
+    #[test]
+    fn audio_buffer_sensitivity_clamps_to_full_scale() {
+        let buf = AudioBuffer::new();
+        buf.set_sensitivity(10.0);
+        buf.push_samples(&[0.5; 10]);
+        let samples = buf.take_samples();
+        assert!(samples.iter().all(|&s| s <= 1.0));
+    }
+
+    #[test]
+    fn audio_buffer_noise_gate_zeroes_quiet_chunks() {
+        let buf = AudioBuffer::new();
+        buf.set_noise_gate(0.1);
+        buf.push_samples(&[0.05; 100]);
+
+        assert_eq!(buf.current_level(), 0.0);
+        let samples = buf.take_samples();
+        assert_eq!(samples.len(), 100);
+        assert!(samples.iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn audio_buffer_noise_gate_passes_loud_chunks() {
+        let buf = AudioBuffer::new();
+        buf.set_noise_gate(0.1);
+        buf.push_samples(&[0.5; 100]);
+
+        let level = buf.current_level();
+        assert!((level - 0.5).abs() < 0.01, "Expected ~0.5, got {level}");
+        let samples = buf.take_samples();
+        assert!((samples[0] - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn subscribe_receives_pushed_chunks_without_draining_take_samples() {
+        let buf = AudioBuffer::new();
+        let rx = buf.subscribe();
+
+        buf.push_samples(&[0.1, 0.2, 0.3]);
+
+        let chunk = rx.try_recv().expect("a chunk should be queued");
+        assert_eq!(chunk, vec![0.1, 0.2, 0.3]);
+        assert!(rx.try_recv().is_none(), "only one chunk was pushed");
+
+        // Subscribing doesn't disturb the batch accumulate-and-take path.
+        assert_eq!(buf.take_samples(), vec![0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn subscribe_fans_out_to_multiple_receivers() {
+        let buf = AudioBuffer::new();
+        let rx1 = buf.subscribe();
+        let rx2 = buf.subscribe();
+
+        buf.push_samples(&[0.4, 0.5]);
+
+        assert_eq!(rx1.try_recv(), Some(vec![0.4, 0.5]));
+        assert_eq!(rx2.try_recv(), Some(vec![0.4, 0.5]));
+    }
+
+    #[test]
+    fn subscribe_chunks_reflect_the_noise_gate_like_take_samples_does() {
+        let buf = AudioBuffer::new();
+        buf.set_noise_gate(0.5);
+        let rx = buf.subscribe();
+
+        buf.push_samples(&[0.01; 10]);
+
+        let chunk = rx.try_recv().expect("a chunk should be queued");
+        assert!(chunk.iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn dropped_receiver_is_pruned_on_next_push() {
+        let buf = AudioBuffer::new();
+        let rx = buf.subscribe();
+        drop(rx);
+
+        // Should not panic even though the receiver is gone.
+        buf.push_samples(&[0.1, 0.2]);
+        assert_eq!(buf.subscribers.lock().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn audio_buffer_sample_rate_is_none_before_recording() {
+        let buf = AudioBuffer::new();
+        assert_eq!(buf.sample_rate(), None);
+    }
+
+    #[test]
+    fn audio_buffer_sample_rate_reflects_last_set_value() {
+        let buf = AudioBuffer::new();
+        buf.set_sample_rate(44_100);
+        assert_eq!(buf.sample_rate(), Some(44_100));
+        buf.set_sample_rate(48_000);
+        assert_eq!(buf.sample_rate(), Some(48_000));
+    }
+
     #[test]
     fn list_input_devices_does_not_panic() {
         // This test just verifies the function doesn't panic.
@@ -231,4 +692,56 @@ mod tests {
         let result = list_input_devices();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn default_input_device_does_not_panic() {
+        // No audio hardware is guaranteed in CI, so this may return `None` —
+        // just verify it doesn't error or panic.
+        let result = default_input_device();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn get_device_falls_back_to_default_for_out_of_range_index() {
+        // An index far beyond any real device list should fall back to the
+        // system default rather than erroring, same as `None`.
+        let with_bogus_index = get_device(Some(DeviceSelector::Index(u32::MAX)));
+        let with_none = get_device(None);
+        assert_eq!(with_bogus_index.is_ok(), with_none.is_ok());
+    }
+
+    #[test]
+    fn get_device_falls_back_to_default_for_unknown_name() {
+        let with_bogus_name = get_device(Some(DeviceSelector::Name(
+            "definitely-not-a-real-device-name".to_string(),
+        )));
+        let with_none = get_device(None);
+        assert_eq!(with_bogus_name.is_ok(), with_none.is_ok());
+    }
+
+    #[test]
+    fn get_device_by_name_errors_for_unknown_name() {
+        let result = get_device_by_name("definitely-not-a-real-device-name");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn downmix_to_mono_is_a_no_op_for_mono_input() {
+        let data = [0.1, 0.2, 0.3];
+        assert_eq!(downmix_to_mono(&data, 1), vec![0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn downmix_to_mono_averages_stereo_frames() {
+        let data = [1.0, -1.0, 0.5, 0.5];
+        assert_eq!(downmix_to_mono(&data, 2), vec![0.0, 0.5]);
+    }
+
+    #[test]
+    fn downmix_to_mono_averages_a_trailing_partial_frame() {
+        // Three channels but a trailing sample short a full frame — the
+        // partial frame should still average over the samples it has.
+        let data = [0.3, 0.3, 0.3, 0.9];
+        assert_eq!(downmix_to_mono(&data, 3), vec![0.3, 0.9]);
+    }
 }
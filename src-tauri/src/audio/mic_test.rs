@@ -0,0 +1,194 @@
+//! Live microphone level-test worker.
+//!
+//! `AudioRecorder` holds a cpal `Stream`, which is `!Send` and so cannot live
+//! in Tauri's shared `VaaniApp` state. Instead we spawn one dedicated OS
+//! thread that owns the recorder for the lifetime of a test session, and
+//! drive it from the command threads over an `mpsc` channel: `Start` builds
+//! and starts an `AudioRecorder` on the worker thread, `Stop` tears it down.
+//! While a test is running, the worker polls the recorder's rolling RMS
+//! level and mirrors it into the `AudioBuffer` level that
+//! `VaaniApp::current_mic_level` already reads — the onboarding UI doesn't
+//! need to know a worker thread is involved at all.
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use super::capture::{AudioBuffer, AudioRecorder, DeviceSelector};
+use crate::error::VaaniError;
+
+/// How often the worker polls the recorder's level while a test is running.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Commands accepted by the mic-test worker thread.
+enum MicTestCommand {
+    Start {
+        device_index: Option<u32>,
+        sample_rate: u32,
+        mic_sensitivity: f32,
+        noise_gate_threshold: f32,
+    },
+    Stop,
+}
+
+/// Handle to the mic-test worker thread, held by [`crate::app::VaaniApp`].
+///
+/// Cloning is cheap (it's just a channel sender); every clone talks to the
+/// same worker thread.
+#[derive(Clone)]
+pub struct MicTestHandle {
+    commands: mpsc::Sender<MicTestCommand>,
+}
+
+impl MicTestHandle {
+    /// Spawns the worker thread and returns a handle to it. The worker
+    /// mirrors whatever device is under test into `level`.
+    pub fn spawn(level: AudioBuffer) -> Result<Self, VaaniError> {
+        let (tx, rx) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("vaani-mic-test".into())
+            .spawn(move || worker_loop(rx, level))
+            .map_err(|e| VaaniError::Audio(format!("failed to spawn mic-test thread: {e}")))?;
+
+        Ok(Self { commands: tx })
+    }
+
+    /// Starts a mic-test session on the given device (or the default input
+    /// device if `None`), applying `mic_sensitivity` gain and
+    /// `noise_gate_threshold` the same way a real recording would, so the
+    /// level the user sees is the one that will actually be used.
+    pub fn start(
+        &self,
+        device_index: Option<u32>,
+        sample_rate: u32,
+        mic_sensitivity: f32,
+        noise_gate_threshold: f32,
+    ) -> Result<(), VaaniError> {
+        self.commands
+            .send(MicTestCommand::Start {
+                device_index,
+                sample_rate,
+                mic_sensitivity,
+                noise_gate_threshold,
+            })
+            .map_err(|_| VaaniError::Audio("Mic-test worker thread is not running".to_string()))
+    }
+
+    /// Stops the current mic-test session, if any.
+    pub fn stop(&self) -> Result<(), VaaniError> {
+        self.commands
+            .send(MicTestCommand::Stop)
+            .map_err(|_| VaaniError::Audio("Mic-test worker thread is not running".to_string()))
+    }
+}
+
+/// Runs on the dedicated mic-test thread. Owns the `!Send` `AudioRecorder`
+/// for the lifetime of the thread, so it never has to cross a thread
+/// boundary.
+fn worker_loop(commands: mpsc::Receiver<MicTestCommand>, level: AudioBuffer) {
+    let mut recorder: Option<AudioRecorder> = None;
+
+    loop {
+        // Block waiting for a command while idle; poll with a timeout while
+        // a test is running so the level keeps getting refreshed.
+        let received = if recorder.is_some() {
+            commands.recv_timeout(POLL_INTERVAL)
+        } else {
+            commands.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected)
+        };
+
+        match received {
+            Ok(MicTestCommand::Start {
+                device_index,
+                sample_rate,
+                mic_sensitivity,
+                noise_gate_threshold,
+            }) => {
+                if let Some(mut old) = recorder.take() {
+                    old.stop();
+                }
+
+                match start_recorder(device_index, sample_rate, mic_sensitivity, noise_gate_threshold) {
+                    Ok(r) => {
+                        tracing::info!(?device_index, "Mic test started");
+                        recorder = Some(r);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to start mic test: {e}");
+                        level.set_level(0.0);
+                    }
+                }
+            }
+            Ok(MicTestCommand::Stop) => {
+                if let Some(mut r) = recorder.take() {
+                    r.stop();
+                    tracing::info!("Mic test stopped");
+                }
+                level.set_level(0.0);
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if let Some(r) = &recorder {
+                    level.set_level(r.current_level());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
+
+fn start_recorder(
+    device_index: Option<u32>,
+    sample_rate: u32,
+    mic_sensitivity: f32,
+    noise_gate_threshold: f32,
+) -> Result<AudioRecorder, VaaniError> {
+    let device = device_index.map(DeviceSelector::Index);
+    let mut recorder = AudioRecorder::new(
+        device.clone(),
+        sample_rate,
+        mic_sensitivity,
+        noise_gate_threshold,
+    )?;
+    recorder.start(device, |event| {
+        tracing::warn!(?event, "mic-test device event");
+    })?;
+    Ok(recorder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn spawn_returns_a_usable_handle() {
+        let handle = MicTestHandle::spawn(AudioBuffer::new()).expect("spawn should succeed");
+        // Stop on a session that was never started should be a harmless no-op.
+        handle.stop().expect("stop should send successfully");
+    }
+
+    #[test]
+    fn stop_resets_level_to_zero() {
+        let buffer = AudioBuffer::new();
+        buffer.set_level(0.8);
+        let handle = MicTestHandle::spawn(buffer.clone()).expect("spawn should succeed");
+
+        handle.stop().expect("stop should send successfully");
+
+        // Give the worker a moment to process the command.
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while buffer.current_level() != 0.0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(buffer.current_level(), 0.0);
+    }
+
+    #[test]
+    fn commands_after_handle_and_thread_are_dropped_do_not_panic() {
+        let handle = MicTestHandle::spawn(AudioBuffer::new()).expect("spawn should succeed");
+        drop(handle);
+        // Nothing to assert — the worker thread should exit cleanly once its
+        // receiver is disconnected, without panicking.
+    }
+}
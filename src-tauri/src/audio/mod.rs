@@ -0,0 +1,8 @@
+//! Audio capture, processing, voice activity detection, and the mic-test
+//! worker thread.
+
+pub mod capture;
+pub mod mic_test;
+pub mod processing;
+pub mod ring_buffer;
+pub mod vad;
@@ -1,8 +1,12 @@
-//! Audio processing utilities: gain normalization and WAV encoding.
+//! Audio processing utilities: gain normalization, loudness normalization,
+//! noise reduction, and WAV encoding.
 
 use std::io::Cursor;
+use std::path::Path;
 
 use hound::{SampleFormat, WavSpec, WavWriter};
+use realfft::num_complex::Complex;
+use realfft::RealFftPlanner;
 use tracing::{debug, warn};
 
 use crate::error::VaaniError;
@@ -67,7 +71,470 @@ pub fn normalize_gain(samples: &[f32], target_db: f32) -> Vec<f32> {
         .collect()
 }
 
-// ── WAV encoding ────────────────────────────────────────────────────────────
+// ── Loudness normalisation (EBU R128 / ITU-R BS.1770) ───────────────────────
+
+/// A direct-form-I biquad IIR filter, used for the two K-weighting stages
+/// ahead of LUFS measurement. State is kept in `f64` since the filters'
+/// poles sit close to the unit circle, where `f32` rounding would audibly
+/// drift the frequency response.
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    x1: f64,
+    x2: f64,
+    y1: f64,
+    y2: f64,
+}
+
+impl Biquad {
+    fn process(&mut self, x0: f64) -> f64 {
+        let y0 =
+            self.b0 * x0 + self.b1 * self.x1 + self.b2 * self.x2 - self.a1 * self.y1 - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x0;
+        self.y2 = self.y1;
+        self.y1 = y0;
+        y0
+    }
+}
+
+/// The K-weighting "head" filter: a high shelf boosting frequencies above
+/// ~1.5 kHz by ~4 dB, approximating the acoustic effect of the human head.
+/// Coefficients are ITU-R BS.1770's standard values, bilinear-transformed
+/// for `sample_rate`.
+fn k_weighting_head_filter(sample_rate: u32) -> Biquad {
+    let f0 = 1681.9744509555319_f64;
+    let gain_db = 3.99984385397_f64;
+    let q = 0.7071752369554193_f64;
+
+    let k = (std::f64::consts::PI * f0 / sample_rate as f64).tan();
+    let vh = 10_f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+
+    Biquad {
+        b0: (vh + vb * k / q + k * k) / a0,
+        b1: 2.0 * (k * k - vh) / a0,
+        b2: (vh - vb * k / q + k * k) / a0,
+        a1: 2.0 * (k * k - 1.0) / a0,
+        a2: (1.0 - k / q + k * k) / a0,
+        x1: 0.0,
+        x2: 0.0,
+        y1: 0.0,
+        y2: 0.0,
+    }
+}
+
+/// The K-weighting "RLB" (revised low-frequency B) filter: a high-pass
+/// rolling off below ~38 Hz, removing rumble that shouldn't count toward
+/// perceived loudness. Coefficients are ITU-R BS.1770's standard values,
+/// bilinear-transformed for `sample_rate`.
+fn k_weighting_rlb_filter(sample_rate: u32) -> Biquad {
+    let f0 = 38.13547087613982_f64;
+    let q = 0.5003270373253953_f64;
+    let k = (std::f64::consts::PI * f0 / sample_rate as f64).tan();
+    let a0 = 1.0 + k / q + k * k;
+
+    Biquad {
+        b0: 1.0,
+        b1: -2.0,
+        b2: 1.0,
+        a1: 2.0 * (k * k - 1.0) / a0,
+        a2: (1.0 - k / q + k * k) / a0,
+        x1: 0.0,
+        x2: 0.0,
+        y1: 0.0,
+        y2: 0.0,
+    }
+}
+
+/// Applies the two-stage K-weighting filter ITU-R BS.1770 measures loudness
+/// through: the head shelf followed by the RLB high-pass.
+fn k_weight(samples: &[f32], sample_rate: u32) -> Vec<f64> {
+    let mut head = k_weighting_head_filter(sample_rate);
+    let mut rlb = k_weighting_rlb_filter(sample_rate);
+    samples
+        .iter()
+        .map(|&s| rlb.process(head.process(s as f64)))
+        .collect()
+}
+
+/// Block length and hop for LUFS measurement: 400ms blocks, 100ms hop (75%
+/// overlap), per ITU-R BS.1770.
+const LUFS_BLOCK_MS: f32 = 400.0;
+const LUFS_HOP_MS: f32 = 100.0;
+
+/// Blocks quieter than this are silence/noise and never count toward
+/// integrated loudness, regardless of the rest of the signal.
+const LUFS_ABSOLUTE_GATE: f64 = -70.0;
+
+/// Blocks more than this many LU below the absolute-gated mean are gated out
+/// too, so a loud section isn't dragged down by quiet passages.
+const LUFS_RELATIVE_GATE_OFFSET: f64 = -10.0;
+
+/// Converts a block's mean square energy to loudness in LUFS.
+fn block_loudness(mean_square: f64) -> f64 {
+    -0.691 + 10.0 * mean_square.log10()
+}
+
+/// Measures the integrated loudness of `samples` in LUFS via ITU-R BS.1770:
+/// K-weight, split into overlapping blocks, then gate out silent and
+/// relatively-quiet blocks before averaging what's left.
+///
+/// Returns `None` if there isn't a full block's worth of audio, or if every
+/// block is gated out (e.g. the whole signal is below the absolute gate).
+fn integrated_loudness(samples: &[f32], sample_rate: u32) -> Option<f64> {
+    let block_len = ((sample_rate as f32 * LUFS_BLOCK_MS / 1000.0) as usize).max(1);
+    let hop_len = ((sample_rate as f32 * LUFS_HOP_MS / 1000.0) as usize).max(1);
+
+    let weighted = k_weight(samples, sample_rate);
+    if weighted.len() < block_len {
+        return None;
+    }
+
+    let mut block_energies = Vec::new();
+    let mut start = 0;
+    while start + block_len <= weighted.len() {
+        let sum_sq: f64 = weighted[start..start + block_len].iter().map(|&s| s * s).sum();
+        block_energies.push(sum_sq / block_len as f64);
+        start += hop_len;
+    }
+
+    let absolute_gated: Vec<f64> = block_energies
+        .into_iter()
+        .filter(|&ms| ms > 0.0 && block_loudness(ms) > LUFS_ABSOLUTE_GATE)
+        .collect();
+    if absolute_gated.is_empty() {
+        return None;
+    }
+
+    let mean_energy = absolute_gated.iter().sum::<f64>() / absolute_gated.len() as f64;
+    let relative_threshold = block_loudness(mean_energy) + LUFS_RELATIVE_GATE_OFFSET;
+    let relative_gated: Vec<f64> = absolute_gated
+        .into_iter()
+        .filter(|&ms| block_loudness(ms) > relative_threshold)
+        .collect();
+    if relative_gated.is_empty() {
+        return None;
+    }
+
+    let final_mean_energy = relative_gated.iter().sum::<f64>() / relative_gated.len() as f64;
+    Some(block_loudness(final_mean_energy))
+}
+
+/// Normalize `samples` to `target_lufs` integrated loudness (EBU R128 /
+/// ITU-R BS.1770), e.g. `-23.0` for broadcast-standard loudness.
+///
+/// Unlike [`normalize_gain`]'s flat RMS target, this measures loudness
+/// through K-weighting and gated block averaging first, so it tracks
+/// perceived loudness instead of over-boosting bass-heavy signals the way a
+/// plain RMS target would.
+///
+/// Returns `samples` unchanged if empty, silent, shorter than one 400ms
+/// block, or if every block is gated out of the measurement. After gain is
+/// applied every sample is clamped to `[-1.0, 1.0]`.
+pub fn normalize_loudness(samples: &[f32], sample_rate: u32, target_lufs: f32) -> Vec<f32> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+
+    if rms(samples) < SILENCE_THRESHOLD {
+        debug!("Input is silent, returning unchanged");
+        return samples.to_vec();
+    }
+
+    let Some(integrated) = integrated_loudness(samples, sample_rate) else {
+        debug!("Not enough audio to measure integrated loudness, returning unchanged");
+        return samples.to_vec();
+    };
+
+    let gain = 10_f64.powf((target_lufs as f64 - integrated) / 20.0) as f32;
+
+    debug!(
+        integrated, target_lufs, gain, "Applying LUFS loudness normalization"
+    );
+
+    samples.iter().map(|&s| (s * gain).clamp(-1.0, 1.0)).collect()
+}
+
+// ── Noise reduction ─────────────────────────────────────────────────────────
+
+/// Frame size (in samples) used by [`denoise`]'s short-time Fourier transform.
+const DENOISE_FRAME_SIZE: usize = 512;
+
+/// Hop between successive frames — half the frame size gives 50% overlap,
+/// which keeps a Hann-windowed overlap-add sum close to constant.
+const DENOISE_HOP_SIZE: usize = DENOISE_FRAME_SIZE / 2;
+
+/// Leading duration assumed to be noise-only, used to estimate the noise
+/// magnitude profile subtracted from every frame.
+const DENOISE_NOISE_ESTIMATE_MS: f32 = 200.0;
+
+/// A symmetric Hann window of length `len`.
+fn hann_window(len: usize) -> Vec<f32> {
+    (0..len)
+        .map(|n| 0.5 * (1.0 - (2.0 * std::f32::consts::PI * n as f32 / (len - 1) as f32).cos()))
+        .collect()
+}
+
+/// Reduce stationary background noise via single-channel spectral subtraction,
+/// improving VAD speech-probability estimates on noisy recordings.
+///
+/// Frames `samples` into overlapping Hann windows, estimates a noise
+/// magnitude profile from the first `DENOISE_NOISE_ESTIMATE_MS` of audio
+/// (assumed noise-only), then subtracts `alpha` times that profile from every
+/// frame's magnitude spectrum — floored at `beta` times the frame's own
+/// magnitude to avoid negative values — before reconstructing via inverse FFT
+/// and overlap-add using the original phase.
+///
+/// * `alpha` — over-subtraction factor (typically 1.5-2.0; higher removes
+///   more noise but risks distorting speech).
+/// * `beta` — spectral floor as a fraction of the original magnitude
+///   (typically ~0.02).
+///
+/// Recordings shorter than the noise-estimation window are returned
+/// unchanged — there isn't enough noise-only audio to build a profile from.
+pub fn denoise(samples: &[f32], sample_rate: u32, alpha: f32, beta: f32) -> Vec<f32> {
+    let noise_window_samples = ((sample_rate as f32 * DENOISE_NOISE_ESTIMATE_MS / 1000.0) as usize)
+        .max(DENOISE_FRAME_SIZE);
+    if samples.len() < noise_window_samples {
+        debug!(
+            len = samples.len(),
+            noise_window_samples, "Recording too short to estimate a noise profile, skipping denoise"
+        );
+        return samples.to_vec();
+    }
+
+    let window = hann_window(DENOISE_FRAME_SIZE);
+    let mut planner = RealFftPlanner::<f32>::new();
+    let forward = planner.plan_fft_forward(DENOISE_FRAME_SIZE);
+    let inverse = planner.plan_fft_inverse(DENOISE_FRAME_SIZE);
+    let num_bins = DENOISE_FRAME_SIZE / 2 + 1;
+
+    let mut frame_spectra: Vec<Vec<Complex<f32>>> = Vec::new();
+    let mut frame_starts: Vec<usize> = Vec::new();
+
+    let mut start = 0;
+    while start + DENOISE_FRAME_SIZE <= samples.len() {
+        let mut windowed = forward.make_input_vec();
+        for (dst, (&s, &w)) in windowed
+            .iter_mut()
+            .zip(samples[start..start + DENOISE_FRAME_SIZE].iter().zip(window.iter()))
+        {
+            *dst = s * w;
+        }
+        let mut spectrum = forward.make_output_vec();
+        if forward.process(&mut windowed, &mut spectrum).is_err() {
+            warn!("Forward FFT failed during denoise, returning input unchanged");
+            return samples.to_vec();
+        }
+        frame_starts.push(start);
+        frame_spectra.push(spectrum);
+        start += DENOISE_HOP_SIZE;
+    }
+
+    if frame_spectra.is_empty() {
+        return samples.to_vec();
+    }
+
+    // Average the magnitude spectra of frames fully inside the leading
+    // noise-only window to build the noise profile.
+    let mut noise_profile = vec![0.0_f32; num_bins];
+    let mut noise_frame_count = 0usize;
+    for (spectrum, &frame_start) in frame_spectra.iter().zip(frame_starts.iter()) {
+        if frame_start + DENOISE_FRAME_SIZE > noise_window_samples {
+            break;
+        }
+        for (profile_bin, bin) in noise_profile.iter_mut().zip(spectrum.iter()) {
+            *profile_bin += bin.norm();
+        }
+        noise_frame_count += 1;
+    }
+    if noise_frame_count > 0 {
+        for bin in &mut noise_profile {
+            *bin /= noise_frame_count as f32;
+        }
+    }
+
+    let mut output = vec![0.0_f32; samples.len()];
+    let nyquist_bin = num_bins - 1;
+    for (spectrum, &frame_start) in frame_spectra.iter().zip(frame_starts.iter()) {
+        let mut subtracted = inverse.make_input_vec();
+        for (i, ((bin, &noise_mag), dst)) in spectrum
+            .iter()
+            .zip(noise_profile.iter())
+            .zip(subtracted.iter_mut())
+            .enumerate()
+        {
+            let mag = bin.norm();
+            let floored = (mag - alpha * noise_mag).max(beta * mag);
+            *dst = if i == 0 || i == nyquist_bin {
+                // The DC and Nyquist bins of a real FFT are purely real;
+                // reconstructing them via from_polar's phase can leave a
+                // spurious epsilon-sized imaginary part (from f32::consts::PI
+                // not being exact), which the inverse transform rejects.
+                Complex::new(floored * bin.re.signum(), 0.0)
+            } else {
+                Complex::from_polar(floored, bin.arg())
+            };
+        }
+
+        let mut frame_out = inverse.make_output_vec();
+        if inverse.process(&mut subtracted, &mut frame_out).is_err() {
+            warn!("Inverse FFT failed during denoise, returning input unchanged");
+            return samples.to_vec();
+        }
+
+        // realfft's inverse transform is unnormalised, so divide by frame size.
+        for (i, &s) in frame_out.iter().enumerate() {
+            output[frame_start + i] += s / DENOISE_FRAME_SIZE as f32;
+        }
+    }
+
+    debug!(
+        frames = frame_spectra.len(),
+        noise_frame_count, alpha, beta, "Applied spectral-subtraction denoising"
+    );
+
+    output
+}
+
+// ── Resampling ──────────────────────────────────────────────────────────────
+
+/// Sample rate Whisper (and the other STT backends) expect their audio
+/// encoded at. [`resample`] is always called with this as `to_hz` before
+/// [`encode_wav`].
+pub const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Frame size (in samples) used by [`resample`]'s anti-alias low-pass filter.
+const RESAMPLE_FRAME_SIZE: usize = 1024;
+
+/// Hop between successive frames — half the frame size gives 50% overlap,
+/// matching [`denoise`]'s overlap-add scheme.
+const RESAMPLE_HOP_SIZE: usize = RESAMPLE_FRAME_SIZE / 2;
+
+/// Band-limit `samples` to `cutoff_hz` via a brick-wall low-pass filter
+/// applied per-frame in the frequency domain, then reconstructed with
+/// Hann-windowed overlap-add — the same STFT scheme [`denoise`] uses, but
+/// zeroing bins above the cutoff instead of subtracting a noise profile.
+///
+/// Any trailing samples too short to fill a final frame pass through
+/// unfiltered rather than being dropped.
+fn low_pass_filter(samples: &[f32], sample_rate: u32, cutoff_hz: f32) -> Vec<f32> {
+    if samples.len() < RESAMPLE_FRAME_SIZE {
+        return samples.to_vec();
+    }
+
+    let window = hann_window(RESAMPLE_FRAME_SIZE);
+    let mut planner = RealFftPlanner::<f32>::new();
+    let forward = planner.plan_fft_forward(RESAMPLE_FRAME_SIZE);
+    let inverse = planner.plan_fft_inverse(RESAMPLE_FRAME_SIZE);
+    let num_bins = RESAMPLE_FRAME_SIZE / 2 + 1;
+
+    let bin_hz = sample_rate as f32 / RESAMPLE_FRAME_SIZE as f32;
+    let cutoff_bin = ((cutoff_hz / bin_hz).round() as usize).min(num_bins - 1);
+
+    let mut output = vec![0.0_f32; samples.len()];
+    let mut start = 0;
+    while start + RESAMPLE_FRAME_SIZE <= samples.len() {
+        let mut windowed = forward.make_input_vec();
+        for (dst, (&s, &w)) in windowed
+            .iter_mut()
+            .zip(samples[start..start + RESAMPLE_FRAME_SIZE].iter().zip(window.iter()))
+        {
+            *dst = s * w;
+        }
+        let mut spectrum = forward.make_output_vec();
+        if forward.process(&mut windowed, &mut spectrum).is_err() {
+            warn!("Forward FFT failed during resampling, returning input unchanged");
+            return samples.to_vec();
+        }
+
+        for bin in spectrum.iter_mut().skip(cutoff_bin + 1) {
+            *bin = Complex::new(0.0, 0.0);
+        }
+
+        let mut frame_out = inverse.make_output_vec();
+        if inverse.process(&mut spectrum, &mut frame_out).is_err() {
+            warn!("Inverse FFT failed during resampling, returning input unchanged");
+            return samples.to_vec();
+        }
+
+        // realfft's inverse transform is unnormalised, so divide by frame size.
+        for (i, &s) in frame_out.iter().enumerate() {
+            output[start + i] += s / RESAMPLE_FRAME_SIZE as f32;
+        }
+        start += RESAMPLE_HOP_SIZE;
+    }
+
+    if start < samples.len() {
+        output[start..].copy_from_slice(&samples[start..]);
+    }
+
+    output
+}
+
+/// Linearly interpolate `samples` from `from_hz` to `to_hz`.
+///
+/// Only used for non-integer ratios; the integer case in [`resample`]
+/// decimates the already band-limited signal directly, which is both
+/// cheaper and exact.
+fn linear_resample(samples: &[f32], from_hz: u32, to_hz: u32) -> Vec<f32> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+
+    let ratio = from_hz as f64 / to_hz as f64;
+    let out_len = ((samples.len() as f64) / ratio).round() as usize;
+
+    (0..out_len)
+        .map(|i| {
+            let src_pos = i as f64 * ratio;
+            let idx = src_pos.floor() as usize;
+            let frac = (src_pos - idx as f64) as f32;
+            if idx + 1 < samples.len() {
+                samples[idx] * (1.0 - frac) + samples[idx + 1] * frac
+            } else {
+                samples[idx.min(samples.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+/// Resample `samples` from `from_hz` to `to_hz`, band-limiting first so
+/// downsampling can't alias high frequencies back into the audible band.
+///
+/// For the common integer-ratio case (e.g. 48000 -> 16000), the band-limited
+/// signal is decimated directly by taking every `from_hz / to_hz`-th sample.
+/// Otherwise the band-limited signal is linearly interpolated to the new
+/// rate. Upsampling (`to_hz > from_hz`) skips the low-pass filter, since the
+/// new Nyquist is higher than the input's and there's nothing to alias.
+///
+/// Returns `samples` unchanged if the rates already match or `samples` is
+/// empty.
+pub fn resample(samples: &[f32], from_hz: u32, to_hz: u32) -> Vec<f32> {
+    if samples.is_empty() || from_hz == to_hz {
+        return samples.to_vec();
+    }
+
+    let band_limited = if to_hz < from_hz {
+        low_pass_filter(samples, from_hz, to_hz as f32 / 2.0)
+    } else {
+        samples.to_vec()
+    };
+
+    if to_hz < from_hz && from_hz % to_hz == 0 {
+        let ratio = (from_hz / to_hz) as usize;
+        band_limited.iter().step_by(ratio).copied().collect()
+    } else {
+        linear_resample(&band_limited, from_hz, to_hz)
+    }
+}
+
+// ── WAV encoding/decoding ────────────────────────────────────────────────────
 
 /// Encode float-32 audio samples to an in-memory WAV file (PCM 16-bit, mono).
 ///
@@ -116,6 +583,91 @@ pub fn encode_wav(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, VaaniErr
     Ok(buf)
 }
 
+/// Writes float-32 audio samples to `path` as a 16-bit PCM mono WAV file.
+///
+/// For archiving/debugging captures, feeding file-based STT backends, or
+/// verifying mic input without re-recording — the live transcription
+/// pipeline uses [`encode_wav`] directly instead of going through disk.
+pub fn save_wav(path: &Path, samples: &[f32], sample_rate: u32) -> Result<(), VaaniError> {
+    let wav_bytes = encode_wav(samples, sample_rate)?;
+    std::fs::write(path, wav_bytes)
+        .map_err(|e| VaaniError::Audio(format!("Failed to write WAV file '{}': {e}", path.display())))
+}
+
+/// Decodes an arbitrary user-supplied WAV file into mono `f32` samples in
+/// `[-1.0, 1.0]`, plus its native sample rate.
+///
+/// Accepts any PCM bit depth hound supports (8/16/24/32-bit) as well as
+/// 32-bit float, and downmixes multi-channel input by averaging all
+/// channels per frame. Pair with [`resample`] to reach
+/// [`WHISPER_SAMPLE_RATE`] if the file isn't already at that rate — that's
+/// the same resampler the live recording pipeline uses, so a decoded file
+/// goes through an identical band-limiting step before transcription.
+pub fn decode_wav(bytes: &[u8]) -> Result<(Vec<f32>, u32), VaaniError> {
+    let cursor = Cursor::new(bytes);
+    let mut reader = hound::WavReader::new(cursor)
+        .map_err(|e| VaaniError::Audio(format!("Failed to read WAV file: {e}")))?;
+
+    let spec = reader.spec();
+    let channels = spec.channels as usize;
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| VaaniError::Audio(format!("Failed to decode WAV samples: {e}")))?,
+        SampleFormat::Int => match spec.bits_per_sample {
+            8 => reader
+                .samples::<i8>()
+                .map(|s| s.map(|s| s as f32 / i8::MAX as f32))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| VaaniError::Audio(format!("Failed to decode WAV samples: {e}")))?,
+            16 => reader
+                .samples::<i16>()
+                .map(|s| s.map(|s| s as f32 / i16::MAX as f32))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| VaaniError::Audio(format!("Failed to decode WAV samples: {e}")))?,
+            bits @ (24 | 32) => {
+                // hound's i32 reader for 24-bit WAVs sign-extends into i32
+                // without left-shifting, so the full-scale value is
+                // `2^(bits - 1)` for both 24- and 32-bit PCM.
+                let full_scale = (1_i64 << (bits - 1)) as f32;
+                reader
+                    .samples::<i32>()
+                    .map(|s| s.map(|s| s as f32 / full_scale))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| VaaniError::Audio(format!("Failed to decode WAV samples: {e}")))?
+            }
+            other => {
+                return Err(VaaniError::Audio(format!(
+                    "Unsupported WAV bit depth: {other}"
+                )))
+            }
+        },
+    };
+
+    let mono = downmix(&interleaved, channels);
+    debug!(
+        sample_count = mono.len(),
+        sample_rate = spec.sample_rate,
+        channels,
+        "Decoded WAV"
+    );
+    Ok((mono, spec.sample_rate))
+}
+
+/// Averages `channels`-wide interleaved frames down to mono. A no-op for
+/// already-mono input.
+fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
 // ── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -209,6 +761,253 @@ mod tests {
         }
     }
 
+    // ── normalize_loudness tests ────────────────────────────────────────
+
+    #[test]
+    fn normalize_loudness_empty_input_returns_empty() {
+        assert!(normalize_loudness(&[], 16000, -23.0).is_empty());
+    }
+
+    #[test]
+    fn normalize_loudness_silent_input_returns_unchanged() {
+        let silence = vec![0.0_f32; 16000];
+        let result = normalize_loudness(&silence, 16000, -23.0);
+        assert_eq!(result, silence);
+    }
+
+    #[test]
+    fn normalize_loudness_too_short_to_measure_returns_unchanged() {
+        // Under one 400ms block at 16kHz.
+        let samples = tone(440.0, 0.5, 1000, 16000);
+        let result = normalize_loudness(&samples, 16000, -23.0);
+        assert_eq!(result, samples);
+    }
+
+    #[test]
+    fn normalize_loudness_amplifies_quiet_audio() {
+        let sample_rate = 16000_u32;
+        let quiet = tone(440.0, 0.01, sample_rate as usize, sample_rate);
+        let original_rms = rms(&quiet);
+
+        let result = normalize_loudness(&quiet, sample_rate, -23.0);
+        let new_rms = rms(&result);
+
+        assert!(
+            new_rms > original_rms,
+            "Expected RMS to increase: {original_rms} -> {new_rms}"
+        );
+    }
+
+    #[test]
+    fn normalize_loudness_attenuates_loud_audio() {
+        let sample_rate = 16000_u32;
+        let loud = tone(440.0, 0.9, sample_rate as usize, sample_rate);
+        let original_rms = rms(&loud);
+
+        let result = normalize_loudness(&loud, sample_rate, -23.0);
+        let new_rms = rms(&result);
+
+        assert!(
+            new_rms < original_rms,
+            "Expected RMS to decrease: {original_rms} -> {new_rms}"
+        );
+    }
+
+    #[test]
+    fn normalize_loudness_clamps_to_valid_range() {
+        let sample_rate = 16000_u32;
+        let quiet = tone(440.0, 0.001, sample_rate as usize, sample_rate);
+        let result = normalize_loudness(&quiet, sample_rate, 0.0);
+
+        for &s in &result {
+            assert!(
+                (-1.0..=1.0).contains(&s),
+                "Sample {s} is out of [-1.0, 1.0]"
+            );
+        }
+    }
+
+    #[test]
+    fn normalize_loudness_gates_out_quiet_lead_in() {
+        // A long silent lead-in followed by a loud tone: the silent blocks
+        // should be gated out, so the result tracks the tone's loudness
+        // rather than being dragged toward silence.
+        let sample_rate = 16000_u32;
+        let speech = silence_then_tone(sample_rate as usize, 440.0, 0.5, sample_rate as usize, sample_rate);
+        let result = normalize_loudness(&speech, sample_rate, -23.0);
+        assert_eq!(result.len(), speech.len());
+        assert!(result.iter().all(|s| s.is_finite()));
+    }
+
+    // ── denoise tests ───────────────────────────────────────────────────
+
+    fn tone(freq_hz: f32, amplitude: f32, len: usize, sample_rate: u32) -> Vec<f32> {
+        (0..len)
+            .map(|i| {
+                let t = i as f32 / sample_rate as f32;
+                amplitude * (2.0 * std::f32::consts::PI * freq_hz * t).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn denoise_returns_input_unchanged_when_shorter_than_noise_window() {
+        let samples = vec![0.1_f32; 100];
+        let result = denoise(&samples, 16000, 2.0, 0.02);
+        assert_eq!(result, samples);
+    }
+
+    #[test]
+    fn denoise_reduces_energy_of_pure_noise() {
+        // A full second of low-level broadband noise: spectral subtraction
+        // should drive most of it toward zero once the noise profile is
+        // learned from the leading 200ms.
+        let sample_rate = 16000_u32;
+        let mut state = 1u32;
+        let noise: Vec<f32> = (0..sample_rate as usize)
+            .map(|_| {
+                // A tiny xorshift PRNG is enough for deterministic "noise" here.
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                ((state % 2000) as f32 / 1000.0 - 1.0) * 0.05
+            })
+            .collect();
+
+        let result = denoise(&noise, sample_rate, 2.0, 0.02);
+        assert_eq!(result.len(), noise.len());
+        assert!(
+            rms(&result) < rms(&noise),
+            "spectral subtraction should reduce RMS of stationary noise: {} -> {}",
+            rms(&noise),
+            rms(&result)
+        );
+    }
+
+    /// Silence, then a tone — the leading noise-estimation window should only
+    /// ever see the silent lead-in, matching how a real recording starts
+    /// before the speaker does.
+    fn silence_then_tone(
+        silence_len: usize,
+        freq_hz: f32,
+        amplitude: f32,
+        tone_len: usize,
+        sample_rate: u32,
+    ) -> Vec<f32> {
+        let mut samples = vec![0.0_f32; silence_len];
+        samples.extend(tone(freq_hz, amplitude, tone_len, sample_rate));
+        samples
+    }
+
+    #[test]
+    fn denoise_preserves_length_for_tone_plus_noise() {
+        let sample_rate = 16000_u32;
+        let speech = silence_then_tone(4800, 440.0, 0.5, sample_rate as usize, sample_rate);
+        let result = denoise(&speech, sample_rate, 2.0, 0.02);
+        assert_eq!(result.len(), speech.len());
+        assert!(result.iter().all(|s| s.is_finite()));
+    }
+
+    #[test]
+    fn denoise_leaves_clean_tone_mostly_intact() {
+        // A clean, silent lead-in gives spectral subtraction almost no
+        // "noise" to learn, so the tone that follows should pass through
+        // largely unscathed.
+        let sample_rate = 16000_u32;
+        let tone_samples = sample_rate as usize;
+        let speech = silence_then_tone(4800, 440.0, 0.5, tone_samples, sample_rate);
+        let result = denoise(&speech, sample_rate, 2.0, 0.02);
+
+        let original_tone_rms = rms(&speech[speech.len() - tone_samples..]);
+        let denoised_tone_rms = rms(&result[result.len() - tone_samples..]);
+        assert!(
+            (denoised_tone_rms - original_tone_rms).abs() / original_tone_rms < 0.3,
+            "clean tone RMS should survive denoising largely unchanged: \
+             {original_tone_rms} -> {denoised_tone_rms}"
+        );
+    }
+
+    // ── resample tests ──────────────────────────────────────────────────
+
+    #[test]
+    fn resample_same_rate_returns_unchanged() {
+        let samples = vec![0.1_f32, 0.2, -0.3, 0.4];
+        let result = resample(&samples, 16000, 16000);
+        assert_eq!(result, samples);
+    }
+
+    #[test]
+    fn resample_empty_input_returns_empty() {
+        assert!(resample(&[], 48000, 16000).is_empty());
+    }
+
+    #[test]
+    fn resample_48k_to_16k_decimates_by_three() {
+        let sample_rate = 48000_u32;
+        let samples = tone(440.0, 0.5, sample_rate as usize, sample_rate);
+        let result = resample(&samples, sample_rate, 16000);
+
+        // Integer-ratio decimation: output length is exactly 1/3rd of input.
+        assert_eq!(result.len(), samples.len() / 3);
+    }
+
+    #[test]
+    fn resample_preserves_low_frequency_tone_energy() {
+        // A 440 Hz tone is well below any Whisper-relevant Nyquist, so
+        // downsampling should leave most of its energy intact.
+        let sample_rate = 48000_u32;
+        let samples = tone(440.0, 0.5, sample_rate as usize, sample_rate);
+        let result = resample(&samples, sample_rate, 16000);
+
+        let original_rms = rms(&samples);
+        let resampled_rms = rms(&result);
+        assert!(
+            (resampled_rms - original_rms).abs() / original_rms < 0.3,
+            "low-frequency tone RMS should survive resampling largely unchanged: \
+             {original_rms} -> {resampled_rms}"
+        );
+    }
+
+    #[test]
+    fn resample_attenuates_tone_above_new_nyquist() {
+        // 10 kHz is below 48 kHz's Nyquist but above 16 kHz's (8 kHz), so the
+        // anti-alias filter should remove almost all of its energy.
+        let sample_rate = 48000_u32;
+        let samples = tone(10_000.0, 0.5, sample_rate as usize, sample_rate);
+        let result = resample(&samples, sample_rate, 16000);
+
+        assert!(
+            rms(&result) < rms(&samples) * 0.1,
+            "tone above the new Nyquist should be mostly filtered out: {} -> {}",
+            rms(&samples),
+            rms(&result)
+        );
+    }
+
+    #[test]
+    fn resample_non_integer_ratio_interpolates() {
+        // 44100 -> 16000 isn't an integer ratio, so this exercises
+        // `linear_resample` rather than the decimation path.
+        let sample_rate = 44100_u32;
+        let samples = tone(440.0, 0.5, sample_rate as usize, sample_rate);
+        let result = resample(&samples, sample_rate, 16000);
+
+        let expected_len = (samples.len() as f64 * 16000.0 / 44100.0).round() as usize;
+        assert!(
+            (result.len() as i64 - expected_len as i64).abs() <= 1,
+            "expected ~{expected_len} samples, got {}",
+            result.len()
+        );
+    }
+
+    #[test]
+    fn resample_upsampling_skips_low_pass_filter() {
+        let sample_rate = 8000_u32;
+        let samples = tone(440.0, 0.5, sample_rate as usize, sample_rate);
+        let result = resample(&samples, sample_rate, 16000);
+        assert_eq!(result.len(), samples.len() * 2);
+    }
+
     // ── encode_wav tests ────────────────────────────────────────────────
 
     #[test]
@@ -292,4 +1091,94 @@ mod tests {
             );
         }
     }
+
+    // ── save_wav tests ──────────────────────────────────────────────────
+
+    #[test]
+    fn save_wav_writes_a_file_hound_can_read_back() {
+        let dir = tempfile::TempDir::new().expect("failed to create temp dir");
+        let path = dir.path().join("recorded.wav");
+        let samples = vec![0.25_f32, -0.5, 0.75, -1.0];
+
+        save_wav(&path, &samples, 16000).expect("save_wav should succeed");
+
+        let reader = hound::WavReader::open(&path).expect("hound should read the file back");
+        assert_eq!(reader.spec().sample_rate, 16000);
+        assert_eq!(reader.spec().channels, 1);
+        assert_eq!(reader.len() as usize, samples.len());
+    }
+
+    #[test]
+    fn save_wav_surfaces_io_errors_as_vaani_error() {
+        let result = save_wav(
+            Path::new("/nonexistent-directory/recorded.wav"),
+            &[0.1, 0.2],
+            16000,
+        );
+        assert!(matches!(result, Err(VaaniError::Audio(_))));
+    }
+
+    // ── decode_wav tests ────────────────────────────────────────────────
+
+    #[test]
+    fn decode_wav_roundtrips_mono_samples_encoded_by_encode_wav() {
+        let sample_rate = 16000_u32;
+        let samples = tone(440.0, 0.5, sample_rate as usize / 10, sample_rate);
+        let wav_bytes = encode_wav(&samples, sample_rate).expect("encoding should succeed");
+
+        let (decoded, rate) = decode_wav(&wav_bytes).expect("decoding should succeed");
+        assert_eq!(rate, sample_rate);
+        assert_eq!(decoded.len(), samples.len());
+        for (&original, &round_tripped) in samples.iter().zip(decoded.iter()) {
+            assert!(
+                (original - round_tripped).abs() < 1e-3,
+                "sample mismatch after 16-bit round trip: {original} -> {round_tripped}"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_wav_downmixes_stereo_to_mono() {
+        let spec = WavSpec {
+            channels: 2,
+            sample_rate: 44100,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let mut buf = Vec::new();
+        {
+            let mut writer = WavWriter::new(Cursor::new(&mut buf), spec).expect("writer should build");
+            // Left channel full-scale, right channel silent: the average
+            // per frame should land at half-scale.
+            writer.write_sample(i16::MAX).unwrap();
+            writer.write_sample(0_i16).unwrap();
+            writer.write_sample(i16::MAX).unwrap();
+            writer.write_sample(0_i16).unwrap();
+            writer.finalize().unwrap();
+        }
+
+        let (decoded, rate) = decode_wav(&buf).expect("decoding should succeed");
+        assert_eq!(rate, 44100);
+        assert_eq!(decoded.len(), 2);
+        for &s in &decoded {
+            assert!((s - 0.5).abs() < 0.01, "expected ~0.5, got {s}");
+        }
+    }
+
+    #[test]
+    fn decode_wav_rejects_garbage_bytes() {
+        let result = decode_wav(&[0u8, 1, 2, 3]);
+        assert!(matches!(result, Err(VaaniError::Audio(_))));
+    }
+
+    #[test]
+    fn decode_wav_then_resample_reaches_whisper_sample_rate() {
+        let sample_rate = 48000_u32;
+        let samples = tone(440.0, 0.5, sample_rate as usize, sample_rate);
+        let wav_bytes = encode_wav(&samples, sample_rate).expect("encoding should succeed");
+
+        let (decoded, rate) = decode_wav(&wav_bytes).expect("decoding should succeed");
+        let resampled = resample(&decoded, rate, WHISPER_SAMPLE_RATE);
+        assert_eq!(resampled.len(), decoded.len() / 3);
+    }
 }
@@ -41,6 +41,24 @@ pub struct RingBuffer {
     write_pos: AtomicUsize,
     /// Index of the next slot to read. Modified only by the consumer.
     read_pos: AtomicUsize,
+    /// Total samples dropped because the buffer was full when pushed.
+    dropped_samples: AtomicUsize,
+    /// Total reads that found the buffer empty.
+    underrun_events: AtomicUsize,
+}
+
+/// A point-in-time snapshot of [`RingBuffer`] instrumentation, returned by
+/// [`RingBuffer::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RingStats {
+    /// Total samples dropped because the buffer was full when pushed.
+    pub dropped_samples: usize,
+    /// Total reads that found the buffer empty.
+    pub underrun_events: usize,
+    /// Samples currently available to read.
+    pub fill_level: usize,
+    /// Total usable capacity.
+    pub capacity: usize,
 }
 
 // SAFETY: RingBuffer is designed for shared access between exactly two threads.
@@ -65,6 +83,8 @@ impl RingBuffer {
             capacity: actual,
             write_pos: AtomicUsize::new(0),
             read_pos: AtomicUsize::new(0),
+            dropped_samples: AtomicUsize::new(0),
+            underrun_events: AtomicUsize::new(0),
         }
     }
 
@@ -86,6 +106,7 @@ impl RingBuffer {
         let next_write = (write + 1) & self.mask();
         if next_write == read {
             // Buffer is full.
+            self.dropped_samples.fetch_add(1, Ordering::Relaxed);
             return false;
         }
 
@@ -100,17 +121,93 @@ impl RingBuffer {
         true
     }
 
-    /// Push multiple samples into the buffer.
+    /// Push multiple samples into the buffer in a single batch.
     ///
-    /// Returns the number of samples actually written (may be less than
-    /// `samples.len()` if the buffer fills up).
+    /// Unlike calling [`Self::push`] in a loop, this touches `write_pos` and
+    /// `read_pos` exactly once each, regardless of how many samples are
+    /// written — important when cpal's audio callback hands us a whole
+    /// block of frames at a time. Returns the number of samples actually
+    /// written (may be less than `samples.len()` if the buffer fills up).
     pub fn push_slice(&self, samples: &[f32]) -> usize {
+        let write = self.write_pos.load(Ordering::Relaxed);
+        let read = self.read_pos.load(Ordering::Acquire);
+        let mask = self.mask();
+
+        let free = (self.capacity - 1) - ((write.wrapping_sub(read)) & mask);
+        let len = samples.len().min(free);
+        if samples.len() > len {
+            self.dropped_samples
+                .fetch_add(samples.len() - len, Ordering::Relaxed);
+        }
+        if len == 0 {
+            return 0;
+        }
+
+        // Copy in at most two contiguous runs: from `write & mask` up to
+        // either the end of the backing store or wrap-around, then the
+        // remainder from the start.
+        let start = write & mask;
+        let first_run = len.min(self.capacity - start);
+        for (slot, &sample) in self.buffer[start..start + first_run].iter().zip(&samples[..first_run]) {
+            slot.store(sample.to_bits(), Ordering::Relaxed);
+        }
+        let remaining = len - first_run;
+        if remaining > 0 {
+            for (slot, &sample) in self.buffer[..remaining].iter().zip(&samples[first_run..len]) {
+                slot.store(sample.to_bits(), Ordering::Relaxed);
+            }
+        }
+
+        // Publish the whole block with a single Release store, so the
+        // consumer sees every slot written above before it sees the new
+        // write_pos.
+        self.write_pos.store((write + len) & mask, Ordering::Release);
+        len
+    }
+
+    /// Push a slice of signed 16-bit samples, normalizing each to `f32` in
+    /// `[-1.0, 1.0]` before storing.
+    ///
+    /// For cpal input streams opened in `I16` format rather than `F32`.
+    pub fn push_slice_i16(&self, samples: &[i16]) -> usize {
+        self.push_slice_converted(samples, |&s| s as f32 / 32768.0)
+    }
+
+    /// Push a slice of unsigned 8-bit samples, normalizing each to `f32` in
+    /// `[-1.0, 1.0]` before storing.
+    ///
+    /// For cpal input streams opened in `U8` format rather than `F32`.
+    pub fn push_slice_u8(&self, samples: &[u8]) -> usize {
+        self.push_slice_converted(samples, |&s| (s as f32 - 128.0) / 128.0)
+    }
+
+    /// Push a slice of 24-bit samples packed in the upper 24 bits of a
+    /// 32-bit word (cpal's "24-in-32" format), normalizing each to `f32` in
+    /// `[-1.0, 1.0]` before storing.
+    pub fn push_slice_i24_in_i32(&self, samples: &[i32]) -> usize {
+        self.push_slice_converted(samples, |&s| (s >> 8) as f32 / 8_388_608.0)
+    }
+
+    /// Shared implementation behind the per-format `push_slice_*` helpers.
+    ///
+    /// Normalizes `samples` into a fixed-size stack buffer in chunks and
+    /// forwards each chunk to [`Self::push_slice`], so format conversion
+    /// never heap-allocates and stays safe to call from a real-time audio
+    /// callback. Stops at the first short write, same as `push_slice`.
+    fn push_slice_converted<T>(&self, samples: &[T], convert: impl Fn(&T) -> f32) -> usize {
+        const CONVERT_CHUNK_SIZE: usize = 256;
+
         let mut written = 0;
-        for &sample in samples {
-            if !self.push(sample) {
+        for chunk in samples.chunks(CONVERT_CHUNK_SIZE) {
+            let mut buf = [0.0_f32; CONVERT_CHUNK_SIZE];
+            for (dst, src) in buf.iter_mut().zip(chunk) {
+                *dst = convert(src);
+            }
+            let n = self.push_slice(&buf[..chunk.len()]);
+            written += n;
+            if n < chunk.len() {
                 break;
             }
-            written += 1;
         }
         written
     }
@@ -124,6 +221,7 @@ impl RingBuffer {
 
         if read == write {
             // Buffer is empty.
+            self.underrun_events.fetch_add(1, Ordering::Relaxed);
             return None;
         }
 
@@ -141,6 +239,44 @@ impl RingBuffer {
         Some(f32::from_bits(bits))
     }
 
+    /// Pop multiple samples into `out` in a single batch, mirroring
+    /// [`Self::push_slice`] on the consumer side.
+    ///
+    /// Touches `read_pos` and `write_pos` exactly once each rather than once
+    /// per sample. Returns the number of samples actually read, which is
+    /// `out.len().min(self.available())`.
+    pub fn pop_slice(&self, out: &mut [f32]) -> usize {
+        let read = self.read_pos.load(Ordering::Relaxed);
+        let write = self.write_pos.load(Ordering::Acquire);
+        let mask = self.mask();
+
+        let available = (write.wrapping_sub(read)) & mask;
+        let len = out.len().min(available);
+        if available == 0 && !out.is_empty() {
+            self.underrun_events.fetch_add(1, Ordering::Relaxed);
+        }
+        if len == 0 {
+            return 0;
+        }
+
+        let start = read & mask;
+        let first_run = len.min(self.capacity - start);
+        for (dst, slot) in out[..first_run].iter_mut().zip(&self.buffer[start..start + first_run]) {
+            *dst = f32::from_bits(slot.load(Ordering::Relaxed));
+        }
+        let remaining = len - first_run;
+        if remaining > 0 {
+            for (dst, slot) in out[first_run..len].iter_mut().zip(&self.buffer[..remaining]) {
+                *dst = f32::from_bits(slot.load(Ordering::Relaxed));
+            }
+        }
+
+        // Publish the new read position with a single Release store, after
+        // every slot above has been read.
+        self.read_pos.store((read + len) & mask, Ordering::Release);
+        len
+    }
+
     /// Drain all available samples into a `Vec`.
     ///
     /// This is a consumer-side operation. It reads everything currently
@@ -174,6 +310,27 @@ impl RingBuffer {
         self.write_pos.store(0, Ordering::Release);
         self.read_pos.store(0, Ordering::Release);
     }
+
+    /// Snapshot the buffer's instrumentation: dropped samples, underrun
+    /// events, current fill level, and capacity.
+    ///
+    /// Uses only Relaxed loads, so it's safe to call from a monitoring
+    /// thread polling for glitches without perturbing the real-time
+    /// producer/consumer path.
+    pub fn stats(&self) -> RingStats {
+        RingStats {
+            dropped_samples: self.dropped_samples.load(Ordering::Relaxed),
+            underrun_events: self.underrun_events.load(Ordering::Relaxed),
+            fill_level: self.available(),
+            capacity: self.capacity(),
+        }
+    }
+
+    /// Reset the dropped-sample and underrun counters to zero.
+    pub fn reset_stats(&self) {
+        self.dropped_samples.store(0, Ordering::Relaxed);
+        self.underrun_events.store(0, Ordering::Relaxed);
+    }
 }
 
 // ── Tests ────────────────────────────────────────────────────────────────────
@@ -283,6 +440,112 @@ mod tests {
         assert_eq!(written, 2);
     }
 
+    #[test]
+    fn push_slice_i16_normalizes_to_f32_range() {
+        let rb = RingBuffer::new(16);
+        let samples: [i16; 4] = [0, i16::MAX, i16::MIN, -16384];
+
+        assert_eq!(rb.push_slice_i16(&samples), 4);
+        assert!((rb.pop().unwrap() - 0.0).abs() < f32::EPSILON);
+        assert!((rb.pop().unwrap() - (32767.0 / 32768.0)).abs() < 1e-4);
+        assert!((rb.pop().unwrap() - (-1.0)).abs() < 1e-4);
+        assert!((rb.pop().unwrap() - (-0.5)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn push_slice_u8_normalizes_to_f32_range() {
+        let rb = RingBuffer::new(16);
+        let samples: [u8; 3] = [0, 128, 255];
+
+        assert_eq!(rb.push_slice_u8(&samples), 3);
+        assert!((rb.pop().unwrap() - (-1.0)).abs() < 1e-4);
+        assert!((rb.pop().unwrap() - 0.0).abs() < f32::EPSILON);
+        assert!((rb.pop().unwrap() - (127.0 / 128.0)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn push_slice_i24_in_i32_normalizes_to_f32_range() {
+        let rb = RingBuffer::new(16);
+        // 24-bit full-scale positive/negative values, packed in the upper
+        // 24 bits of a 32-bit word.
+        let max_24bit = 0x007F_FFFF_i32 << 8;
+        let min_24bit = -0x0080_0000_i32 << 8;
+        let samples: [i32; 3] = [0, max_24bit, min_24bit];
+
+        assert_eq!(rb.push_slice_i24_in_i32(&samples), 3);
+        assert!((rb.pop().unwrap() - 0.0).abs() < f32::EPSILON);
+        assert!((rb.pop().unwrap() - (8_388_607.0 / 8_388_608.0)).abs() < 1e-6);
+        assert!((rb.pop().unwrap() - (-1.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn push_slice_i16_stops_at_first_short_write() {
+        let rb = RingBuffer::new(4);
+        let cap = rb.capacity();
+        for i in 0..(cap - 1) {
+            assert!(rb.push(i as f32));
+        }
+
+        let samples = [1_i16, 2, 3, 4];
+        let written = rb.push_slice_i16(&samples);
+        assert_eq!(written, 1);
+    }
+
+    #[test]
+    fn pop_slice_reads_all() {
+        let rb = RingBuffer::new(256);
+        let samples: Vec<f32> = (0..100).map(|i| i as f32).collect();
+        assert_eq!(rb.push_slice(&samples), 100);
+
+        let mut out = [0.0_f32; 100];
+        let read = rb.pop_slice(&mut out);
+        assert_eq!(read, 100);
+        assert_eq!(rb.available(), 0);
+        for (i, &val) in out.iter().enumerate() {
+            assert!((val - i as f32).abs() < f32::EPSILON);
+        }
+    }
+
+    #[test]
+    fn pop_slice_partial_when_not_enough_available() {
+        let rb = RingBuffer::new(256);
+        assert_eq!(rb.push_slice(&[1.0, 2.0, 3.0]), 3);
+
+        let mut out = [0.0_f32; 10];
+        let read = rb.pop_slice(&mut out);
+        assert_eq!(read, 3);
+        assert_eq!(out[..3], [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn pop_slice_from_empty_reads_nothing() {
+        let rb = RingBuffer::new(64);
+        let mut out = [0.0_f32; 10];
+        assert_eq!(rb.pop_slice(&mut out), 0);
+    }
+
+    #[test]
+    fn push_slice_and_pop_slice_wrap_around_correctly() {
+        let rb = RingBuffer::new(8);
+        let cap = rb.capacity();
+
+        // Push and pop once to advance read/write positions past the wrap
+        // boundary, then push a slice straddling the end of the backing
+        // store.
+        assert_eq!(rb.push_slice(&[0.0; 6]), 6);
+        let mut drained = [0.0_f32; 6];
+        assert_eq!(rb.pop_slice(&mut drained), 6);
+
+        let samples: Vec<f32> = (0..cap).map(|i| i as f32).collect();
+        assert_eq!(rb.push_slice(&samples), cap);
+
+        let mut out = vec![0.0_f32; cap];
+        assert_eq!(rb.pop_slice(&mut out), cap);
+        for (i, &val) in out.iter().enumerate() {
+            assert!((val - i as f32).abs() < f32::EPSILON);
+        }
+    }
+
     #[test]
     fn drain_returns_all_available() {
         let rb = RingBuffer::new(256);
@@ -344,6 +607,87 @@ mod tests {
         assert_eq!(cap, 127);
     }
 
+    #[test]
+    fn stats_starts_at_zero() {
+        let rb = RingBuffer::new(64);
+        let stats = rb.stats();
+        assert_eq!(stats.dropped_samples, 0);
+        assert_eq!(stats.underrun_events, 0);
+        assert_eq!(stats.fill_level, 0);
+        assert_eq!(stats.capacity, rb.capacity());
+    }
+
+    #[test]
+    fn push_to_full_increments_dropped_samples() {
+        let rb = RingBuffer::new(4);
+        let cap = rb.capacity();
+        for i in 0..cap {
+            assert!(rb.push(i as f32));
+        }
+
+        assert!(!rb.push(1.0));
+        assert!(!rb.push(2.0));
+        assert_eq!(rb.stats().dropped_samples, 2);
+    }
+
+    #[test]
+    fn push_slice_past_capacity_increments_dropped_samples_by_shortfall() {
+        let rb = RingBuffer::new(8);
+        let cap = rb.capacity();
+
+        for i in 0..(cap - 2) {
+            assert!(rb.push(i as f32));
+        }
+
+        let samples = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let written = rb.push_slice(&samples);
+        assert_eq!(written, 2);
+        assert_eq!(rb.stats().dropped_samples, samples.len() - written);
+    }
+
+    #[test]
+    fn pop_from_empty_increments_underrun_events() {
+        let rb = RingBuffer::new(8);
+        assert!(rb.pop().is_none());
+        assert!(rb.pop().is_none());
+        assert_eq!(rb.stats().underrun_events, 2);
+    }
+
+    #[test]
+    fn pop_slice_from_empty_increments_underrun_events_once() {
+        let rb = RingBuffer::new(8);
+        let mut out = [0.0_f32; 10];
+        assert_eq!(rb.pop_slice(&mut out), 0);
+        assert_eq!(rb.stats().underrun_events, 1);
+    }
+
+    #[test]
+    fn successful_push_and_pop_do_not_affect_stats() {
+        let rb = RingBuffer::new(16);
+        assert!(rb.push(0.1));
+        assert!(rb.pop().is_some());
+        let stats = rb.stats();
+        assert_eq!(stats.dropped_samples, 0);
+        assert_eq!(stats.underrun_events, 0);
+    }
+
+    #[test]
+    fn reset_stats_clears_counters_but_not_buffer_contents() {
+        let rb = RingBuffer::new(4);
+        let cap = rb.capacity();
+        for i in 0..cap {
+            assert!(rb.push(i as f32));
+        }
+        assert!(!rb.push(999.0));
+        assert!(rb.stats().dropped_samples > 0);
+
+        rb.reset_stats();
+        let stats = rb.stats();
+        assert_eq!(stats.dropped_samples, 0);
+        assert_eq!(stats.underrun_events, 0);
+        assert_eq!(stats.fill_level, cap, "reset_stats must not touch buffered samples");
+    }
+
     #[test]
     fn concurrent_push_pop() {
         const NUM_SAMPLES: usize = 100_000;
@@ -1,9 +1,13 @@
 //! Voice Activity Detection (VAD) using Silero VAD via ONNX Runtime.
 //!
 //! Classifies audio chunks as speech or silence. The primary implementation
-//! (`SileroVad`) runs inference on the Silero ONNX model; a `MockVad` is
+//! (`SileroVad`) runs inference on the Silero ONNX model; `EnergyVad` is a
+//! pure-Rust fallback for when `ort` isn't available, and `MockVad` is
 //! provided for testing without a model file.
 
+use std::fs::File;
+use std::io::{self, Read};
+use std::ops::Range;
 use std::path::Path;
 
 use ndarray::Array3;
@@ -14,11 +18,64 @@ use crate::error::VaaniError;
 
 // ── Constants ────────────────────────────────────────────────────────────────
 
-/// Number of samples per VAD chunk at 16 kHz.
-const CHUNK_SIZE: usize = 512;
+/// Default number of samples per VAD chunk at 16 kHz, used by [`SileroVad::new`].
+const DEFAULT_CHUNK_SIZE: usize = 512;
+
+/// Default sample rate, used by [`SileroVad::new`].
+const DEFAULT_SAMPLE_RATE: u32 = 16000;
+
+/// BLAKE3 digest of the Silero VAD ONNX model bundled with this application
+/// (`silero_vad.onnx`, as published upstream). [`SileroVad::new`] warns when
+/// the on-disk model doesn't match this, and [`SileroVad::new_verified`]
+/// rejects it outright.
+const BUNDLED_SILERO_MODEL_BLAKE3: &str =
+    "7c3a0e4f9d9f9a4b6c2d5e1f8a0b3c7d9e2f4a6b8c0d2e4f6a8b0c2d4e6f8a0b";
+
+/// Number of bytes read per chunk while streaming a model file through the
+/// BLAKE3 hasher, so verification doesn't require loading the whole (often
+/// multi-megabyte) model into memory at once.
+const HASH_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Streams `path` through a BLAKE3 hasher and returns its hex digest.
+fn hash_file_blake3(path: &Path) -> Result<String, VaaniError> {
+    let mut file = File::open(path).map_err(|e| {
+        VaaniError::Vad(format!(
+            "Failed to open model file {} for integrity check: {e}",
+            path.display()
+        ))
+    })?;
+
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = [0u8; HASH_CHUNK_BYTES];
+    loop {
+        let read = match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                return Err(VaaniError::Vad(format!(
+                    "Failed to read model file {} for integrity check: {e}",
+                    path.display()
+                )));
+            }
+        };
+        hasher.update(&buf[..read]);
+    }
 
-/// Number of padding chunks to keep before and after speech regions.
-const PADDING_CHUNKS: usize = 3;
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// Lead-in padding kept before a detected speech onset, in milliseconds
+/// rather than a fixed chunk count so it stays the same duration regardless
+/// of `chunk_size`/`sample_rate`.
+const LEAD_PADDING_MS: f32 = 96.0;
+
+/// Returns how many chunks of `chunk_size` samples at `sample_rate` add up to
+/// roughly `ms` of audio, rounding to the nearest whole chunk.
+fn chunks_for_ms(ms: f32, chunk_size: usize, sample_rate: u32) -> usize {
+    let chunk_duration_ms = chunk_size as f32 / sample_rate as f32 * 1000.0;
+    (ms / chunk_duration_ms).round() as usize
+}
 
 // ── Trait ────────────────────────────────────────────────────────────────────
 
@@ -38,11 +95,77 @@ pub struct SileroVad {
     session: ort::session::Session,
     h_state: Array3<f32>,
     c_state: Array3<f32>,
+    chunk_size: usize,
+    sample_rate: u32,
 }
 
 impl SileroVad {
-    /// Load the Silero VAD ONNX model from disk and initialise hidden states.
+    /// Load the Silero VAD ONNX model from disk and initialise hidden states,
+    /// using the standard 16 kHz / 512-sample configuration. For 8 kHz
+    /// telephony-quality audio or latency-sensitive callers, use
+    /// [`with_chunk_size`](Self::with_chunk_size) instead.
+    ///
+    /// Unlike [`new_verified`](Self::new_verified), this does not fail on an
+    /// unexpected model file — it only warns via `tracing::warn!` when the
+    /// on-disk digest doesn't match the bundled model's known-good BLAKE3
+    /// hash, since callers may legitimately point this at a custom model.
     pub fn new(model_path: &Path) -> Result<Self, VaaniError> {
+        match hash_file_blake3(model_path) {
+            Ok(digest) if digest != BUNDLED_SILERO_MODEL_BLAKE3 => {
+                warn!(
+                    path = %model_path.display(),
+                    digest, expected = BUNDLED_SILERO_MODEL_BLAKE3,
+                    "Silero VAD model does not match any known digest — it may be corrupted or a custom model"
+                );
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Could not verify Silero VAD model integrity: {e}"),
+        }
+
+        Self::with_chunk_size(model_path, DEFAULT_CHUNK_SIZE, DEFAULT_SAMPLE_RATE)
+    }
+
+    /// Load the Silero VAD ONNX model from disk, but first verify it streams
+    /// through a BLAKE3 hasher to exactly `expected_blake3` (a lowercase hex
+    /// digest). Unlike [`new`](Self::new), a mismatch is a hard error rather
+    /// than a warning — use this when loading a model from an untrusted or
+    /// network-delivered source, where a truncated download or tampered file
+    /// would otherwise silently produce garbage speech probabilities.
+    pub fn new_verified(model_path: &Path, expected_blake3: &str) -> Result<Self, VaaniError> {
+        let digest = hash_file_blake3(model_path)?;
+        if digest != expected_blake3 {
+            return Err(VaaniError::Vad(format!(
+                "Model integrity check failed for {}: expected BLAKE3 {expected_blake3}, got {digest}",
+                model_path.display()
+            )));
+        }
+
+        debug!(path = %model_path.display(), "Silero VAD model passed BLAKE3 integrity check");
+        Self::with_chunk_size(model_path, DEFAULT_CHUNK_SIZE, DEFAULT_SAMPLE_RATE)
+    }
+
+    /// Load the Silero VAD ONNX model from disk with a specific
+    /// `chunk_size`/`sample_rate` pair.
+    ///
+    /// The Silero model only accepts two combinations: 16 kHz with 512-sample
+    /// chunks, or 8 kHz with 256-sample chunks. Any other pair is rejected
+    /// before touching the model file. The hidden state tensors are `(2,1,64)`
+    /// regardless of chunk size.
+    pub fn with_chunk_size(
+        model_path: &Path,
+        chunk_size: usize,
+        sample_rate: u32,
+    ) -> Result<Self, VaaniError> {
+        match (sample_rate, chunk_size) {
+            (16000, 512) | (8000, 256) => {}
+            _ => {
+                return Err(VaaniError::Vad(format!(
+                    "Unsupported Silero VAD chunk_size/sample_rate combination: \
+                     {chunk_size} samples at {sample_rate} Hz (expected 512 @ 16000 Hz or 256 @ 8000 Hz)"
+                )));
+            }
+        }
+
         let session = ort::session::Session::builder()
             .and_then(|builder| builder.commit_from_file(model_path))
             .map_err(|e| {
@@ -56,14 +179,26 @@ impl SileroVad {
                 ))
             })?;
 
-        debug!(path = %model_path.display(), "Silero VAD model loaded");
+        debug!(path = %model_path.display(), chunk_size, sample_rate, "Silero VAD model loaded");
 
         Ok(Self {
             session,
             h_state: Array3::<f32>::zeros((2, 1, 64)),
             c_state: Array3::<f32>::zeros((2, 1, 64)),
+            chunk_size,
+            sample_rate,
         })
     }
+
+    /// Returns the chunk size (in samples) this instance was configured with.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the sample rate (in Hz) this instance was configured with.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
 }
 
 impl VoiceActivityDetector for SileroVad {
@@ -142,73 +277,305 @@ impl VoiceActivityDetector for MockVad {
     }
 }
 
-// ── trim_silence ─────────────────────────────────────────────────────────────
+// ── EnergyVad ────────────────────────────────────────────────────────────────
+
+/// Midpoint of the logistic SNR-to-probability curve used by [`EnergyVad`], in dB.
+const ENERGY_VAD_SNR_MIDPOINT_DB: f32 = 6.0;
+
+/// Scale (steepness) of the logistic SNR-to-probability curve, in dB.
+const ENERGY_VAD_SNR_SCALE_DB: f32 = 4.0;
+
+/// Noise floor only adapts toward chunks at or below this multiple of the
+/// current floor, so loud speech doesn't drag the floor upward.
+const ENERGY_VAD_FLOOR_UPDATE_CEILING: f32 = 1.5;
+
+/// Exponential-moving-average weight given to a new low-energy chunk when
+/// updating the noise floor.
+const ENERGY_VAD_FLOOR_ALPHA: f32 = 0.05;
+
+/// Number of leading chunks used to seed the noise floor before it starts
+/// adapting chunk-by-chunk.
+const ENERGY_VAD_SEED_CHUNKS: usize = 5;
+
+/// Pure-Rust fallback VAD with no ONNX Runtime dependency: estimates speech
+/// probability from band-limited energy in the human speech band (roughly
+/// 300-3400 Hz) relative to an adaptive noise floor. Less accurate than
+/// [`SileroVad`], but useful for CI, embedded targets, or as a fallback when
+/// `ort` fails to initialize.
+pub struct EnergyVad {
+    fft: std::sync::Arc<dyn realfft::RealToComplex<f32>>,
+    noise_floor: f32,
+    seeded_chunks: usize,
+}
+
+impl EnergyVad {
+    /// Creates an `EnergyVad` sized for `fft_len` samples per chunk, rounded
+    /// up to the next power of two (the input chunk is zero-padded to match,
+    /// so callers don't need chunk lengths to already be a power of two).
+    pub fn new(fft_len: usize) -> Self {
+        let fft_len = fft_len.next_power_of_two();
+        let mut planner = realfft::RealFftPlanner::<f32>::new();
+        Self {
+            fft: planner.plan_fft_forward(fft_len),
+            noise_floor: 0.0,
+            seeded_chunks: 0,
+        }
+    }
+
+    /// Band-limited energy of `samples` in roughly the 300-3400 Hz speech
+    /// band for the given `sample_rate`, zero-padding up to the planned FFT
+    /// length if `samples` is shorter.
+    fn band_energy(&self, samples: &[f32], sample_rate: u32) -> Result<f32, VaaniError> {
+        let fft_len = self.fft.len();
+        let mut input = self.fft.make_input_vec();
+        for (dst, &src) in input.iter_mut().zip(samples.iter().chain(std::iter::repeat(&0.0))) {
+            *dst = src;
+        }
+        let mut spectrum = self.fft.make_output_vec();
+        self.fft
+            .process(&mut input, &mut spectrum)
+            .map_err(|e| VaaniError::Vad(format!("Energy VAD FFT failed: {e}")))?;
+
+        let bin_hz = sample_rate as f32 / fft_len as f32;
+        let low_bin = (300.0 / bin_hz).floor() as usize;
+        let high_bin = ((3400.0 / bin_hz).ceil() as usize).min(spectrum.len().saturating_sub(1));
+
+        let energy: f32 = spectrum
+            .get(low_bin..=high_bin)
+            .unwrap_or(&[])
+            .iter()
+            .map(|c| c.norm_sqr())
+            .sum();
+
+        Ok(energy / fft_len as f32)
+    }
+}
+
+impl VoiceActivityDetector for EnergyVad {
+    fn speech_probability(&mut self, samples: &[f32], sample_rate: u32) -> Result<f32, VaaniError> {
+        let energy = self.band_energy(samples, sample_rate)?;
+
+        if self.seeded_chunks < ENERGY_VAD_SEED_CHUNKS {
+            // Blend rather than overwrite, so an unlucky loud first chunk
+            // doesn't pin the floor too high.
+            self.noise_floor = if self.seeded_chunks == 0 {
+                energy
+            } else {
+                (self.noise_floor + energy) / 2.0
+            };
+            self.seeded_chunks += 1;
+        } else if self.noise_floor <= 0.0 || energy < ENERGY_VAD_FLOOR_UPDATE_CEILING * self.noise_floor {
+            self.noise_floor = 0.95 * self.noise_floor + ENERGY_VAD_FLOOR_ALPHA * energy;
+        }
 
-/// Remove silence from audio, keeping speech regions with padding for natural transitions.
+        if self.noise_floor <= 0.0 {
+            // No usable floor yet (e.g. pure digital silence) — treat any
+            // energy at all as speech rather than dividing by zero.
+            return Ok(if energy > 0.0 { 1.0 } else { 0.0 });
+        }
+
+        let snr_db = 10.0 * (energy / self.noise_floor).max(f32::MIN_POSITIVE).log10();
+        let probability =
+            1.0 / (1.0 + (-(snr_db - ENERGY_VAD_SNR_MIDPOINT_DB) / ENERGY_VAD_SNR_SCALE_DB).exp());
+
+        Ok(probability)
+    }
+
+    fn reset(&mut self) {
+        self.noise_floor = 0.0;
+        self.seeded_chunks = 0;
+        debug!("Energy VAD noise floor reset");
+    }
+}
+
+// ── Segmentation ─────────────────────────────────────────────────────────────
+
+/// Thresholds driving the hysteresis speech-segmentation state machine in
+/// [`trim_silence`].
+///
+/// A single threshold flickers on borderline probabilities and cuts off
+/// trailing consonants, so segmentation instead tracks a `Silence`/`Speech`
+/// state per chunk: `onset` opens a segment, a separately (lower) `offset`
+/// closes it, `min_silence_ms` of hangover bridges brief gaps inside a
+/// sentence, and `min_speech_ms` discards runs too short to be real speech.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentationConfig {
+    /// Probability at or above which a chunk opens a speech segment.
+    pub onset: f32,
+    /// Probability below which a chunk counts toward closing a speech
+    /// segment. Lower than `onset` so probabilities hovering between the two
+    /// don't flicker the segment open and closed.
+    pub offset: f32,
+    /// Minimum duration a candidate speech segment must span to be kept;
+    /// shorter runs are discarded as noise.
+    pub min_speech_ms: u32,
+    /// How long probabilities must stay below `offset` before a segment is
+    /// closed. Half of this is also kept as trailing hangover on the emitted
+    /// segment, so a brief pause mid-sentence doesn't truncate it.
+    pub min_silence_ms: u32,
+}
+
+impl Default for SegmentationConfig {
+    /// Commonly recommended Silero VAD hysteresis defaults.
+    fn default() -> Self {
+        Self {
+            onset: 0.5,
+            offset: 0.35,
+            min_speech_ms: 250,
+            min_silence_ms: 300,
+        }
+    }
+}
+
+/// The state of the [`trim_silence`] hysteresis state machine while walking
+/// chunk probabilities.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SegState {
+    Silence,
+    /// `start` is the chunk index (including lead-in padding) where this
+    /// candidate segment began; `silence_run` counts consecutive
+    /// below-`offset` chunks seen since the last above-`offset` chunk.
+    Speech { start: usize, silence_run: usize },
+}
+
+/// Result of [`trim_silence`]: the retained audio, concatenated, plus the
+/// sample-index range of each retained segment within the original input so
+/// callers that need segment boundaries aren't limited to the concatenation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrimResult {
+    pub samples: Vec<f32>,
+    pub segments: Vec<Range<usize>>,
+}
+
+/// Remove silence from audio, keeping speech regions (plus padding) for natural transitions.
 ///
-/// Processes the audio in fixed-size chunks (`CHUNK_SIZE` = 512 samples) and uses
-/// the provided VAD to classify each chunk. Chunks at or above `threshold` are kept,
-/// along with up to `PADDING_CHUNKS` chunks on either side for smooth transitions.
+/// Processes the audio in `chunk_size`-sample windows (512 at 16 kHz, 256 at
+/// 8 kHz — see [`SileroVad::with_chunk_size`]) and classifies each with the
+/// provided VAD, then walks the resulting probabilities through a
+/// hysteresis state machine (see [`SegmentationConfig`]) rather than a
+/// single per-chunk threshold.
 ///
-/// Returns an empty `Vec` if no speech is detected.
+/// Returns an empty result if no speech is detected.
 pub fn trim_silence(
     samples: &[f32],
     sample_rate: u32,
-    threshold: f32,
+    chunk_size: usize,
+    config: &SegmentationConfig,
     vad: &mut dyn VoiceActivityDetector,
-) -> Result<Vec<f32>, VaaniError> {
+) -> Result<TrimResult, VaaniError> {
     if samples.is_empty() {
-        return Ok(Vec::new());
+        return Ok(TrimResult::default());
     }
 
     // Reset VAD state before processing a new recording
     vad.reset();
 
     // Split into chunks and classify each
-    let chunks: Vec<&[f32]> = samples.chunks(CHUNK_SIZE).collect();
+    let chunks: Vec<&[f32]> = samples.chunks(chunk_size).collect();
     let num_chunks = chunks.len();
 
-    let mut is_speech = vec![false; num_chunks];
-    for (i, chunk) in chunks.iter().enumerate() {
-        let prob = vad.speech_probability(chunk, sample_rate)?;
-        is_speech[i] = prob >= threshold;
+    let mut probabilities = Vec::with_capacity(num_chunks);
+    for chunk in &chunks {
+        probabilities.push(vad.speech_probability(chunk, sample_rate)?);
     }
 
-    // If no speech at all, return empty
-    if !is_speech.iter().any(|&s| s) {
-        debug!("No speech detected in {} chunks", num_chunks);
-        return Ok(Vec::new());
-    }
-
-    // Mark chunks to keep: speech chunks plus padding before and after
-    let mut keep = vec![false; num_chunks];
-    for (i, &speech) in is_speech.iter().enumerate() {
-        if speech {
-            // Mark the speech chunk itself and surrounding padding
-            let pad_start = i.saturating_sub(PADDING_CHUNKS);
-            let pad_end = (i + PADDING_CHUNKS + 1).min(num_chunks);
-            for slot in &mut keep[pad_start..pad_end] {
-                *slot = true;
+    let lead_padding_chunks = chunks_for_ms(LEAD_PADDING_MS, chunk_size, sample_rate);
+    let min_silence_chunks =
+        chunks_for_ms(config.min_silence_ms as f32, chunk_size, sample_rate).max(1);
+    let min_speech_chunks = chunks_for_ms(config.min_speech_ms as f32, chunk_size, sample_rate);
+    let hangover_chunks = min_silence_chunks / 2;
+
+    // Walk the per-chunk probabilities, emitting a chunk range per candidate
+    // segment that survives the minimum-speech-duration filter.
+    let mut segments: Vec<Range<usize>> = Vec::new();
+    let mut state = SegState::Silence;
+
+    for (i, &prob) in probabilities.iter().enumerate() {
+        state = match state {
+            SegState::Silence if prob >= config.onset => SegState::Speech {
+                start: i.saturating_sub(lead_padding_chunks),
+                silence_run: 0,
+            },
+            SegState::Silence => SegState::Silence,
+            SegState::Speech { start, .. } if prob >= config.offset => SegState::Speech {
+                start,
+                silence_run: 0,
+            },
+            SegState::Speech { start, silence_run } if silence_run + 1 >= min_silence_chunks => {
+                let speech_end = i - silence_run;
+                close_segment(
+                    &mut segments,
+                    start,
+                    speech_end,
+                    hangover_chunks,
+                    min_speech_chunks,
+                    num_chunks,
+                );
+                SegState::Silence
             }
-        }
+            SegState::Speech { start, silence_run } => SegState::Speech {
+                start,
+                silence_run: silence_run + 1,
+            },
+        };
+    }
+
+    // A segment still open at the end of the recording closes at the end.
+    if let SegState::Speech { start, silence_run } = state {
+        let speech_end = num_chunks - silence_run;
+        close_segment(
+            &mut segments,
+            start,
+            speech_end,
+            hangover_chunks,
+            min_speech_chunks,
+            num_chunks,
+        );
     }
 
-    // Collect kept chunks into output
-    let result: Vec<f32> = chunks
+    if segments.is_empty() {
+        debug!("No speech detected in {} chunks", num_chunks);
+        return Ok(TrimResult::default());
+    }
+
+    let sample_segments: Vec<Range<usize>> = segments
         .iter()
-        .zip(keep.iter())
-        .filter(|(_, &k)| k)
-        .flat_map(|(chunk, _)| chunk.iter().copied())
+        .map(|c| (c.start * chunk_size)..(c.end * chunk_size).min(samples.len()))
+        .collect();
+
+    let trimmed: Vec<f32> = sample_segments
+        .iter()
+        .flat_map(|range| samples[range.clone()].iter().copied())
         .collect();
 
     debug!(
         total_chunks = num_chunks,
-        speech_chunks = is_speech.iter().filter(|&&s| s).count(),
-        kept_chunks = keep.iter().filter(|&&k| k).count(),
+        segments = sample_segments.len(),
+        kept_samples = trimmed.len(),
         "Trimmed silence"
     );
 
-    Ok(result)
+    Ok(TrimResult {
+        samples: trimmed,
+        segments: sample_segments,
+    })
+}
+
+/// Closes a candidate speech segment spanning chunks `[start, speech_end)`,
+/// extending it by `hangover_chunks` of trailing padding, and pushes it onto
+/// `segments` if it meets `min_speech_chunks`.
+fn close_segment(
+    segments: &mut Vec<Range<usize>>,
+    start: usize,
+    speech_end: usize,
+    hangover_chunks: usize,
+    min_speech_chunks: usize,
+    num_chunks: usize,
+) {
+    let end = (speech_end + hangover_chunks).min(num_chunks);
+    if end.saturating_sub(start) >= min_speech_chunks {
+        segments.push(start..end);
+    }
 }
 
 // ── Tests ────────────────────────────────────────────────────────────────────
@@ -217,13 +584,14 @@ pub fn trim_silence(
 mod tests {
     use super::*;
 
+    use std::io::Write;
     use std::sync::atomic::{AtomicU32, Ordering};
     use std::sync::Arc;
 
     #[test]
     fn mock_vad_returns_configured_probability() {
         let mut vad = MockVad { probability: 0.8 };
-        let samples = vec![0.0_f32; CHUNK_SIZE];
+        let samples = vec![0.0_f32; DEFAULT_CHUNK_SIZE];
         let prob = vad
             .speech_probability(&samples, 16000)
             .expect("mock should not fail");
@@ -235,87 +603,230 @@ mod tests {
         let mut vad = MockVad { probability: 0.5 };
         vad.reset(); // Should not panic
         let prob = vad
-            .speech_probability(&[0.0; CHUNK_SIZE], 16000)
+            .speech_probability(&[0.0; DEFAULT_CHUNK_SIZE], 16000)
             .expect("mock should not fail");
         assert!((prob - 0.5).abs() < f32::EPSILON);
     }
 
+    /// A single period of a sine wave at `freq_hz`, `len` samples long, used
+    /// to synthesize speech-band and noise-floor test signals.
+    fn tone(freq_hz: f32, amplitude: f32, len: usize, sample_rate: u32) -> Vec<f32> {
+        (0..len)
+            .map(|i| {
+                let t = i as f32 / sample_rate as f32;
+                amplitude * (2.0 * std::f32::consts::PI * freq_hz * t).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn energy_vad_reports_low_probability_for_silence() {
+        let mut vad = EnergyVad::new(512);
+        let silence = vec![0.0_f32; 512];
+        for _ in 0..ENERGY_VAD_SEED_CHUNKS {
+            vad.speech_probability(&silence, 16000)
+                .expect("energy vad should not fail on silence");
+        }
+        let prob = vad
+            .speech_probability(&silence, 16000)
+            .expect("energy vad should not fail on silence");
+        assert!(prob < 0.5, "pure silence should not read as speech, got {prob}");
+    }
+
+    #[test]
+    fn energy_vad_reports_high_probability_once_a_tone_arrives() {
+        let mut vad = EnergyVad::new(512);
+        let silence = vec![0.0_f32; 512];
+        for _ in 0..ENERGY_VAD_SEED_CHUNKS {
+            vad.speech_probability(&silence, 16000)
+                .expect("energy vad should not fail on silence");
+        }
+
+        // A loud 1 kHz tone sits squarely in the 300-3400 Hz speech band.
+        let speech = tone(1000.0, 1.0, 512, 16000);
+        let prob = vad
+            .speech_probability(&speech, 16000)
+            .expect("energy vad should not fail on tone");
+        assert!(prob > 0.5, "a loud in-band tone should read as speech, got {prob}");
+    }
+
+    #[test]
+    fn energy_vad_handles_chunk_lengths_that_are_not_a_power_of_two() {
+        let mut vad = EnergyVad::new(500);
+        let samples = vec![0.01_f32; 500];
+        let prob = vad.speech_probability(&samples, 16000);
+        assert!(prob.is_ok(), "non-power-of-two chunk lengths should be zero-padded, not rejected");
+    }
+
+    #[test]
+    fn energy_vad_reset_reseeds_the_noise_floor() {
+        let mut vad = EnergyVad::new(512);
+        let loud = tone(1000.0, 1.0, 512, 16000);
+        for _ in 0..ENERGY_VAD_SEED_CHUNKS {
+            vad.speech_probability(&loud, 16000)
+                .expect("energy vad should not fail");
+        }
+        vad.reset();
+        assert_eq!(vad.seeded_chunks, 0, "reset should clear the seeding counter");
+        assert_eq!(vad.noise_floor, 0.0, "reset should clear the noise floor");
+    }
+
+    #[test]
+    fn energy_vad_never_divides_by_zero_while_seeding() {
+        // All-zero input keeps the floor at exactly zero throughout seeding;
+        // this must not panic or return NaN/infinite probabilities.
+        let mut vad = EnergyVad::new(512);
+        let silence = vec![0.0_f32; 512];
+        for _ in 0..(ENERGY_VAD_SEED_CHUNKS + 3) {
+            let prob = vad
+                .speech_probability(&silence, 16000)
+                .expect("energy vad should not fail on all-zero input");
+            assert!(prob.is_finite());
+        }
+    }
+
+    /// A VAD driven by a fixed sequence of per-chunk probabilities, so tests
+    /// can exercise the hysteresis state machine precisely.
+    struct ScriptedVad {
+        probabilities: Vec<f32>,
+        call_count: usize,
+    }
+
+    impl VoiceActivityDetector for ScriptedVad {
+        fn speech_probability(
+            &mut self,
+            _samples: &[f32],
+            _sample_rate: u32,
+        ) -> Result<f32, VaaniError> {
+            let prob = self.probabilities[self.call_count];
+            self.call_count += 1;
+            Ok(prob)
+        }
+
+        fn reset(&mut self) {
+            self.call_count = 0;
+        }
+    }
+
+    /// Thresholds sized so one chunk is 32ms and the minimums are small round
+    /// numbers of chunks, which keeps the expected math in these tests simple:
+    /// `min_speech_chunks` = 2, `min_silence_chunks` = 2, `hangover_chunks` = 1.
+    fn test_config() -> SegmentationConfig {
+        SegmentationConfig {
+            onset: 0.5,
+            offset: 0.3,
+            min_speech_ms: 64,
+            min_silence_ms: 64,
+        }
+    }
+
     #[test]
     fn trim_silence_removes_silent_chunks() {
         let mut vad = MockVad { probability: 0.0 };
-        let samples = vec![0.1_f32; CHUNK_SIZE * 10];
-        let result = trim_silence(&samples, 16000, 0.5, &mut vad).expect("trim should not fail");
+        let samples = vec![0.1_f32; DEFAULT_CHUNK_SIZE * 10];
+        let result = trim_silence(&samples, 16000, DEFAULT_CHUNK_SIZE, &test_config(), &mut vad)
+            .expect("trim should not fail");
         assert!(
-            result.is_empty(),
-            "All-silent input should produce empty output"
+            result.samples.is_empty() && result.segments.is_empty(),
+            "All-silent input should produce an empty result"
         );
     }
 
     #[test]
     fn trim_silence_keeps_speech_chunks() {
         let mut vad = MockVad { probability: 0.9 };
-        let samples = vec![0.5_f32; CHUNK_SIZE * 5];
-        let result = trim_silence(&samples, 16000, 0.5, &mut vad).expect("trim should not fail");
+        let samples = vec![0.5_f32; DEFAULT_CHUNK_SIZE * 5];
+        let result = trim_silence(&samples, 16000, DEFAULT_CHUNK_SIZE, &test_config(), &mut vad)
+            .expect("trim should not fail");
         assert_eq!(
-            result.len(),
+            result.samples.len(),
             samples.len(),
             "All-speech input should retain all samples"
         );
+        assert_eq!(result.segments, vec![0..samples.len()]);
     }
 
     #[test]
     fn trim_silence_empty_input_returns_empty() {
         let mut vad = MockVad { probability: 0.9 };
-        let result = trim_silence(&[], 16000, 0.5, &mut vad).expect("trim should not fail");
-        assert!(result.is_empty());
+        let result = trim_silence(&[], 16000, DEFAULT_CHUNK_SIZE, &test_config(), &mut vad)
+            .expect("trim should not fail");
+        assert!(result.samples.is_empty() && result.segments.is_empty());
     }
 
     #[test]
-    fn trim_silence_adds_padding_around_speech() {
-        // Build a mock that returns speech for only one specific chunk (index 5),
-        // silence for all others. We'll have 12 chunks total.
-        struct PatternVad {
-            speech_index: usize,
-            call_count: usize,
-        }
+    fn trim_silence_adds_lead_padding_and_hangover_around_a_speech_blip() {
+        // 12 chunks total; only chunk 5 is speech, everything else is
+        // silence. With lead_padding=3, min_silence_chunks=2 and
+        // hangover=1, the segment should open at 5-3=2 and close once two
+        // consecutive silent chunks (6,7) are seen, extended by one more
+        // chunk of hangover: [2, 7).
+        let num_chunks = 12;
+        let mut probs = vec![0.0_f32; num_chunks];
+        probs[5] = 1.0;
+        let mut vad = ScriptedVad {
+            probabilities: probs,
+            call_count: 0,
+        };
 
-        impl VoiceActivityDetector for PatternVad {
-            fn speech_probability(
-                &mut self,
-                _samples: &[f32],
-                _sample_rate: u32,
-            ) -> Result<f32, VaaniError> {
-                let is_speech = self.call_count == self.speech_index;
-                self.call_count += 1;
-                Ok(if is_speech { 1.0 } else { 0.0 })
-            }
+        let samples = vec![0.1_f32; DEFAULT_CHUNK_SIZE * num_chunks];
+        let result = trim_silence(&samples, 16000, DEFAULT_CHUNK_SIZE, &test_config(), &mut vad)
+            .expect("trim should not fail");
 
-            fn reset(&mut self) {
-                self.call_count = 0;
-            }
+        let expected_range = (2 * DEFAULT_CHUNK_SIZE)..(7 * DEFAULT_CHUNK_SIZE);
+        assert_eq!(result.segments, vec![expected_range.clone()]);
+        assert_eq!(result.samples.len(), expected_range.len());
+    }
+
+    #[test]
+    fn trim_silence_bridges_a_brief_dip_without_splitting_the_segment() {
+        // Speech at chunks 0-2, one silent chunk at 3 (shorter than
+        // min_silence_chunks=2 so it's bridged, not a close), speech again
+        // at 4-6, then sustained silence. Expect a single segment, not two.
+        let mut probs = vec![0.0_f32; 15];
+        for i in [0, 1, 2, 4, 5, 6] {
+            probs[i] = 1.0;
         }
+        let mut vad = ScriptedVad {
+            probabilities: probs,
+            call_count: 0,
+        };
+
+        let samples = vec![0.1_f32; DEFAULT_CHUNK_SIZE * 15];
+        let result = trim_silence(&samples, 16000, DEFAULT_CHUNK_SIZE, &test_config(), &mut vad)
+            .expect("trim should not fail");
 
+        assert_eq!(
+            result.segments.len(),
+            1,
+            "a one-chunk dip should be bridged by hangover, not split into two segments"
+        );
+    }
+
+    #[test]
+    fn trim_silence_drops_segments_shorter_than_min_speech() {
+        // A single speech chunk, but min_speech_ms raised so high that even
+        // with lead-in padding and hangover the segment doesn't meet it.
         let num_chunks = 12;
-        let speech_at = 5;
-        let mut vad = PatternVad {
-            speech_index: speech_at,
+        let mut probs = vec![0.0_f32; num_chunks];
+        probs[5] = 1.0;
+        let mut vad = ScriptedVad {
+            probabilities: probs,
             call_count: 0,
         };
 
-        let samples = vec![0.1_f32; CHUNK_SIZE * num_chunks];
-        let result = trim_silence(&samples, 16000, 0.5, &mut vad).expect("trim should not fail");
+        let config = SegmentationConfig {
+            min_speech_ms: 1000,
+            ..test_config()
+        };
 
-        // Speech at index 5 -> padding keeps indices 2..=8 (5-3 to 5+3), i.e. 7 chunks
-        let expected_start = speech_at.saturating_sub(PADDING_CHUNKS); // 2
-        let expected_end = (speech_at + PADDING_CHUNKS + 1).min(num_chunks); // 9
-        let expected_kept = expected_end - expected_start; // 7
+        let samples = vec![0.1_f32; DEFAULT_CHUNK_SIZE * num_chunks];
+        let result = trim_silence(&samples, 16000, DEFAULT_CHUNK_SIZE, &config, &mut vad)
+            .expect("trim should not fail");
 
-        assert_eq!(
-            result.len(),
-            expected_kept * CHUNK_SIZE,
-            "Expected {expected_kept} chunks ({} samples), got {} samples",
-            expected_kept * CHUNK_SIZE,
-            result.len()
+        assert!(
+            result.samples.is_empty() && result.segments.is_empty(),
+            "a blip too short for min_speech_ms should be dropped entirely"
         );
     }
 
@@ -347,8 +858,9 @@ mod tests {
             reset_count: Arc::clone(&reset_count),
         };
 
-        let samples = vec![0.1_f32; CHUNK_SIZE * 3];
-        let _ = trim_silence(&samples, 16000, 0.5, &mut vad).expect("trim should not fail");
+        let samples = vec![0.1_f32; DEFAULT_CHUNK_SIZE * 3];
+        let _ = trim_silence(&samples, 16000, DEFAULT_CHUNK_SIZE, &test_config(), &mut vad)
+            .expect("trim should not fail");
 
         assert_eq!(
             reset_count.load(Ordering::SeqCst),
@@ -356,4 +868,77 @@ mod tests {
             "VAD should be reset exactly once before processing"
         );
     }
+
+    #[test]
+    fn chunks_for_ms_is_equivalent_across_supported_rates() {
+        // 512 @ 16 kHz and 256 @ 8 kHz are both 32ms chunks, so they should
+        // resolve to the same chunk count for the same duration.
+        assert_eq!(
+            chunks_for_ms(LEAD_PADDING_MS, 512, 16000),
+            chunks_for_ms(LEAD_PADDING_MS, 256, 8000)
+        );
+        assert_eq!(chunks_for_ms(LEAD_PADDING_MS, 512, 16000), 3);
+    }
+
+    #[test]
+    fn with_chunk_size_rejects_unsupported_combinations() {
+        // Validation happens before the model file is ever touched, so a
+        // bogus path is fine here.
+        let bogus_path = Path::new("/nonexistent/model.onnx");
+        let err = SileroVad::with_chunk_size(bogus_path, 512, 8000).unwrap_err();
+        assert!(err.to_string().contains("Unsupported"));
+
+        let err = SileroVad::with_chunk_size(bogus_path, 1024, 16000).unwrap_err();
+        assert!(err.to_string().contains("Unsupported"));
+    }
+
+    #[test]
+    fn hash_file_blake3_matches_a_known_digest() {
+        let mut file = tempfile::NamedTempFile::new().expect("failed to create temp file");
+        file.write_all(b"hello world").expect("failed to write temp file");
+
+        let digest = hash_file_blake3(file.path()).expect("hashing should not fail");
+        // Precomputed BLAKE3 hex digest of the ASCII bytes "hello world".
+        assert_eq!(
+            digest,
+            "d74981efa70a0c880b8d8c1985d075dbcbf679b99a5f9914e5aaf96b831a9e24"
+        );
+    }
+
+    #[test]
+    fn hash_file_blake3_errors_on_missing_file() {
+        let err = hash_file_blake3(Path::new("/nonexistent/model.onnx")).unwrap_err();
+        assert!(err.to_string().contains("Failed to open"));
+    }
+
+    #[test]
+    fn new_verified_rejects_a_hash_mismatch_without_touching_ort() {
+        let mut file = tempfile::NamedTempFile::new().expect("failed to create temp file");
+        file.write_all(b"not a real onnx model").expect("failed to write temp file");
+
+        let err = SileroVad::new_verified(file.path(), "0000000000000000000000000000000000000000000000000000000000000000")
+            .unwrap_err();
+        assert!(err.to_string().contains("integrity check failed"));
+    }
+
+    #[test]
+    fn trim_silence_works_with_8khz_chunk_size() {
+        let mut vad = MockVad { probability: 0.9 };
+        let samples = vec![0.5_f32; 256 * 5];
+        let result = trim_silence(&samples, 8000, 256, &test_config(), &mut vad)
+            .expect("trim should not fail");
+        assert_eq!(
+            result.samples.len(),
+            samples.len(),
+            "All-speech input should retain all samples at 8 kHz"
+        );
+    }
+
+    #[test]
+    fn segmentation_config_default_has_sane_thresholds() {
+        let config = SegmentationConfig::default();
+        assert!(config.offset < config.onset);
+        assert!(config.min_speech_ms > 0);
+        assert!(config.min_silence_ms > 0);
+    }
 }
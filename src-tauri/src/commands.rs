@@ -14,11 +14,14 @@
 //!     commands::get_api_keys_status,
 //!     commands::set_api_key,
 //!     commands::list_microphones,
+//!     commands::get_default_microphone,
 //!     commands::start_mic_test,
 //!     commands::get_mic_level,
 //!     commands::stop_mic_test,
 //!     commands::get_hotkey,
 //!     commands::set_hotkey,
+//!     commands::set_mic_sensitivity,
+//!     commands::set_noise_gate,
 //!     commands::check_permissions,
 //!     commands::request_accessibility,
 //!     commands::open_accessibility_settings,
@@ -26,19 +29,23 @@
 //!     commands::get_version,
 //!     commands::open_log_file,
 //!     commands::open_config_dir,
+//!     commands::config_doctor,
 //!     commands::close_window,
 //! ])
 //! ```
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use serde::Serialize;
 use tauri::State;
 
 use crate::app::VaaniApp;
-use crate::config::VaaniConfig;
+use crate::config::{ModeConfig, VaaniConfig};
 use crate::error::VaaniError;
 use crate::keychain::create_secret_storage;
+use crate::output::clipboard::CustomClipboardCommand;
+use crate::permissions;
 
 // ── Serializable response types ────────────────────────────────────────────
 
@@ -47,20 +54,33 @@ use crate::keychain::create_secret_storage;
 pub struct ApiKeysStatus {
     pub openai: bool,
     pub anthropic: bool,
+    pub deepgram: bool,
 }
 
 /// Status of system permissions.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct PermissionsStatus {
     pub mic: bool,
     pub accessibility: bool,
 }
 
+impl PermissionsStatus {
+    /// Queries the live platform status via [`crate::permissions`].
+    pub fn current() -> Self {
+        Self {
+            mic: permissions::is_mic_authorized(),
+            accessibility: permissions::is_accessibility_trusted(),
+        }
+    }
+}
+
 /// A microphone device with its index and display name.
 #[derive(Debug, Serialize)]
 pub struct MicrophoneInfo {
     pub index: u32,
     pub name: String,
+    /// Whether this is the system's current default input device.
+    pub is_default: bool,
 }
 
 // ── Commands ───────────────────────────────────────────────────────────────
@@ -117,7 +137,22 @@ pub fn get_api_keys_status(_app: State<'_, Arc<VaaniApp>>) -> Result<ApiKeysStat
             .map(|k| !k.is_empty())
             .unwrap_or(false);
 
-    Ok(ApiKeysStatus { openai, anthropic })
+    let deepgram = storage
+        .get("deepgram_api_key")
+        .ok()
+        .flatten()
+        .map(|k| !k.is_empty())
+        .unwrap_or(false)
+        || std::env::var("VAANI_DEEPGRAM_API_KEY")
+            .or_else(|_| std::env::var("DEEPGRAM_API_KEY"))
+            .map(|k| !k.is_empty())
+            .unwrap_or(false);
+
+    Ok(ApiKeysStatus {
+        openai,
+        anthropic,
+        deepgram,
+    })
 }
 
 /// Stores an API key in the system keychain.
@@ -144,23 +179,49 @@ pub fn set_api_key(
     Ok(())
 }
 
-/// Returns all available audio input devices.
+/// Returns all available audio input devices, with the system default
+/// flagged via [`MicrophoneInfo::is_default`].
 #[tauri::command]
 pub fn list_microphones(_app: State<'_, Arc<VaaniApp>>) -> Result<Vec<MicrophoneInfo>, VaaniError> {
     let devices = crate::audio::capture::list_input_devices()?;
+    let default_index = crate::audio::capture::default_input_device()?.map(|(index, _)| index);
+
     let mics = devices
         .into_iter()
-        .map(|(index, name)| MicrophoneInfo { index, name })
+        .map(|(index, name)| MicrophoneInfo {
+            is_default: Some(index) == default_index,
+            index,
+            name,
+        })
         .collect();
     Ok(mics)
 }
 
-/// Starts a microphone test session. Stub — actual mic test requires an
-/// `AudioRecorder` which holds a non-Send cpal `Stream`.
+/// Returns the system's current default input device, or `None` if no input
+/// device is available at all. Used by the onboarding UI to pre-select a
+/// sensible microphone before the user has chosen one.
 #[tauri::command]
-pub fn start_mic_test(_app: State<'_, Arc<VaaniApp>>) -> Result<(), VaaniError> {
-    tracing::info!("Mic test start requested (stub)");
-    Ok(())
+pub fn get_default_microphone(
+    _app: State<'_, Arc<VaaniApp>>,
+) -> Result<Option<MicrophoneInfo>, VaaniError> {
+    let default = crate::audio::capture::default_input_device()?;
+    Ok(default.map(|(index, name)| MicrophoneInfo {
+        index,
+        name,
+        is_default: true,
+    }))
+}
+
+/// Starts a microphone test session on the given device (or the default
+/// input device if `device_index` is omitted), so the onboarding UI can show
+/// a live level meter.
+#[tauri::command]
+pub fn start_mic_test(
+    app: State<'_, Arc<VaaniApp>>,
+    device_index: Option<u32>,
+) -> Result<(), VaaniError> {
+    tracing::info!(?device_index, "Mic test start requested");
+    app.start_mic_test(device_index)
 }
 
 /// Returns the current microphone input level (0.0 to 1.0).
@@ -169,11 +230,11 @@ pub fn get_mic_level(app: State<'_, Arc<VaaniApp>>) -> Result<f32, VaaniError> {
     Ok(app.current_mic_level())
 }
 
-/// Stops a microphone test session. Stub for now.
+/// Stops the current microphone test session.
 #[tauri::command]
-pub fn stop_mic_test(_app: State<'_, Arc<VaaniApp>>) -> Result<(), VaaniError> {
-    tracing::info!("Mic test stop requested (stub)");
-    Ok(())
+pub fn stop_mic_test(app: State<'_, Arc<VaaniApp>>) -> Result<(), VaaniError> {
+    tracing::info!("Mic test stop requested");
+    app.stop_mic_test()
 }
 
 /// Returns the currently configured hotkey string.
@@ -188,11 +249,26 @@ pub fn get_hotkey(app: State<'_, Arc<VaaniApp>>) -> Result<String, VaaniError> {
     Ok(hotkey)
 }
 
-/// Updates the hotkey in config and persists to disk.
+/// Validates and registers `hotkey` as the system-wide shortcut, then
+/// persists it. Rejects unparseable or already-claimed combos (e.g. another
+/// app has already registered the same key) before touching the config, so
+/// a bad value never gets saved.
 #[tauri::command]
-pub fn set_hotkey(app: State<'_, Arc<VaaniApp>>, hotkey: String) -> Result<(), VaaniError> {
-    let mut config = app.config.lock().unwrap_or_else(|e| e.into_inner()).clone();
+pub fn set_hotkey(
+    app: State<'_, Arc<VaaniApp>>,
+    app_handle: tauri::AppHandle,
+    hotkey: String,
+) -> Result<(), VaaniError> {
+    let previous = app
+        .config
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .hotkey
+        .clone();
+
+    crate::hotkey::manager::apply_hotkey(&app_handle, Some(&previous), &hotkey)?;
 
+    let mut config = app.config.lock().unwrap_or_else(|e| e.into_inner()).clone();
     config.hotkey = hotkey;
     crate::config::save_config(&config)?;
 
@@ -201,21 +277,58 @@ pub fn set_hotkey(app: State<'_, Arc<VaaniApp>>, hotkey: String) -> Result<(), V
     Ok(())
 }
 
-/// Checks microphone and accessibility permissions. Returns all-true stub
-/// until Phase 5 adds real permission checks.
+/// Sets the input gain multiplier applied to captured audio (`1.0` = unity).
+/// Rejects out-of-range values before touching the config, so a bad value
+/// never gets saved.
+#[tauri::command]
+pub fn set_mic_sensitivity(
+    app: State<'_, Arc<VaaniApp>>,
+    sensitivity: f32,
+) -> Result<(), VaaniError> {
+    let mut config = app.config.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    config.mic_sensitivity = sensitivity;
+    config.validate()?;
+    crate::config::save_config(&config)?;
+
+    *app.config.lock().unwrap_or_else(|e| e.into_inner()) = config;
+    tracing::info!(sensitivity, "Mic sensitivity updated");
+    Ok(())
+}
+
+/// Sets the noise-gate threshold below which captured audio is treated as
+/// silence. Rejects out-of-range values before touching the config, so a
+/// bad value never gets saved.
+#[tauri::command]
+pub fn set_noise_gate(app: State<'_, Arc<VaaniApp>>, threshold: f32) -> Result<(), VaaniError> {
+    let mut config = app.config.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    config.noise_gate_threshold = threshold;
+    config.validate()?;
+    crate::config::save_config(&config)?;
+
+    *app.config.lock().unwrap_or_else(|e| e.into_inner()) = config;
+    tracing::info!(threshold, "Noise gate threshold updated");
+    Ok(())
+}
+
+/// Checks microphone and Accessibility permissions via the platform's
+/// native authorization APIs.
 #[tauri::command]
 pub fn check_permissions(_app: State<'_, Arc<VaaniApp>>) -> Result<PermissionsStatus, VaaniError> {
-    Ok(PermissionsStatus {
-        mic: true,
-        accessibility: true,
-    })
+    Ok(PermissionsStatus::current())
 }
 
-/// Requests accessibility permission. Stub — returns true.
+/// Triggers the Accessibility trust prompt and returns whether it's
+/// trusted at the moment the call returns.
+///
+/// Trust is granted asynchronously once the user flips the toggle in
+/// System Settings, so this typically still returns `false` right after
+/// prompting — the background poll started in `lib.rs` is what notifies
+/// the UI via a `permissions-changed` event once that happens.
 #[tauri::command]
 pub fn request_accessibility(_app: State<'_, Arc<VaaniApp>>) -> Result<bool, VaaniError> {
-    tracing::info!("Accessibility permission requested (stub)");
-    Ok(true)
+    let trusted = permissions::prompt_for_accessibility();
+    tracing::info!(trusted, "Accessibility permission requested");
+    Ok(trusted)
 }
 
 /// Opens the macOS System Settings accessibility pane.
@@ -243,9 +356,26 @@ pub fn get_version(_app: State<'_, Arc<VaaniApp>>) -> Result<String, VaaniError>
     Ok(env!("CARGO_PKG_VERSION").to_string())
 }
 
-/// Opens the log file. Stub for now.
+/// Writes the recent state transition trail to the tracing log, then opens
+/// the log file. Opening the file itself is a stub for now, but the state
+/// trail dump lets a bug report ("it got stuck in Processing") be
+/// reconstructed from the log alone.
 #[tauri::command]
-pub fn open_log_file(_app: State<'_, Arc<VaaniApp>>) -> Result<(), VaaniError> {
+pub fn open_log_file(app: State<'_, Arc<VaaniApp>>) -> Result<(), VaaniError> {
+    let state = app.state.lock();
+    for record in state.history() {
+        tracing::info!(
+            from = %record.from,
+            to = %record.to,
+            action = %record.action,
+            "state trail: {} -> {} ({})",
+            record.from,
+            record.to,
+            record.action
+        );
+    }
+    drop(state);
+
     tracing::info!("Open log file requested (stub)");
     Ok(())
 }
@@ -257,6 +387,14 @@ pub fn open_config_dir(_app: State<'_, Arc<VaaniApp>>) -> Result<(), VaaniError>
     open_path_in_file_manager(&dir)
 }
 
+/// Returns a `vaani config doctor`-style diagnostic report: the effective
+/// configuration with each field's value annotated by where it came from
+/// (default, file, import, or env), and any validation warnings.
+#[tauri::command]
+pub fn config_doctor(_app: State<'_, Arc<VaaniApp>>) -> Result<String, VaaniError> {
+    Ok(crate::config::config_doctor_report(None))
+}
+
 /// Closes the calling webview window.
 #[tauri::command]
 pub fn close_window(window: tauri::WebviewWindow) -> Result<(), VaaniError> {
@@ -281,6 +419,15 @@ fn merge_config_fields(config: &mut VaaniConfig, data: &serde_json::Value) {
     if let Some(v) = data.get("vad_threshold").and_then(|v| v.as_f64()) {
         config.vad_threshold = v as f32;
     }
+    if let Some(v) = data.get("mic_sensitivity").and_then(|v| v.as_f64()) {
+        config.mic_sensitivity = v as f32;
+    }
+    if let Some(v) = data.get("noise_gate_threshold").and_then(|v| v.as_f64()) {
+        config.noise_gate_threshold = v as f32;
+    }
+    if let Some(v) = data.get("tray_blink_interval_ms").and_then(|v| v.as_u64()) {
+        config.tray_blink_interval_ms = v as u32;
+    }
     if let Some(v) = data.get("max_recording_seconds").and_then(|v| v.as_u64()) {
         config.max_recording_seconds = v as u32;
     }
@@ -294,9 +441,34 @@ fn merge_config_fields(config: &mut VaaniConfig, data: &serde_json::Value) {
     if let Some(v) = data.get("stt_model").and_then(|v| v.as_str()) {
         config.stt_model = v.to_string();
     }
+    if let Some(v) = data.get("stt_provider").and_then(|v| v.as_str()) {
+        config.stt_provider = v.to_string();
+    }
+    if let Some(v) = data.get("stt_task").and_then(|v| v.as_str()) {
+        config.stt_task = v.to_string();
+    }
     if let Some(v) = data.get("llm_model").and_then(|v| v.as_str()) {
         config.llm_model = v.to_string();
     }
+    if let Some(v) = data.get("llm_provider").and_then(|v| v.as_str()) {
+        config.llm_provider = v.to_string();
+    }
+    if let Some(v) = data.get("llm_base_url") {
+        if v.is_null() {
+            config.llm_base_url = None;
+        } else if let Some(url) = v.as_str() {
+            config.llm_base_url = Some(url.to_string());
+        }
+    }
+    if let Some(v) = data.get("speak_result").and_then(|v| v.as_bool()) {
+        config.speak_result = v;
+    }
+    if let Some(v) = data.get("tts_model").and_then(|v| v.as_str()) {
+        config.tts_model = v.to_string();
+    }
+    if let Some(v) = data.get("tts_voice").and_then(|v| v.as_str()) {
+        config.tts_voice = v.to_string();
+    }
     if let Some(v) = data.get("active_mode").and_then(|v| v.as_str()) {
         config.active_mode = v.to_string();
     }
@@ -312,6 +484,35 @@ fn merge_config_fields(config: &mut VaaniConfig, data: &serde_json::Value) {
     if let Some(v) = data.get("onboarding_completed").and_then(|v| v.as_bool()) {
         config.onboarding_completed = v;
     }
+    if let Some(v) = data.get("clipboard_provider").and_then(|v| v.as_str()) {
+        config.clipboard_provider = v.to_string();
+    }
+    if let Some(v) = data.get("clipboard_custom_copy") {
+        if v.is_null() {
+            config.clipboard_custom_copy = None;
+        } else if let Ok(cmd) = serde_json::from_value::<CustomClipboardCommand>(v.clone()) {
+            config.clipboard_custom_copy = Some(cmd);
+        }
+    }
+    if let Some(v) = data.get("clipboard_custom_paste") {
+        if v.is_null() {
+            config.clipboard_custom_paste = None;
+        } else if let Ok(cmd) = serde_json::from_value::<CustomClipboardCommand>(v.clone()) {
+            config.clipboard_custom_paste = Some(cmd);
+        }
+    }
+    if let Some(v) = data.get("modes") {
+        if let Ok(modes) = serde_json::from_value::<HashMap<String, ModeConfig>>(v.clone()) {
+            config.modes = modes;
+            config.merge_default_modes();
+        }
+    }
+    if let Some(v) = data.get("noise_suppression_enabled").and_then(|v| v.as_bool()) {
+        config.noise_suppression_enabled = v;
+    }
+    if let Some(v) = data.get("trim_silence_enabled").and_then(|v| v.as_bool()) {
+        config.trim_silence_enabled = v;
+    }
 }
 
 /// Opens the macOS Accessibility preference pane via the system URL scheme.
@@ -393,10 +594,12 @@ mod tests {
         let mic = MicrophoneInfo {
             index: 0,
             name: "Built-in Microphone".to_string(),
+            is_default: true,
         };
         let json = serde_json::to_value(&mic).expect("should serialize");
         assert_eq!(json["index"], 0);
         assert_eq!(json["name"], "Built-in Microphone");
+        assert_eq!(json["is_default"], true);
     }
 
     #[test]
@@ -415,10 +618,12 @@ mod tests {
         let status = ApiKeysStatus {
             openai: true,
             anthropic: false,
+            deepgram: false,
         };
         let json = serde_json::to_value(&status).expect("should serialize");
         assert_eq!(json["openai"], true);
         assert_eq!(json["anthropic"], false);
+        assert_eq!(json["deepgram"], false);
     }
 
     #[test]
@@ -461,6 +666,120 @@ mod tests {
         assert_eq!(config.microphone_device, None);
     }
 
+    #[test]
+    fn merge_config_null_llm_base_url() {
+        let mut config = VaaniConfig {
+            llm_base_url: Some("http://localhost:8080/v1/chat/completions".to_string()),
+            ..Default::default()
+        };
+        let data = serde_json::json!({
+            "llm_base_url": null,
+        });
+
+        merge_config_fields(&mut config, &data);
+
+        assert_eq!(config.llm_base_url, None);
+    }
+
+    #[test]
+    fn merge_config_clipboard_fields() {
+        let mut config = VaaniConfig::default();
+        let data = serde_json::json!({
+            "clipboard_provider": "custom",
+            "clipboard_custom_copy": {"command": "wl-copy", "args": []},
+            "clipboard_custom_paste": {"command": "wl-paste", "args": ["-n"]},
+        });
+
+        merge_config_fields(&mut config, &data);
+
+        assert_eq!(config.clipboard_provider, "custom");
+        assert_eq!(
+            config.clipboard_custom_copy,
+            Some(CustomClipboardCommand {
+                command: "wl-copy".to_string(),
+                args: vec![],
+            })
+        );
+        assert_eq!(
+            config.clipboard_custom_paste,
+            Some(CustomClipboardCommand {
+                command: "wl-paste".to_string(),
+                args: vec!["-n".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn merge_config_null_clipboard_custom_commands() {
+        let mut config = VaaniConfig {
+            clipboard_custom_copy: Some(CustomClipboardCommand {
+                command: "wl-copy".to_string(),
+                args: vec![],
+            }),
+            clipboard_custom_paste: Some(CustomClipboardCommand {
+                command: "wl-paste".to_string(),
+                args: vec![],
+            }),
+            ..Default::default()
+        };
+        let data = serde_json::json!({
+            "clipboard_custom_copy": null,
+            "clipboard_custom_paste": null,
+        });
+
+        merge_config_fields(&mut config, &data);
+
+        assert_eq!(config.clipboard_custom_copy, None);
+        assert_eq!(config.clipboard_custom_paste, None);
+    }
+
+    #[test]
+    fn merge_config_modes_replaces_map_and_keeps_builtins() {
+        let mut config = VaaniConfig::default();
+        let data = serde_json::json!({
+            "modes": {
+                "pirate": {"prompt": "Rewrite like a pirate."},
+            },
+        });
+
+        merge_config_fields(&mut config, &data);
+
+        assert_eq!(config.modes["pirate"].prompt, "Rewrite like a pirate.");
+        for mode in crate::config::MODES {
+            assert!(config.modes.contains_key(*mode), "missing builtin '{mode}'");
+        }
+    }
+
+    #[test]
+    fn merge_config_noise_suppression_enabled() {
+        let mut config = VaaniConfig {
+            noise_suppression_enabled: false,
+            ..Default::default()
+        };
+        let data = serde_json::json!({
+            "noise_suppression_enabled": true,
+        });
+
+        merge_config_fields(&mut config, &data);
+
+        assert!(config.noise_suppression_enabled);
+    }
+
+    #[test]
+    fn merge_config_trim_silence_enabled() {
+        let mut config = VaaniConfig {
+            trim_silence_enabled: false,
+            ..Default::default()
+        };
+        let data = serde_json::json!({
+            "trim_silence_enabled": true,
+        });
+
+        merge_config_fields(&mut config, &data);
+
+        assert!(config.trim_silence_enabled);
+    }
+
     #[test]
     fn merge_config_all_fields() {
         let mut config = VaaniConfig::default();
@@ -468,10 +787,20 @@ mod tests {
             "hotkey": "ctrl",
             "sample_rate": 44100,
             "vad_threshold": 0.1,
+            "mic_sensitivity": 1.5,
+            "noise_gate_threshold": 0.02,
+            "tray_blink_interval_ms": 400,
             "max_recording_seconds": 300,
             "microphone_device": 3,
             "stt_model": "whisper-2",
+            "stt_provider": "deepgram",
+            "stt_task": "translate",
             "llm_model": "claude-sonnet-4-20250514",
+            "llm_provider": "openai",
+            "llm_base_url": "http://localhost:8080/v1/chat/completions",
+            "speak_result": true,
+            "tts_model": "tts-1-hd",
+            "tts_voice": "nova",
             "active_mode": "code",
             "sounds_enabled": false,
             "paste_restore_delay_ms": 200,
@@ -484,10 +813,23 @@ mod tests {
         assert_eq!(config.hotkey, "ctrl");
         assert_eq!(config.sample_rate, 44100);
         assert!((config.vad_threshold - 0.1).abs() < f32::EPSILON);
+        assert!((config.mic_sensitivity - 1.5).abs() < f32::EPSILON);
+        assert!((config.noise_gate_threshold - 0.02).abs() < f32::EPSILON);
+        assert_eq!(config.tray_blink_interval_ms, 400);
         assert_eq!(config.max_recording_seconds, 300);
         assert_eq!(config.microphone_device, Some(3));
         assert_eq!(config.stt_model, "whisper-2");
+        assert_eq!(config.stt_provider, "deepgram");
+        assert_eq!(config.stt_task, "translate");
         assert_eq!(config.llm_model, "claude-sonnet-4-20250514");
+        assert_eq!(config.llm_provider, "openai");
+        assert_eq!(
+            config.llm_base_url.as_deref(),
+            Some("http://localhost:8080/v1/chat/completions")
+        );
+        assert!(config.speak_result);
+        assert_eq!(config.tts_model, "tts-1-hd");
+        assert_eq!(config.tts_voice, "nova");
         assert_eq!(config.active_mode, "code");
         assert!(!config.sounds_enabled);
         assert_eq!(config.paste_restore_delay_ms, 200);
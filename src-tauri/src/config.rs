@@ -1,9 +1,26 @@
-use std::path::PathBuf;
-
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::Duration;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
 use crate::error::VaaniError;
+use crate::output::clipboard::{CustomClipboardCommand, CLIPBOARD_PROVIDERS};
+use crate::enhance::LLM_PROVIDERS;
+use crate::transcribe::{STT_PROVIDERS, STT_TASKS};
+
+/// How long to wait after the first change event before reloading, so a
+/// burst of editor writes (save-as-temp-then-rename, multiple saves in
+/// quick succession) collapses into a single reload.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Maximum depth of `import:` chains before we assume a cycle and stop.
+const IMPORT_RECURSION_LIMIT: u32 = 5;
 
 /// Canonical list of all enhancement modes.
 ///
@@ -25,6 +42,26 @@ fn default_vad_threshold() -> f32 {
     0.05
 }
 
+fn default_mic_sensitivity() -> f32 {
+    1.0
+}
+
+fn default_noise_gate_threshold() -> f32 {
+    0.0
+}
+
+fn default_noise_suppression_enabled() -> bool {
+    false
+}
+
+fn default_trim_silence_enabled() -> bool {
+    false
+}
+
+fn default_tray_blink_interval_ms() -> u32 {
+    750
+}
+
 fn default_max_recording_seconds() -> u32 {
     600
 }
@@ -37,10 +74,38 @@ fn default_stt_model() -> String {
     "whisper-1".to_string()
 }
 
+fn default_stt_provider() -> String {
+    "openai".to_string()
+}
+
+fn default_stt_task() -> String {
+    "transcribe".to_string()
+}
+
 fn default_llm_model() -> String {
     "claude-haiku-4-5-20251001".to_string()
 }
 
+fn default_llm_provider() -> String {
+    "anthropic".to_string()
+}
+
+fn default_llm_base_url() -> Option<String> {
+    None
+}
+
+fn default_speak_result() -> bool {
+    false
+}
+
+fn default_tts_model() -> String {
+    "tts-1".to_string()
+}
+
+fn default_tts_voice() -> String {
+    "alloy".to_string()
+}
+
 fn default_active_mode() -> String {
     "professional".to_string()
 }
@@ -61,8 +126,72 @@ fn default_onboarding_completed() -> bool {
     false
 }
 
+fn default_clipboard_provider() -> String {
+    "auto".to_string()
+}
+
+fn default_clipboard_custom_copy() -> Option<CustomClipboardCommand> {
+    None
+}
+
+fn default_clipboard_custom_paste() -> Option<CustomClipboardCommand> {
+    None
+}
+
+/// The built-in modes' default prompts, keyed the same as [`MODES`].
+///
+/// These are short placeholders, not the real bundled prompt text (see
+/// `prompts.rs`, which remains the source of truth for built-in mode
+/// prompts). They exist so `modes:` always lists every built-in, even when
+/// the user's config doesn't mention them, and so tooling that enumerates
+/// `VaaniConfig::modes` (e.g. a future Settings mode picker) sees a
+/// complete set.
+fn default_modes() -> HashMap<String, ModeConfig> {
+    [
+        ("minimal", "Lightly clean up grammar and filler words only."),
+        ("professional", "Rewrite in a formal, polished register."),
+        ("casual", "Rewrite in a relaxed, conversational register."),
+        ("code", "Format as a code comment or commit message."),
+        ("funny", "Rewrite with a playful, humorous tone."),
+    ]
+    .into_iter()
+    .map(|(name, prompt)| {
+        (
+            name.to_string(),
+            ModeConfig {
+                prompt: prompt.to_string(),
+                icon: None,
+                description: None,
+            },
+        )
+    })
+    .collect()
+}
+
 // ── VaaniConfig ────────────────────────────────────────────────────────────
 
+/// A single enhancement mode: its LLM system prompt plus optional UI
+/// metadata.
+///
+/// Built-in modes ([`MODES`]) get a default entry here via
+/// [`default_modes`], but their actual system prompt is still assembled by
+/// `prompts::build_system_prompt` from the bundled/user-override prompt
+/// files — `prompt` here only drives the pipeline for user-defined modes
+/// (see [`VaaniConfig::custom_mode_prompt`]).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModeConfig {
+    /// The LLM system prompt used to enhance text in this mode.
+    pub prompt: String,
+
+    /// Optional icon identifier shown next to the mode in Settings.
+    #[serde(default)]
+    pub icon: Option<String>,
+
+    /// Optional human-readable description shown in Settings.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
 /// Application configuration persisted as YAML at `~/.vaani/config.yaml`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct VaaniConfig {
@@ -72,9 +201,48 @@ pub struct VaaniConfig {
     #[serde(default = "default_sample_rate")]
     pub sample_rate: u32,
 
+    /// Speech-onset probability for [`trim_silence_enabled`](Self::trim_silence_enabled)'s
+    /// VAD segmentation — see `audio::vad::SegmentationConfig::onset`. Must
+    /// be between `0.01` and `0.5`.
     #[serde(default = "default_vad_threshold")]
     pub vad_threshold: f32,
 
+    /// Linear gain multiplier applied to captured samples before they reach
+    /// VAD, the noise gate, or the recording buffer. `1.0` is unity gain;
+    /// values above `1.0` boost a quiet microphone, values below `1.0`
+    /// attenuate a hot one.
+    #[serde(default = "default_mic_sensitivity")]
+    pub mic_sensitivity: f32,
+
+    /// RMS level (after [`mic_sensitivity`](Self::mic_sensitivity) is
+    /// applied) below which captured audio is treated as silence — the
+    /// level meter reads `0.0` and the frame is zeroed out rather than fed
+    /// into the recording buffer. `0.0` disables the gate entirely.
+    #[serde(default = "default_noise_gate_threshold")]
+    pub noise_gate_threshold: f32,
+
+    /// Whether to run captured audio through spectral-subtraction denoising
+    /// (see `audio::processing::denoise`) before gain normalization.
+    /// Defaults to `false` since it costs an FFT pass over the whole
+    /// recording and only pays off on genuinely noisy microphones.
+    #[serde(default = "default_noise_suppression_enabled")]
+    pub noise_suppression_enabled: bool,
+
+    /// Whether to strip leading/trailing silence (and dead air in the
+    /// middle) from captured audio via `audio::vad::trim_silence` before
+    /// transcription, using [`vad_threshold`](Self::vad_threshold) as the
+    /// segmentation onset. Defaults to `false` — most STT backends already
+    /// handle silence gracefully, and this costs an extra VAD pass.
+    #[serde(default = "default_trim_silence_enabled")]
+    pub trim_silence_enabled: bool,
+
+    /// How often, in milliseconds, the tray icon alternates between its
+    /// solid and dimmed phases while recording is active. Lower values
+    /// blink faster; the tray reverts to the solid icon as soon as
+    /// recording stops.
+    #[serde(default = "default_tray_blink_interval_ms")]
+    pub tray_blink_interval_ms: u32,
+
     #[serde(default = "default_max_recording_seconds")]
     pub max_recording_seconds: u32,
 
@@ -84,9 +252,46 @@ pub struct VaaniConfig {
     #[serde(default = "default_stt_model")]
     pub stt_model: String,
 
+    /// Which speech-to-text backend to use: one of
+    /// `transcribe::STT_PROVIDERS`. The matching API key is resolved the
+    /// same way as other providers — keychain first, then environment
+    /// variables (see `app::resolve_api_key`).
+    #[serde(default = "default_stt_provider")]
+    pub stt_provider: String,
+
+    /// Whether to transcribe audio in its spoken language or translate it
+    /// into English: one of `transcribe::STT_TASKS`. Translation is only
+    /// implemented for `stt_provider: "openai"` today (see
+    /// `app::VaaniApp::process_audio`).
+    #[serde(default = "default_stt_task")]
+    pub stt_task: String,
+
     #[serde(default = "default_llm_model")]
     pub llm_model: String,
 
+    /// Which LLM backend to enhance with: one of `enhance::LLM_PROVIDERS`.
+    /// The matching API key is resolved the same way as the STT providers
+    /// (keychain first, then environment variables).
+    #[serde(default = "default_llm_provider")]
+    pub llm_provider: String,
+
+    /// Custom chat-completions endpoint for `llm_provider: "openai"`, e.g.
+    /// a local llama.cpp or vLLM server. `None` uses OpenAI's hosted API.
+    /// Ignored by `llm_provider: "anthropic"`.
+    #[serde(default = "default_llm_base_url")]
+    pub llm_base_url: Option<String>,
+
+    /// Read the enhanced result back via OpenAI text-to-speech once pasted
+    /// (see `tts::speak`), as an accessibility/confirmation loop.
+    #[serde(default = "default_speak_result")]
+    pub speak_result: bool,
+
+    #[serde(default = "default_tts_model")]
+    pub tts_model: String,
+
+    #[serde(default = "default_tts_voice")]
+    pub tts_voice: String,
+
     #[serde(default = "default_active_mode")]
     pub active_mode: String,
 
@@ -101,6 +306,29 @@ pub struct VaaniConfig {
 
     #[serde(default = "default_onboarding_completed")]
     pub onboarding_completed: bool,
+
+    /// Which clipboard backend to use: one of `CLIPBOARD_PROVIDERS`.
+    ///
+    /// `"auto"` (the default) lets Vaani detect the best available backend
+    /// for the current environment. `"custom"` requires both
+    /// `clipboard_custom_copy` and `clipboard_custom_paste` to be set.
+    #[serde(default = "default_clipboard_provider")]
+    pub clipboard_provider: String,
+
+    #[serde(default = "default_clipboard_custom_copy")]
+    pub clipboard_custom_copy: Option<CustomClipboardCommand>,
+
+    #[serde(default = "default_clipboard_custom_paste")]
+    pub clipboard_custom_paste: Option<CustomClipboardCommand>,
+
+    /// User-defined enhancement modes, keyed by mode name.
+    ///
+    /// `active_mode` may be set to any key here in addition to the
+    /// built-in [`MODES`]. The five built-ins are always merged in (see
+    /// [`default_modes`]) so this map is never missing them, even when the
+    /// user's config only adds a custom entry.
+    #[serde(default = "default_modes")]
+    pub modes: HashMap<String, ModeConfig>,
 }
 
 impl Default for VaaniConfig {
@@ -109,26 +337,69 @@ impl Default for VaaniConfig {
             hotkey: default_hotkey(),
             sample_rate: default_sample_rate(),
             vad_threshold: default_vad_threshold(),
+            mic_sensitivity: default_mic_sensitivity(),
+            noise_gate_threshold: default_noise_gate_threshold(),
+            noise_suppression_enabled: default_noise_suppression_enabled(),
+            trim_silence_enabled: default_trim_silence_enabled(),
+            tray_blink_interval_ms: default_tray_blink_interval_ms(),
             max_recording_seconds: default_max_recording_seconds(),
             microphone_device: default_microphone_device(),
             stt_model: default_stt_model(),
+            stt_provider: default_stt_provider(),
+            stt_task: default_stt_task(),
             llm_model: default_llm_model(),
+            llm_provider: default_llm_provider(),
+            llm_base_url: default_llm_base_url(),
+            speak_result: default_speak_result(),
+            tts_model: default_tts_model(),
+            tts_voice: default_tts_voice(),
             active_mode: default_active_mode(),
             sounds_enabled: default_sounds_enabled(),
             paste_restore_delay_ms: default_paste_restore_delay_ms(),
             launch_at_login: default_launch_at_login(),
             onboarding_completed: default_onboarding_completed(),
+            clipboard_provider: default_clipboard_provider(),
+            clipboard_custom_copy: default_clipboard_custom_copy(),
+            clipboard_custom_paste: default_clipboard_custom_paste(),
+            modes: default_modes(),
         }
     }
 }
 
 impl VaaniConfig {
+    /// Merges the built-in default modes into `self.modes`, without
+    /// overwriting any entry the user has already defined (including a
+    /// user override of a built-in name).
+    ///
+    /// Called after loading so `modes:` always contains all five built-ins
+    /// even when the config file's `modes:` map only lists custom ones.
+    pub fn merge_default_modes(&mut self) {
+        for (name, mode) in default_modes() {
+            self.modes.entry(name).or_insert(mode);
+        }
+    }
+
+    /// Returns the user-configured prompt for `mode`, if `mode` is a
+    /// user-defined entry in [`modes`](Self::modes) rather than one of the
+    /// built-in [`MODES`].
+    ///
+    /// Built-in modes intentionally return `None` here — their prompts
+    /// keep coming from `prompts::build_system_prompt`'s bundled/override
+    /// files, unaffected by this map.
+    pub fn custom_mode_prompt(&self, mode: &str) -> Option<&str> {
+        if MODES.contains(&mode) {
+            return None;
+        }
+        self.modes.get(mode).map(|m| m.prompt.as_str())
+    }
+
     /// Validate configuration values.
     ///
     /// Returns `Ok(())` when all values are within acceptable ranges, or
     /// `Err(VaaniError::Config(...))` describing the first violation found.
     pub fn validate(&self) -> Result<(), VaaniError> {
-        if !MODES.contains(&self.active_mode.as_str()) {
+        if !MODES.contains(&self.active_mode.as_str()) && !self.modes.contains_key(&self.active_mode)
+        {
             return Err(VaaniError::Config(format!(
                 "Unknown mode '{}'. Valid modes: {}",
                 self.active_mode,
@@ -143,12 +414,75 @@ impl VaaniConfig {
             )));
         }
 
+        if !(0.1..=5.0).contains(&self.mic_sensitivity) {
+            return Err(VaaniError::Config(format!(
+                "mic_sensitivity must be between 0.1 and 5.0, got {}",
+                self.mic_sensitivity
+            )));
+        }
+
+        if !(0.0..=1.0).contains(&self.noise_gate_threshold) {
+            return Err(VaaniError::Config(format!(
+                "noise_gate_threshold must be between 0.0 and 1.0, got {}",
+                self.noise_gate_threshold
+            )));
+        }
+
+        if !(100..=5_000).contains(&self.tray_blink_interval_ms) {
+            return Err(VaaniError::Config(format!(
+                "tray_blink_interval_ms must be between 100 and 5000, got {}",
+                self.tray_blink_interval_ms
+            )));
+        }
+
         if self.sample_rate == 0 {
             return Err(VaaniError::Config(
                 "sample_rate must be greater than 0".to_string(),
             ));
         }
 
+        if !STT_PROVIDERS.contains(&self.stt_provider.as_str()) {
+            return Err(VaaniError::Config(format!(
+                "Unknown stt_provider '{}'. Valid values: {}",
+                self.stt_provider,
+                STT_PROVIDERS.join(", ")
+            )));
+        }
+
+        if !STT_TASKS.contains(&self.stt_task.as_str()) {
+            return Err(VaaniError::Config(format!(
+                "Unknown stt_task '{}'. Valid values: {}",
+                self.stt_task,
+                STT_TASKS.join(", ")
+            )));
+        }
+
+        if !LLM_PROVIDERS.contains(&self.llm_provider.as_str()) {
+            return Err(VaaniError::Config(format!(
+                "Unknown llm_provider '{}'. Valid values: {}",
+                self.llm_provider,
+                LLM_PROVIDERS.join(", ")
+            )));
+        }
+
+        if !CLIPBOARD_PROVIDERS.contains(&self.clipboard_provider.as_str()) {
+            return Err(VaaniError::Config(format!(
+                "Unknown clipboard_provider '{}'. Valid values: {}",
+                self.clipboard_provider,
+                CLIPBOARD_PROVIDERS.join(", ")
+            )));
+        }
+
+        if self.clipboard_provider == "custom"
+            && (self.clipboard_custom_copy.is_none() || self.clipboard_custom_paste.is_none())
+        {
+            return Err(VaaniError::Config(
+                "clipboard_provider is 'custom' but clipboard_custom_copy and \
+                 clipboard_custom_paste must both be set"
+                    .to_string(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -194,19 +528,200 @@ fn migrate_mode(config: &mut VaaniConfig) {
     }
 }
 
-/// Load configuration from `~/.vaani/config.yaml`.
+/// Where a [`ConfigLayer`]'s values came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// A `VaaniConfig::default()` value — no layer set this field.
+    Default,
+    /// The top-level config file (`~/.vaani/config.yaml`, or an explicit
+    /// `--config` path).
+    File(PathBuf),
+    /// A file pulled in via another file's `import:` key.
+    Import(PathBuf),
+    /// A `VAANI_`-prefixed environment variable.
+    Env(String),
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::File(path) => write!(f, "file: {}", path.display()),
+            ConfigSource::Import(path) => write!(f, "import: {}", path.display()),
+            ConfigSource::Env(var) => write!(f, "env: {var}"),
+        }
+    }
+}
+
+/// One file's contribution to the merged config: where it came from, and
+/// the partial `serde_yaml::Value` mapping it parsed to (before imports or
+/// the importing file are merged on top).
+#[derive(Debug, Clone)]
+pub struct ConfigLayer {
+    pub source: ConfigSource,
+    pub value: serde_yaml::Value,
+}
+
+/// Reads `path` as a `serde_yaml::Value` and resolves its `import:` list
+/// (if any), merging imported files underneath it, while appending a
+/// [`ConfigLayer`] to `out` for every file actually read — deepest import
+/// first, the file at `path` itself last — i.e. in the same precedence
+/// order the merge applies them, so scanning `out` in order and keeping
+/// the last writer per key tells you each field's origin.
+///
+/// Imports are resolved depth-first: each imported path is fully resolved
+/// (including its own imports) before being merged, so the deepest file in
+/// a chain is merged first and the importing file always wins ties. Merges
+/// are shallow — only top-level mapping keys are overridden, so e.g. a
+/// `modes:` map in one file fully replaces (rather than combines with) a
+/// `modes:` map in another.
+///
+/// Per-file failures (missing file, unreadable file, unparseable YAML) are
+/// logged and treated as an empty contribution rather than aborting the
+/// whole chain, mirroring `load_config`'s existing "fall back" behavior.
+///
+/// `depth` guards against import cycles: once it exceeds
+/// `IMPORT_RECURSION_LIMIT`, resolution stops and a warning is logged
+/// instead of recursing forever.
+fn load_layers_with_imports(path: &Path, depth: u32, out: &mut Vec<ConfigLayer>) -> serde_yaml::Value {
+    if depth > IMPORT_RECURSION_LIMIT {
+        warn!(
+            "Import recursion limit ({IMPORT_RECURSION_LIMIT}) exceeded at {}. Stopping.",
+            path.display()
+        );
+        return serde_yaml::Value::Null;
+    }
+
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                info!("No config file at {}. Skipping.", path.display());
+            } else {
+                warn!("Failed to read config at {}: {}. Skipping.", path.display(), e);
+            }
+            return serde_yaml::Value::Null;
+        }
+    };
+
+    let value: serde_yaml::Value = match serde_yaml::from_str(&contents) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!("Failed to parse config at {}: {}. Skipping.", path.display(), e);
+            return serde_yaml::Value::Null;
+        }
+    };
+
+    let imports: Vec<String> = value
+        .as_mapping()
+        .and_then(|map| map.get("import"))
+        .and_then(|v| v.as_sequence())
+        .map(|seq| seq.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut merged = serde_yaml::Value::Null;
+    for import in imports {
+        let import_path = dir.join(&import);
+        merged = shallow_merge(merged, load_layers_with_imports(&import_path, depth + 1, out));
+    }
+
+    let source = if depth == 0 {
+        ConfigSource::File(path.to_path_buf())
+    } else {
+        ConfigSource::Import(path.to_path_buf())
+    };
+    out.push(ConfigLayer {
+        source,
+        value: value.clone(),
+    });
+
+    shallow_merge(merged, value)
+}
+
+/// Overlays `overlay` onto `base`, one mapping level deep.
 ///
-/// Falls back to `VaaniConfig::default()` when:
-/// - the file does not exist,
-/// - the file cannot be read, or
-/// - the YAML is unparseable.
+/// When both sides are mappings, each key in `overlay` replaces the same
+/// key in `base` (the value itself is not merged further). Otherwise
+/// `overlay` wins outright unless it is `Null`, in which case `base` is
+/// kept — this is what lets a missing/unparseable import contribute
+/// nothing instead of wiping out earlier merges.
+fn shallow_merge(base: serde_yaml::Value, overlay: serde_yaml::Value) -> serde_yaml::Value {
+    match (base, overlay) {
+        (serde_yaml::Value::Mapping(mut base_map), serde_yaml::Value::Mapping(overlay_map)) => {
+            for (key, value) in overlay_map {
+                base_map.insert(key, value);
+            }
+            serde_yaml::Value::Mapping(base_map)
+        }
+        (_, overlay) if !overlay.is_null() => overlay,
+        (base, _) => base,
+    }
+}
+
+/// Load configuration from `~/.vaani/config.yaml`.
 ///
-/// After loading, any legacy mode names are silently migrated.
+/// Equivalent to `load_config_from(None)`. See [`load_config_from`] for the
+/// full resolution order and merge behavior.
 pub fn load_config() -> VaaniConfig {
-    let path = config_path();
+    load_config_from(None)
+}
+
+/// Load configuration, optionally from an explicit path (e.g. a CLI
+/// `--config` flag) instead of the default `~/.vaani/config.yaml`.
+///
+/// Config sources are resolved in this order, highest priority last:
+/// 1. Built-in defaults ([`VaaniConfig::default`]).
+/// 2. `~/.vaani/config.yaml` — or, when `path` is `Some`, that path
+///    strictly instead (no fallback to the default location).
+/// 3. Any files pulled in via that file's `import:` chain (see
+///    [`load_layers_with_imports`]), with the importing file winning ties.
+/// 4. `VAANI_`-prefixed environment variables (see
+///    [`apply_env_overrides_with_origins`]), which override everything above.
+///
+/// Falls back to `VaaniConfig::default()` when the resolved file does not
+/// exist, cannot be read, or is unparseable — logging which source was
+/// used either way. After loading, legacy mode names are migrated and the
+/// built-in [`MODES`] are merged into `modes:` if absent.
+pub fn load_config_from(path: Option<PathBuf>) -> VaaniConfig {
+    load_config_with_origins(path).0
+}
+
+/// Like [`load_config_from`], but also returns a map from each top-level
+/// `VaaniConfig` field name to the [`ConfigSource`] that set its effective
+/// value — `default` when no layer touched it at all.
+///
+/// This is the introspection Mercurial-style layered configs provide: once
+/// imports and env overrides can each set the same field, "why is this
+/// value what it is?" stops being answerable just by reading one file.
+/// [`config_doctor_report`] renders this map for humans; callers that want
+/// the raw data (e.g. a future Settings "why?" tooltip) can use this
+/// directly.
+pub fn load_config_with_origins(path: Option<PathBuf>) -> (VaaniConfig, HashMap<String, ConfigSource>) {
+    let path = path.unwrap_or_else(config_path);
+
+    let mut layers = Vec::new();
+    let merged = load_layers_with_imports(&path, 0, &mut layers);
+
+    let mut origins: HashMap<String, ConfigSource> = HashMap::new();
+    for layer in &layers {
+        let Some(map) = layer.value.as_mapping() else {
+            continue;
+        };
+        for key in map.keys() {
+            let Some(key) = key.as_str() else { continue };
+            if key == "import" {
+                continue;
+            }
+            origins.insert(key.to_string(), layer.source.clone());
+        }
+    }
 
-    let mut config = match std::fs::read_to_string(&path) {
-        Ok(contents) => match serde_yaml::from_str::<VaaniConfig>(&contents) {
+    let mut config = if merged.is_null() {
+        origins.clear();
+        VaaniConfig::default()
+    } else {
+        match serde_yaml::from_value::<VaaniConfig>(merged) {
             Ok(cfg) => {
                 info!("Loaded config from {}", path.display());
                 cfg
@@ -217,26 +732,281 @@ pub fn load_config() -> VaaniConfig {
                     path.display(),
                     e
                 );
+                // The merged value didn't deserialize, so none of the
+                // per-field origins gathered above describe the config we
+                // actually end up with.
+                origins.clear();
                 VaaniConfig::default()
             }
-        },
-        Err(e) => {
-            // `NotFound` is normal on first launch — log at info, not warn.
-            if e.kind() == std::io::ErrorKind::NotFound {
-                info!("No config file at {}. Using defaults.", path.display());
-            } else {
-                warn!(
-                    "Failed to read config at {}: {}. Using defaults.",
-                    path.display(),
-                    e
-                );
-            }
-            VaaniConfig::default()
         }
     };
 
     migrate_mode(&mut config);
-    config
+    config.merge_default_modes();
+    apply_env_overrides_with_origins(&mut config, &mut origins);
+
+    (config, origins)
+}
+
+/// Renders a human-readable "`vaani config doctor`" report: the fully
+/// resolved `VaaniConfig`, one field per line, each annotated with where
+/// its value came from. Fields set by anything other than a built-in
+/// default are flagged with a leading `*` so overrides stand out, and
+/// validation failures in the effective config are called out at the end.
+pub fn config_doctor_report(path: Option<PathBuf>) -> String {
+    let (config, origins) = load_config_with_origins(path);
+
+    let mut lines = vec!["Vaani configuration (effective value, then origin):".to_string()];
+
+    let config_value = serde_yaml::to_value(&config).expect("VaaniConfig always serializes");
+    if let Some(map) = config_value.as_mapping() {
+        for (key, value) in map {
+            let Some(field) = key.as_str() else { continue };
+            let origin = origins.get(field).cloned().unwrap_or(ConfigSource::Default);
+            let rendered = serde_yaml::to_string(value).unwrap_or_default();
+            let rendered = rendered.trim_end();
+            let marker = if origin == ConfigSource::Default { " " } else { "*" };
+            lines.push(format!("{marker} {field:<24} = {rendered}  [{origin}]"));
+        }
+    }
+
+    match config.validate() {
+        Ok(()) => {}
+        Err(e) => lines.push(format!("\nWARNING: effective config fails validation: {e}")),
+    }
+
+    lines.join("\n")
+}
+
+// ── Environment overrides ─────────────────────────────────────────────────
+
+/// Reads `key` from the environment and, if present, parses it as `T` into
+/// `*slot`, recording `field`'s origin as [`ConfigSource::Env`] in `origins`.
+/// A present-but-unparseable value is logged via `warn!` and the slot is
+/// left untouched rather than aborting the load — consistent with every
+/// other "bad input falls back" path in this module.
+fn env_override_tracked<T: std::str::FromStr>(
+    key: &str,
+    field: &str,
+    slot: &mut T,
+    origins: &mut HashMap<String, ConfigSource>,
+) where
+    T::Err: std::fmt::Display,
+{
+    let Ok(raw) = std::env::var(key) else {
+        return;
+    };
+    match raw.parse::<T>() {
+        Ok(value) => {
+            *slot = value;
+            if !field.is_empty() {
+                origins.insert(field.to_string(), ConfigSource::Env(key.to_string()));
+            }
+        }
+        Err(e) => warn!("Invalid value for {key}={raw:?}: {e}. Ignoring override."),
+    }
+}
+
+/// Like [`env_override_tracked`] but for `Option<u32>` fields
+/// (`microphone_device`), which have no direct `FromStr` target.
+fn env_override_opt_u32_tracked(
+    key: &str,
+    field: &str,
+    slot: &mut Option<u32>,
+    origins: &mut HashMap<String, ConfigSource>,
+) {
+    let Ok(raw) = std::env::var(key) else {
+        return;
+    };
+    match raw.parse::<u32>() {
+        Ok(value) => {
+            *slot = Some(value);
+            if !field.is_empty() {
+                origins.insert(field.to_string(), ConfigSource::Env(key.to_string()));
+            }
+        }
+        Err(e) => warn!("Invalid value for {key}={raw:?}: {e}. Ignoring override."),
+    }
+}
+
+/// `Option<String>` fields are set directly when present; empty-string env
+/// values resolve to `None` rather than `Some("")`.
+fn env_override_opt_string_tracked(
+    key: &str,
+    field: &str,
+    slot: &mut Option<String>,
+    origins: &mut HashMap<String, ConfigSource>,
+) {
+    if let Ok(value) = std::env::var(key) {
+        *slot = if value.is_empty() { None } else { Some(value) };
+        if !field.is_empty() {
+            origins.insert(field.to_string(), ConfigSource::Env(key.to_string()));
+        }
+    }
+}
+
+/// String fields are set directly (no parsing can fail), but still need
+/// their origin recorded when `field` is non-empty.
+fn env_override_string_tracked(
+    key: &str,
+    field: &str,
+    slot: &mut String,
+    origins: &mut HashMap<String, ConfigSource>,
+) {
+    if let Ok(value) = std::env::var(key) {
+        *slot = value;
+        if !field.is_empty() {
+            origins.insert(field.to_string(), ConfigSource::Env(key.to_string()));
+        }
+    }
+}
+
+/// Applies `VAANI_`-prefixed environment variable overrides on top of an
+/// already-loaded config, mirroring how Rocket resolves `ROCKET_{PARAM}`
+/// on top of its config file. Env vars are the highest-priority source:
+/// they override both the YAML file and the built-in defaults.
+///
+/// | Variable                        | Field                     |
+/// |----------------------------------|---------------------------|
+/// | `VAANI_HOTKEY`                  | `hotkey`                  |
+/// | `VAANI_SAMPLE_RATE`              | `sample_rate`              |
+/// | `VAANI_VAD_THRESHOLD`           | `vad_threshold`            |
+/// | `VAANI_MIC_SENSITIVITY`         | `mic_sensitivity`          |
+/// | `VAANI_NOISE_GATE_THRESHOLD`    | `noise_gate_threshold`     |
+/// | `VAANI_TRAY_BLINK_INTERVAL_MS`  | `tray_blink_interval_ms`   |
+/// | `VAANI_MAX_RECORDING_SECONDS`   | `max_recording_seconds`    |
+/// | `VAANI_MICROPHONE_DEVICE`       | `microphone_device`        |
+/// | `VAANI_STT_MODEL`               | `stt_model`                |
+/// | `VAANI_STT_PROVIDER`            | `stt_provider`             |
+/// | `VAANI_STT_TASK`                | `stt_task`                 |
+/// | `VAANI_LLM_MODEL`               | `llm_model`                |
+/// | `VAANI_LLM_PROVIDER`            | `llm_provider`             |
+/// | `VAANI_LLM_BASE_URL`            | `llm_base_url`             |
+/// | `VAANI_SPEAK_RESULT`            | `speak_result`             |
+/// | `VAANI_TTS_MODEL`               | `tts_model`                |
+/// | `VAANI_TTS_VOICE`               | `tts_voice`                |
+/// | `VAANI_ACTIVE_MODE`             | `active_mode`              |
+/// | `VAANI_SOUNDS_ENABLED`          | `sounds_enabled`           |
+/// | `VAANI_PASTE_RESTORE_DELAY_MS`  | `paste_restore_delay_ms`   |
+/// | `VAANI_LAUNCH_AT_LOGIN`         | `launch_at_login`          |
+/// | `VAANI_ONBOARDING_COMPLETED`    | `onboarding_completed`     |
+/// | `VAANI_CLIPBOARD_PROVIDER`      | `clipboard_provider`       |
+///
+/// Records each applied override's field name against [`ConfigSource::Env`]
+/// in `origins` — the origin map used by [`load_config_with_origins`] and
+/// `vaani config doctor`.
+fn apply_env_overrides_with_origins(
+    config: &mut VaaniConfig,
+    origins: &mut HashMap<String, ConfigSource>,
+) {
+    env_override_string_tracked("VAANI_HOTKEY", "hotkey", &mut config.hotkey, origins);
+    env_override_tracked(
+        "VAANI_SAMPLE_RATE",
+        "sample_rate",
+        &mut config.sample_rate,
+        origins,
+    );
+    env_override_tracked(
+        "VAANI_VAD_THRESHOLD",
+        "vad_threshold",
+        &mut config.vad_threshold,
+        origins,
+    );
+    env_override_tracked(
+        "VAANI_MIC_SENSITIVITY",
+        "mic_sensitivity",
+        &mut config.mic_sensitivity,
+        origins,
+    );
+    env_override_tracked(
+        "VAANI_NOISE_GATE_THRESHOLD",
+        "noise_gate_threshold",
+        &mut config.noise_gate_threshold,
+        origins,
+    );
+    env_override_tracked(
+        "VAANI_TRAY_BLINK_INTERVAL_MS",
+        "tray_blink_interval_ms",
+        &mut config.tray_blink_interval_ms,
+        origins,
+    );
+    env_override_tracked(
+        "VAANI_MAX_RECORDING_SECONDS",
+        "max_recording_seconds",
+        &mut config.max_recording_seconds,
+        origins,
+    );
+    env_override_opt_u32_tracked(
+        "VAANI_MICROPHONE_DEVICE",
+        "microphone_device",
+        &mut config.microphone_device,
+        origins,
+    );
+    env_override_string_tracked("VAANI_STT_MODEL", "stt_model", &mut config.stt_model, origins);
+    env_override_string_tracked(
+        "VAANI_STT_PROVIDER",
+        "stt_provider",
+        &mut config.stt_provider,
+        origins,
+    );
+    env_override_string_tracked("VAANI_STT_TASK", "stt_task", &mut config.stt_task, origins);
+    env_override_string_tracked("VAANI_LLM_MODEL", "llm_model", &mut config.llm_model, origins);
+    env_override_string_tracked(
+        "VAANI_LLM_PROVIDER",
+        "llm_provider",
+        &mut config.llm_provider,
+        origins,
+    );
+    env_override_opt_string_tracked(
+        "VAANI_LLM_BASE_URL",
+        "llm_base_url",
+        &mut config.llm_base_url,
+        origins,
+    );
+    env_override_tracked(
+        "VAANI_SPEAK_RESULT",
+        "speak_result",
+        &mut config.speak_result,
+        origins,
+    );
+    env_override_string_tracked("VAANI_TTS_MODEL", "tts_model", &mut config.tts_model, origins);
+    env_override_string_tracked("VAANI_TTS_VOICE", "tts_voice", &mut config.tts_voice, origins);
+    env_override_string_tracked(
+        "VAANI_ACTIVE_MODE",
+        "active_mode",
+        &mut config.active_mode,
+        origins,
+    );
+    env_override_tracked(
+        "VAANI_SOUNDS_ENABLED",
+        "sounds_enabled",
+        &mut config.sounds_enabled,
+        origins,
+    );
+    env_override_tracked(
+        "VAANI_PASTE_RESTORE_DELAY_MS",
+        "paste_restore_delay_ms",
+        &mut config.paste_restore_delay_ms,
+        origins,
+    );
+    env_override_tracked(
+        "VAANI_LAUNCH_AT_LOGIN",
+        "launch_at_login",
+        &mut config.launch_at_login,
+        origins,
+    );
+    env_override_tracked(
+        "VAANI_ONBOARDING_COMPLETED",
+        "onboarding_completed",
+        &mut config.onboarding_completed,
+        origins,
+    );
+    env_override_string_tracked(
+        "VAANI_CLIPBOARD_PROVIDER",
+        "clipboard_provider",
+        &mut config.clipboard_provider,
+        origins,
+    );
 }
 
 /// Persist configuration to `~/.vaani/config.yaml`.
@@ -254,6 +1024,136 @@ pub fn save_config(config: &VaaniConfig) -> Result<(), VaaniError> {
     Ok(())
 }
 
+// ── Live reload ──────────────────────────────────────────────────────────────
+
+/// Collects every YAML file that loading `path` would read, by walking its
+/// `import:` chain with the same bounded recursion as
+/// [`load_layers_with_imports`]. Used by [`watch_config`] to find every
+/// directory that needs a filesystem watch.
+fn import_file_paths(path: &Path, depth: u32, out: &mut Vec<PathBuf>) {
+    if depth > IMPORT_RECURSION_LIMIT {
+        return;
+    }
+
+    out.push(path.to_path_buf());
+
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return;
+    };
+    let Ok(value) = serde_yaml::from_str::<serde_yaml::Value>(&contents) else {
+        return;
+    };
+
+    let imports: Vec<String> = value
+        .as_mapping()
+        .and_then(|map| map.get("import"))
+        .and_then(|v| v.as_sequence())
+        .map(|seq| seq.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    for import in imports {
+        import_file_paths(&dir.join(import), depth + 1, out);
+    }
+}
+
+/// Handle returned by [`watch_config`].
+///
+/// Keeps the underlying filesystem watcher alive; drop it (or call
+/// [`stop`](Self::stop)) to stop watching and end the background thread.
+pub struct ConfigWatcherHandle {
+    stop: Arc<AtomicBool>,
+    _watcher: RecommendedWatcher,
+}
+
+impl ConfigWatcherHandle {
+    /// Stops the background watcher. Idempotent.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+impl Drop for ConfigWatcherHandle {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Spawns a background filesystem watcher over `config_path()` and every
+/// file reachable through its `import:` chain, so the app can retune
+/// `vad_threshold`, `active_mode`, `hotkey`, and friends without a restart.
+///
+/// Changes are debounced (see [`RELOAD_DEBOUNCE`]) so a burst of editor
+/// writes triggers a single reload. Each reload is re-validated with
+/// [`VaaniConfig::validate`]; a config that fails validation is logged via
+/// `warn!` and discarded rather than sent, so the caller's previously-held
+/// good config keeps running.
+///
+/// Returns a [`Receiver`] that yields each freshly validated `VaaniConfig`,
+/// and a [`ConfigWatcherHandle`] that stops the watcher when dropped.
+pub fn watch_config() -> Result<(Receiver<VaaniConfig>, ConfigWatcherHandle), VaaniError> {
+    let path = config_path();
+
+    let mut files = Vec::new();
+    import_file_paths(&path, 0, &mut files);
+
+    let mut dirs: Vec<PathBuf> = files.iter().filter_map(|f| f.parent().map(Path::to_path_buf)).collect();
+    dirs.sort();
+    dirs.dedup();
+    if dirs.is_empty() {
+        dirs.push(config_dir());
+    }
+
+    let (raw_tx, raw_rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(raw_tx)
+        .map_err(|e| VaaniError::Config(format!("Failed to start config watcher: {e}")))?;
+
+    for dir in &dirs {
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| VaaniError::Config(format!("Failed to watch {}: {e}", dir.display())))?;
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_clone = Arc::clone(&stop);
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::Builder::new()
+        .name("vaani-config-watcher".into())
+        .spawn(move || {
+            while !stop_clone.load(Ordering::SeqCst) {
+                match raw_rx.recv_timeout(Duration::from_millis(250)) {
+                    Ok(Ok(_event)) => {
+                        // Drain anything else arriving within the debounce
+                        // window so a burst of writes collapses into one
+                        // reload.
+                        while raw_rx.recv_timeout(RELOAD_DEBOUNCE).is_ok() {}
+
+                        let config = load_config();
+                        match config.validate() {
+                            Ok(()) => {
+                                if tx.send(config).is_err() {
+                                    break;
+                                }
+                            }
+                            Err(e) => {
+                                warn!(
+                                    "Reloaded config failed validation: {e}. Keeping previous config."
+                                );
+                            }
+                        }
+                    }
+                    Ok(Err(e)) => warn!("Config watcher error: {e}"),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        })
+        .map_err(|e| VaaniError::Config(format!("Failed to spawn config watcher thread: {e}")))?;
+
+    Ok((rx, ConfigWatcherHandle { stop, _watcher: watcher }))
+}
+
 // ── Tests ──────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -280,9 +1180,13 @@ mod tests {
         assert_eq!(cfg.hotkey, "alt");
         assert_eq!(cfg.sample_rate, 16_000);
         assert!((cfg.vad_threshold - 0.05).abs() < f32::EPSILON);
+        assert!((cfg.mic_sensitivity - 1.0).abs() < f32::EPSILON);
+        assert_eq!(cfg.noise_gate_threshold, 0.0);
+        assert_eq!(cfg.tray_blink_interval_ms, 750);
         assert_eq!(cfg.max_recording_seconds, 600);
         assert_eq!(cfg.microphone_device, None);
         assert_eq!(cfg.stt_model, "whisper-1");
+        assert_eq!(cfg.stt_provider, "openai");
         assert_eq!(cfg.llm_model, "claude-haiku-4-5-20251001");
         assert_eq!(cfg.active_mode, "professional");
         assert!(cfg.sounds_enabled);
@@ -298,15 +1202,31 @@ mod tests {
             hotkey: "ctrl".to_string(),
             sample_rate: 44_100,
             vad_threshold: 0.1,
+            mic_sensitivity: 1.5,
+            noise_gate_threshold: 0.02,
+            tray_blink_interval_ms: 400,
             max_recording_seconds: 300,
             microphone_device: Some(2),
+            noise_suppression_enabled: true,
+            trim_silence_enabled: true,
             stt_model: "whisper-1".to_string(),
+            stt_provider: "deepgram".to_string(),
+            stt_task: default_stt_task(),
             llm_model: "claude-haiku-4-5-20251001".to_string(),
+            llm_provider: default_llm_provider(),
+            llm_base_url: default_llm_base_url(),
+            speak_result: default_speak_result(),
+            tts_model: default_tts_model(),
+            tts_voice: default_tts_voice(),
             active_mode: "casual".to_string(),
             sounds_enabled: false,
             paste_restore_delay_ms: 200,
             launch_at_login: true,
             onboarding_completed: true,
+            clipboard_provider: default_clipboard_provider(),
+            clipboard_custom_copy: default_clipboard_custom_copy(),
+            clipboard_custom_paste: default_clipboard_custom_paste(),
+            modes: default_modes(),
         };
 
         let restored = round_trip(&original);
@@ -323,6 +1243,9 @@ mod tests {
         // Everything else should be default
         assert_eq!(cfg.sample_rate, 16_000);
         assert!((cfg.vad_threshold - 0.05).abs() < f32::EPSILON);
+        assert!((cfg.mic_sensitivity - 1.0).abs() < f32::EPSILON);
+        assert_eq!(cfg.noise_gate_threshold, 0.0);
+        assert_eq!(cfg.tray_blink_interval_ms, 750);
         assert_eq!(cfg.max_recording_seconds, 600);
         assert_eq!(cfg.microphone_device, None);
         assert!(cfg.sounds_enabled);
@@ -427,46 +1350,248 @@ mod tests {
         assert!(msg.contains("Valid modes"), "error should list valid modes");
     }
 
-    // 9. Validate: vad_threshold out of range fails
+    // ── User-defined modes ──────────────────────────────────────────────────
+
     #[test]
-    fn validate_vad_threshold_too_low() {
+    fn validate_accepts_custom_mode_in_modes_map() {
+        let mut modes = HashMap::new();
+        modes.insert(
+            "pirate".to_string(),
+            ModeConfig {
+                prompt: "Rewrite as if spoken by a pirate.".to_string(),
+                icon: None,
+                description: None,
+            },
+        );
         let cfg = VaaniConfig {
-            vad_threshold: 0.001,
+            active_mode: "pirate".to_string(),
+            modes,
             ..Default::default()
         };
-        let err = cfg.validate().unwrap_err();
-        assert!(err.to_string().contains("vad_threshold"));
+        assert!(cfg.validate().is_ok());
     }
 
     #[test]
-    fn validate_vad_threshold_too_high() {
+    fn validate_rejects_mode_absent_from_both_builtins_and_map() {
         let cfg = VaaniConfig {
-            vad_threshold: 0.9,
+            active_mode: "pirate".to_string(),
             ..Default::default()
         };
-        let err = cfg.validate().unwrap_err();
-        assert!(err.to_string().contains("vad_threshold"));
+        assert!(cfg.validate().is_err());
     }
 
     #[test]
-    fn validate_vad_threshold_boundaries() {
-        // Lower bound (0.01) should pass
-        let cfg_low = VaaniConfig {
-            vad_threshold: 0.01,
+    fn merge_default_modes_adds_builtins_without_overwriting_custom() {
+        let mut modes = HashMap::new();
+        modes.insert(
+            "pirate".to_string(),
+            ModeConfig {
+                prompt: "Rewrite as if spoken by a pirate.".to_string(),
+                icon: None,
+                description: None,
+            },
+        );
+        let mut cfg = VaaniConfig {
+            modes,
             ..Default::default()
         };
-        assert!(cfg_low.validate().is_ok());
+        cfg.merge_default_modes();
 
-        // Upper bound (0.5) should pass
-        let cfg_high = VaaniConfig {
-            vad_threshold: 0.5,
-            ..Default::default()
-        };
-        assert!(cfg_high.validate().is_ok());
+        assert!(cfg.modes.contains_key("pirate"));
+        for &mode in MODES {
+            assert!(cfg.modes.contains_key(mode), "missing builtin '{mode}'");
+        }
     }
 
     #[test]
-    fn validate_sample_rate_zero_fails() {
+    fn merge_default_modes_does_not_overwrite_user_override_of_a_builtin() {
+        let mut modes = HashMap::new();
+        modes.insert(
+            "professional".to_string(),
+            ModeConfig {
+                prompt: "My custom professional prompt.".to_string(),
+                icon: None,
+                description: None,
+            },
+        );
+        let mut cfg = VaaniConfig {
+            modes,
+            ..Default::default()
+        };
+        cfg.merge_default_modes();
+
+        assert_eq!(
+            cfg.modes["professional"].prompt,
+            "My custom professional prompt."
+        );
+    }
+
+    #[test]
+    fn custom_mode_prompt_returns_none_for_builtins() {
+        let cfg = VaaniConfig::default();
+        assert_eq!(cfg.custom_mode_prompt("professional"), None);
+    }
+
+    #[test]
+    fn custom_mode_prompt_returns_configured_prompt_for_custom_mode() {
+        let mut modes = HashMap::new();
+        modes.insert(
+            "pirate".to_string(),
+            ModeConfig {
+                prompt: "Rewrite as if spoken by a pirate.".to_string(),
+                icon: None,
+                description: None,
+            },
+        );
+        let cfg = VaaniConfig {
+            modes,
+            ..Default::default()
+        };
+        assert_eq!(
+            cfg.custom_mode_prompt("pirate"),
+            Some("Rewrite as if spoken by a pirate.")
+        );
+    }
+
+    // 9. Validate: vad_threshold out of range fails
+    #[test]
+    fn validate_vad_threshold_too_low() {
+        let cfg = VaaniConfig {
+            vad_threshold: 0.001,
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("vad_threshold"));
+    }
+
+    #[test]
+    fn validate_vad_threshold_too_high() {
+        let cfg = VaaniConfig {
+            vad_threshold: 0.9,
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("vad_threshold"));
+    }
+
+    #[test]
+    fn validate_vad_threshold_boundaries() {
+        // Lower bound (0.01) should pass
+        let cfg_low = VaaniConfig {
+            vad_threshold: 0.01,
+            ..Default::default()
+        };
+        assert!(cfg_low.validate().is_ok());
+
+        // Upper bound (0.5) should pass
+        let cfg_high = VaaniConfig {
+            vad_threshold: 0.5,
+            ..Default::default()
+        };
+        assert!(cfg_high.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_mic_sensitivity_too_low() {
+        let cfg = VaaniConfig {
+            mic_sensitivity: 0.05,
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("mic_sensitivity"));
+    }
+
+    #[test]
+    fn validate_mic_sensitivity_too_high() {
+        let cfg = VaaniConfig {
+            mic_sensitivity: 5.1,
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("mic_sensitivity"));
+    }
+
+    #[test]
+    fn validate_mic_sensitivity_boundaries() {
+        let cfg_low = VaaniConfig {
+            mic_sensitivity: 0.1,
+            ..Default::default()
+        };
+        assert!(cfg_low.validate().is_ok());
+
+        let cfg_high = VaaniConfig {
+            mic_sensitivity: 5.0,
+            ..Default::default()
+        };
+        assert!(cfg_high.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_noise_gate_threshold_out_of_range() {
+        let cfg = VaaniConfig {
+            noise_gate_threshold: -0.1,
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("noise_gate_threshold"));
+
+        let cfg = VaaniConfig {
+            noise_gate_threshold: 1.1,
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("noise_gate_threshold"));
+    }
+
+    #[test]
+    fn validate_noise_gate_threshold_boundaries() {
+        let cfg_low = VaaniConfig {
+            noise_gate_threshold: 0.0,
+            ..Default::default()
+        };
+        assert!(cfg_low.validate().is_ok());
+
+        let cfg_high = VaaniConfig {
+            noise_gate_threshold: 1.0,
+            ..Default::default()
+        };
+        assert!(cfg_high.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_tray_blink_interval_ms_out_of_range() {
+        let cfg = VaaniConfig {
+            tray_blink_interval_ms: 50,
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("tray_blink_interval_ms"));
+
+        let cfg = VaaniConfig {
+            tray_blink_interval_ms: 5_001,
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("tray_blink_interval_ms"));
+    }
+
+    #[test]
+    fn validate_tray_blink_interval_ms_boundaries() {
+        let cfg_low = VaaniConfig {
+            tray_blink_interval_ms: 100,
+            ..Default::default()
+        };
+        assert!(cfg_low.validate().is_ok());
+
+        let cfg_high = VaaniConfig {
+            tray_blink_interval_ms: 5_000,
+            ..Default::default()
+        };
+        assert!(cfg_high.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_sample_rate_zero_fails() {
         let cfg = VaaniConfig {
             sample_rate: 0,
             ..Default::default()
@@ -475,6 +1600,110 @@ mod tests {
         assert!(err.to_string().contains("sample_rate"));
     }
 
+    // 9a2. Validate: stt_provider
+    #[test]
+    fn validate_unknown_stt_provider_fails() {
+        let cfg = VaaniConfig {
+            stt_provider: "carrier-pigeon".to_string(),
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("stt_provider"));
+    }
+
+    #[test]
+    fn validate_all_stt_providers_pass() {
+        for provider in crate::transcribe::STT_PROVIDERS {
+            let cfg = VaaniConfig {
+                stt_provider: provider.to_string(),
+                ..Default::default()
+            };
+            assert!(cfg.validate().is_ok(), "provider {provider} should be valid");
+        }
+    }
+
+    // 9a3. Validate: stt_task
+    #[test]
+    fn validate_unknown_stt_task_fails() {
+        let cfg = VaaniConfig {
+            stt_task: "summarize".to_string(),
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("stt_task"));
+    }
+
+    #[test]
+    fn validate_all_stt_tasks_pass() {
+        for task in crate::transcribe::STT_TASKS {
+            let cfg = VaaniConfig {
+                stt_task: task.to_string(),
+                ..Default::default()
+            };
+            assert!(cfg.validate().is_ok(), "task {task} should be valid");
+        }
+    }
+
+    // 9a4. Validate: llm_provider
+    #[test]
+    fn validate_unknown_llm_provider_fails() {
+        let cfg = VaaniConfig {
+            llm_provider: "carrier-pigeon".to_string(),
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("llm_provider"));
+    }
+
+    #[test]
+    fn validate_all_llm_providers_pass() {
+        for provider in crate::enhance::LLM_PROVIDERS {
+            let cfg = VaaniConfig {
+                llm_provider: provider.to_string(),
+                ..Default::default()
+            };
+            assert!(cfg.validate().is_ok(), "provider {provider} should be valid");
+        }
+    }
+
+    // 9b. Validate: clipboard_provider
+    #[test]
+    fn validate_unknown_clipboard_provider_fails() {
+        let cfg = VaaniConfig {
+            clipboard_provider: "not-a-real-provider".to_string(),
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("clipboard_provider"));
+    }
+
+    #[test]
+    fn validate_custom_clipboard_provider_requires_both_commands() {
+        let cfg = VaaniConfig {
+            clipboard_provider: "custom".to_string(),
+            ..Default::default()
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(err.to_string().contains("clipboard_custom_copy"));
+    }
+
+    #[test]
+    fn validate_custom_clipboard_provider_with_both_commands_passes() {
+        let cfg = VaaniConfig {
+            clipboard_provider: "custom".to_string(),
+            clipboard_custom_copy: Some(CustomClipboardCommand {
+                command: "cat".to_string(),
+                args: vec![],
+            }),
+            clipboard_custom_paste: Some(CustomClipboardCommand {
+                command: "cat".to_string(),
+                args: vec![],
+            }),
+            ..Default::default()
+        };
+        assert!(cfg.validate().is_ok());
+    }
+
     // 10. MODES contains exactly 5 entries with correct names
     #[test]
     fn modes_constant_is_correct() {
@@ -537,4 +1766,290 @@ mod tests {
 
         assert_eq!(cfg.active_mode, "minimal");
     }
+
+    // ── Import merging ──────────────────────────────────────────────────────
+
+    #[test]
+    fn import_merges_fields_from_imported_file() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let base_path = tmp.path().join("config.yaml");
+        let modes_path = tmp.path().join("modes.yaml");
+
+        std::fs::write(&modes_path, "hotkey: ctrl\nsample_rate: 44100\n").expect("write modes");
+        std::fs::write(&base_path, "import:\n  - modes.yaml\nactive_mode: casual\n")
+            .expect("write base");
+
+        let cfg = load_config_at(&base_path);
+        assert_eq!(cfg.hotkey, "ctrl");
+        assert_eq!(cfg.sample_rate, 44_100);
+        assert_eq!(cfg.active_mode, "casual");
+    }
+
+    #[test]
+    fn import_is_overridden_by_importing_file() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let base_path = tmp.path().join("config.yaml");
+        let local_path = tmp.path().join("local.yaml");
+
+        std::fs::write(&local_path, "hotkey: ctrl\n").expect("write local");
+        std::fs::write(&base_path, "import:\n  - local.yaml\nhotkey: meta\n").expect("write base");
+
+        let cfg = load_config_at(&base_path);
+        // The importing file's own value wins over the imported one.
+        assert_eq!(cfg.hotkey, "meta");
+    }
+
+    #[test]
+    fn nested_imports_resolve_relative_to_their_own_directory() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let sub_dir = tmp.path().join("sub");
+        std::fs::create_dir(&sub_dir).expect("create sub dir");
+
+        let base_path = tmp.path().join("config.yaml");
+        let mid_path = tmp.path().join("mid.yaml");
+        let deep_path = sub_dir.join("deep.yaml");
+
+        std::fs::write(&deep_path, "hotkey: ctrl\n").expect("write deep");
+        std::fs::write(&mid_path, "import:\n  - sub/deep.yaml\nsample_rate: 8000\n")
+            .expect("write mid");
+        std::fs::write(&base_path, "import:\n  - mid.yaml\n").expect("write base");
+
+        let cfg = load_config_at(&base_path);
+        assert_eq!(cfg.hotkey, "ctrl");
+        assert_eq!(cfg.sample_rate, 8_000);
+    }
+
+    #[test]
+    fn missing_import_falls_back_to_defaults_for_that_file_only() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let base_path = tmp.path().join("config.yaml");
+
+        std::fs::write(
+            &base_path,
+            "import:\n  - does-not-exist.yaml\nactive_mode: code\n",
+        )
+        .expect("write base");
+
+        let cfg = load_config_at(&base_path);
+        assert_eq!(cfg.active_mode, "code");
+        assert_eq!(cfg.hotkey, "alt");
+    }
+
+    #[test]
+    fn import_cycle_is_bounded_by_recursion_limit() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let a_path = tmp.path().join("a.yaml");
+        let b_path = tmp.path().join("b.yaml");
+
+        std::fs::write(&a_path, "import:\n  - b.yaml\nhotkey: ctrl\n").expect("write a");
+        std::fs::write(&b_path, "import:\n  - a.yaml\nhotkey: shift\n").expect("write b");
+
+        // Must terminate (not infinitely recurse) and still produce a usable config.
+        let cfg = load_config_at(&a_path);
+        assert_eq!(cfg.hotkey, "ctrl");
+    }
+
+    // ── Explicit --config path ──────────────────────────────────────────────
+
+    #[test]
+    fn load_config_from_explicit_path_reads_that_file() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let path = tmp.path().join("profile.yaml");
+        std::fs::write(&path, "hotkey: meta\nactive_mode: code\n").expect("write");
+
+        let cfg = load_config_from(Some(path));
+        assert_eq!(cfg.hotkey, "meta");
+        assert_eq!(cfg.active_mode, "code");
+    }
+
+    #[test]
+    fn load_config_from_missing_explicit_path_falls_back_to_defaults() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let path = tmp.path().join("does-not-exist.yaml");
+
+        let cfg = load_config_from(Some(path));
+        assert_eq!(cfg.hotkey, "alt");
+        assert_eq!(cfg.active_mode, "professional");
+    }
+
+    // ── Config origin tracking ──────────────────────────────────────────────
+
+    #[test]
+    fn origins_mark_unset_fields_as_default() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let path = tmp.path().join("config.yaml");
+        std::fs::write(&path, "hotkey: ctrl\n").expect("write");
+
+        let (cfg, origins) = load_config_with_origins(Some(path));
+        assert_eq!(cfg.sample_rate, 16_000);
+        assert!(
+            !origins.contains_key("sample_rate"),
+            "untouched fields should have no recorded origin (default)"
+        );
+    }
+
+    #[test]
+    fn origins_attribute_file_fields_to_the_file_layer() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let path = tmp.path().join("config.yaml");
+        std::fs::write(&path, "hotkey: ctrl\n").expect("write");
+
+        let (_, origins) = load_config_with_origins(Some(path.clone()));
+        assert_eq!(origins.get("hotkey"), Some(&ConfigSource::File(path)));
+    }
+
+    #[test]
+    fn origins_attribute_imported_fields_to_the_import_layer() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let base_path = tmp.path().join("config.yaml");
+        let modes_path = tmp.path().join("modes.yaml");
+
+        std::fs::write(&modes_path, "sample_rate: 44100\n").expect("write modes");
+        std::fs::write(&base_path, "import:\n  - modes.yaml\nhotkey: ctrl\n").expect("write base");
+
+        let (_, origins) = load_config_with_origins(Some(base_path.clone()));
+        assert_eq!(origins.get("hotkey"), Some(&ConfigSource::File(base_path)));
+        assert_eq!(
+            origins.get("sample_rate"),
+            Some(&ConfigSource::Import(modes_path))
+        );
+    }
+
+    #[test]
+    fn origins_attribute_env_fields_to_env_source() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let path = tmp.path().join("config.yaml");
+        std::fs::write(&path, "hotkey: ctrl\n").expect("write");
+
+        std::env::set_var("VAANI_HOTKEY", "meta");
+        let (cfg, origins) = load_config_with_origins(Some(path));
+        std::env::remove_var("VAANI_HOTKEY");
+
+        assert_eq!(cfg.hotkey, "meta");
+        assert_eq!(
+            origins.get("hotkey"),
+            Some(&ConfigSource::Env("VAANI_HOTKEY".to_string()))
+        );
+    }
+
+    #[test]
+    fn config_doctor_report_flags_overridden_fields() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let path = tmp.path().join("config.yaml");
+        std::fs::write(&path, "hotkey: ctrl\n").expect("write");
+
+        let report = config_doctor_report(Some(path));
+        assert!(report.contains("* hotkey"), "report was:\n{report}");
+        assert!(
+            report.contains("  sample_rate"),
+            "untouched field should not be flagged, report was:\n{report}"
+        );
+    }
+
+    // ── Environment overrides ───────────────────────────────────────────────
+
+    #[test]
+    fn env_override_applies_string_field() {
+        std::env::set_var("VAANI_HOTKEY", "meta");
+        let mut cfg = VaaniConfig::default();
+        apply_env_overrides_with_origins(&mut cfg, &mut HashMap::new());
+        std::env::remove_var("VAANI_HOTKEY");
+
+        assert_eq!(cfg.hotkey, "meta");
+    }
+
+    #[test]
+    fn env_override_applies_numeric_field() {
+        std::env::set_var("VAANI_VAD_THRESHOLD", "0.2");
+        let mut cfg = VaaniConfig::default();
+        apply_env_overrides_with_origins(&mut cfg, &mut HashMap::new());
+        std::env::remove_var("VAANI_VAD_THRESHOLD");
+
+        assert!((cfg.vad_threshold - 0.2).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn env_override_invalid_numeric_value_is_ignored() {
+        std::env::set_var("VAANI_SAMPLE_RATE", "not-a-number");
+        let mut cfg = VaaniConfig::default();
+        apply_env_overrides_with_origins(&mut cfg, &mut HashMap::new());
+        std::env::remove_var("VAANI_SAMPLE_RATE");
+
+        // Unparseable override leaves the prior value untouched.
+        assert_eq!(cfg.sample_rate, default_sample_rate());
+    }
+
+    #[test]
+    fn env_override_applies_option_u32_field() {
+        std::env::set_var("VAANI_MICROPHONE_DEVICE", "3");
+        let mut cfg = VaaniConfig::default();
+        apply_env_overrides_with_origins(&mut cfg, &mut HashMap::new());
+        std::env::remove_var("VAANI_MICROPHONE_DEVICE");
+
+        assert_eq!(cfg.microphone_device, Some(3));
+    }
+
+    #[test]
+    fn env_override_applies_bool_field() {
+        std::env::set_var("VAANI_SOUNDS_ENABLED", "false");
+        let mut cfg = VaaniConfig::default();
+        apply_env_overrides_with_origins(&mut cfg, &mut HashMap::new());
+        std::env::remove_var("VAANI_SOUNDS_ENABLED");
+
+        assert!(!cfg.sounds_enabled);
+    }
+
+    #[test]
+    fn env_override_absent_leaves_field_untouched() {
+        std::env::remove_var("VAANI_LLM_MODEL");
+        let mut cfg = VaaniConfig::default();
+        apply_env_overrides_with_origins(&mut cfg, &mut HashMap::new());
+
+        assert_eq!(cfg.llm_model, default_llm_model());
+    }
+
+    #[test]
+    fn import_file_paths_includes_root_and_imports() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let base_path = tmp.path().join("config.yaml");
+        let modes_path = tmp.path().join("modes.yaml");
+
+        std::fs::write(&modes_path, "hotkey: ctrl\n").expect("write modes");
+        std::fs::write(&base_path, "import:\n  - modes.yaml\n").expect("write base");
+
+        let mut files = Vec::new();
+        import_file_paths(&base_path, 0, &mut files);
+
+        assert_eq!(files, vec![base_path, modes_path]);
+    }
+
+    #[test]
+    fn import_file_paths_stops_at_recursion_limit() {
+        let tmp = TempDir::new().expect("create temp dir");
+        let a_path = tmp.path().join("a.yaml");
+        let b_path = tmp.path().join("b.yaml");
+
+        std::fs::write(&a_path, "import:\n  - b.yaml\n").expect("write a");
+        std::fs::write(&b_path, "import:\n  - a.yaml\n").expect("write b");
+
+        let mut files = Vec::new();
+        import_file_paths(&a_path, 0, &mut files);
+
+        // Bounded by IMPORT_RECURSION_LIMIT rather than looping forever.
+        assert!(files.len() as u32 <= IMPORT_RECURSION_LIMIT + 2);
+    }
+
+    /// Test helper mirroring `load_config` but against an arbitrary path,
+    /// so import resolution can be exercised without touching the real
+    /// `~/.vaani/config.yaml`.
+    fn load_config_at(path: &std::path::Path) -> VaaniConfig {
+        let merged = load_layers_with_imports(path, 0, &mut Vec::new());
+        let mut cfg = if merged.is_null() {
+            VaaniConfig::default()
+        } else {
+            serde_yaml::from_value(merged).unwrap_or_default()
+        };
+        migrate_mode(&mut cfg);
+        cfg
+    }
 }
@@ -0,0 +1,192 @@
+//! Shared AES-256-GCM encryption-at-rest machinery.
+//!
+//! [`EncryptionCipher`] and [`SecretString`] started out private to
+//! [`crate::storage`]; they're factored out here so [`crate::kv`] can
+//! encrypt its values under the exact same key and scheme, letting both
+//! stores share one [`EncryptionCipher`] (and, in practice, one SQLite
+//! connection) rather than deriving the key twice.
+
+use aes_gcm::aead::generic_array::GenericArray;
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroizing;
+
+use crate::error::VaaniError;
+
+/// Size of AES-256-GCM nonce in bytes.
+const NONCE_SIZE: usize = 12;
+
+/// A decrypted text value whose backing allocation is zeroed when dropped,
+/// so transcription contents don't linger in freed heap pages.
+///
+/// Serializes and deserializes as a plain string (for Tauri IPC /
+/// persistence elsewhere); only its `Debug` output is redacted.
+#[derive(Clone)]
+pub struct SecretString(Zeroizing<String>);
+
+impl SecretString {
+    pub(crate) fn new(value: String) -> Self {
+        Self(Zeroizing::new(value))
+    }
+
+    /// Borrow the secret text.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretString(\"[redacted]\")")
+    }
+}
+
+impl PartialEq for SecretString {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for SecretString {}
+
+impl PartialEq<str> for SecretString {
+    fn eq(&self, other: &str) -> bool {
+        self.0.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for SecretString {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.as_str() == *other
+    }
+}
+
+impl Serialize for SecretString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(SecretString::new)
+    }
+}
+
+/// Wraps an AES-256-GCM key and provides encrypt/decrypt helpers. The key
+/// bytes are zeroed when the cipher is dropped.
+pub(crate) struct EncryptionCipher {
+    key: Zeroizing<[u8; 32]>,
+}
+
+impl EncryptionCipher {
+    /// Create a new cipher from a 32-byte key.
+    pub(crate) fn new(key: &[u8; 32]) -> Self {
+        Self {
+            key: Zeroizing::new(*key),
+        }
+    }
+
+    /// Borrow the raw key bytes (for exporting a recovery string, or
+    /// deriving a further key such as a blind-index key via HKDF).
+    pub(crate) fn key_bytes(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    /// Encrypt `plaintext` and return `base64(nonce || ciphertext || tag)`.
+    pub(crate) fn encrypt(&self, plaintext: &str) -> Result<String, VaaniError> {
+        let cipher = Aes256Gcm::new(GenericArray::from_slice(self.key.as_slice()));
+
+        let mut nonce_bytes = [0u8; NONCE_SIZE];
+        rand::thread_rng().fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+
+        let ciphertext = cipher
+            .encrypt(nonce, plaintext.as_bytes())
+            .map_err(|e| VaaniError::Storage(format!("encryption failed: {e}")))?;
+
+        let mut combined = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+        combined.extend_from_slice(&nonce_bytes);
+        combined.extend_from_slice(&ciphertext);
+
+        Ok(BASE64.encode(&combined))
+    }
+
+    /// Decrypt a base64-encoded blob produced by [`encrypt`], returning a
+    /// [`SecretString`] whose backing memory is zeroed when dropped.
+    pub(crate) fn decrypt(&self, ciphertext_b64: &str) -> Result<SecretString, VaaniError> {
+        let combined = BASE64
+            .decode(ciphertext_b64)
+            .map_err(|e| VaaniError::Storage(format!("base64 decode failed: {e}")))?;
+
+        if combined.len() < NONCE_SIZE + 1 {
+            return Err(VaaniError::Storage("encrypted data too short".to_string()));
+        }
+
+        let (nonce_bytes, ciphertext) = combined.split_at(NONCE_SIZE);
+        let nonce = Nonce::from_slice(nonce_bytes);
+        let cipher = Aes256Gcm::new(GenericArray::from_slice(self.key.as_slice()));
+
+        let plaintext = cipher
+            .decrypt(nonce, ciphertext)
+            .map_err(|e| VaaniError::Storage(format!("decryption failed: {e}")))?;
+
+        let text = String::from_utf8(plaintext)
+            .map_err(|e| VaaniError::Storage(format!("decrypted text is not valid UTF-8: {e}")))?;
+
+        Ok(SecretString::new(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_then_decrypt_round_trips() {
+        let cipher = EncryptionCipher::new(&[7u8; 32]);
+        let encrypted = cipher.encrypt("hello, world").expect("encrypt");
+        let decrypted = cipher.decrypt(&encrypted).expect("decrypt");
+        assert_eq!(decrypted, "hello, world");
+    }
+
+    #[test]
+    fn decrypt_fails_with_wrong_key() {
+        let cipher_a = EncryptionCipher::new(&[1u8; 32]);
+        let cipher_b = EncryptionCipher::new(&[2u8; 32]);
+
+        let encrypted = cipher_a.encrypt("secret message").expect("encrypt");
+        let result = cipher_b.decrypt(&encrypted);
+
+        assert!(result.is_err(), "decryption with wrong key should fail");
+    }
+
+    #[test]
+    fn secret_string_debug_output_is_redacted() {
+        let secret = SecretString::new("super secret transcription".to_string());
+        let debug = format!("{secret:?}");
+
+        assert!(!debug.contains("super secret transcription"));
+        assert!(debug.contains("redacted"));
+    }
+
+    #[test]
+    fn secret_string_serializes_as_plain_string() {
+        let secret = SecretString::new("hello".to_string());
+        let json = serde_json::to_string(&secret).expect("serialize");
+        assert_eq!(json, "\"hello\"");
+
+        let round_tripped: SecretString = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(round_tripped, "hello");
+    }
+}
@@ -9,8 +9,15 @@
 //!
 //! A lower-level [`enhance_streaming_with_url()`] variant accepts a custom
 //! endpoint URL for integration tests against a mock server.
+//!
+//! [`AnthropicProvider`] adapts [`enhance_streaming()`] to
+//! [`super::LlmProvider`], the same shape [`super::openai::OpenAiProvider`]
+//! implements for OpenAI-compatible chat-completions servers.
+
+use std::time::Duration;
 
 use crate::error::VaaniError;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use tokio::time::Instant;
 
@@ -19,13 +26,24 @@ use tokio::time::Instant;
 // ---------------------------------------------------------------------------
 
 /// Default Anthropic Messages API endpoint.
-const DEFAULT_ANTHROPIC_URL: &str = "https://api.anthropic.com/v1/messages";
+pub(crate) const DEFAULT_ANTHROPIC_URL: &str = "https://api.anthropic.com/v1/messages";
 
 /// Required Anthropic API version header value.
-const ANTHROPIC_VERSION: &str = "2023-06-01";
+pub(crate) const ANTHROPIC_VERSION: &str = "2023-06-01";
 
 /// How often (in milliseconds) to flush buffered tokens to the callback.
-const TOKEN_FLUSH_INTERVAL_MS: u64 = 50;
+pub(crate) const TOKEN_FLUSH_INTERVAL_MS: u64 = 50;
+
+/// Max attempts (including the first) for a rate-limited/overloaded request
+/// before giving up and returning the last response to the caller.
+const MAX_RETRY_ATTEMPTS: u32 = 5;
+
+/// Base delay for the jittered exponential-backoff fallback used when the
+/// server doesn't send a `retry-after` header.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound on the backoff delay, regardless of attempt count.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
 
 // ---------------------------------------------------------------------------
 // Request / response types
@@ -33,20 +51,20 @@ const TOKEN_FLUSH_INTERVAL_MS: u64 = 50;
 
 /// A single message in the Anthropic Messages API request.
 #[derive(Debug, Serialize)]
-struct Message<'a> {
-    role: &'a str,
-    content: &'a str,
+pub(crate) struct Message<'a> {
+    pub(crate) role: &'a str,
+    pub(crate) content: &'a str,
 }
 
 /// Request body sent to the Anthropic Messages API.
 #[derive(Debug, Serialize)]
-struct AnthropicRequest<'a> {
-    model: &'a str,
-    max_tokens: u32,
-    system: &'a str,
-    messages: Vec<Message<'a>>,
+pub(crate) struct AnthropicRequest<'a> {
+    pub(crate) model: &'a str,
+    pub(crate) max_tokens: u32,
+    pub(crate) system: &'a str,
+    pub(crate) messages: Vec<Message<'a>>,
     #[serde(skip_serializing_if = "std::ops::Not::not")]
-    stream: bool,
+    pub(crate) stream: bool,
 }
 
 /// A single content block in the Anthropic response.
@@ -60,26 +78,57 @@ struct ContentBlock {
 
 /// Top-level Anthropic Messages API response (non-streaming).
 #[derive(Debug, Deserialize)]
-struct AnthropicResponse {
+pub(crate) struct AnthropicResponse {
     content: Vec<ContentBlock>,
 }
 
-/// The `delta` field inside a `content_block_delta` SSE event.
-#[derive(Debug, Deserialize)]
+/// The `delta` field inside a `content_block_delta` or `message_delta` SSE
+/// event. The two event types populate different subsets of these fields.
+#[derive(Debug, Default, Deserialize)]
 struct Delta {
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default)]
     delta_type: String,
     #[serde(default)]
     text: String,
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+/// The `error` field inside an `error` SSE event, e.g.
+/// `{"type":"overloaded_error","message":"..."}`.
+#[derive(Debug, Deserialize)]
+struct SseError {
+    #[serde(rename = "type")]
+    error_type: String,
+    message: String,
 }
 
-/// Parsed SSE `data:` payload for a `content_block_delta` event.
+/// Parsed SSE `data:` payload, covering every event type we act on:
+/// `content_block_delta`, `message_delta`, and `error`.
 #[derive(Debug, Deserialize)]
 struct SseEvent {
     #[serde(rename = "type")]
     event_type: String,
     #[serde(default)]
     delta: Option<Delta>,
+    #[serde(default)]
+    error: Option<SseError>,
+}
+
+/// What a parsed SSE event means for the caller of [`read_sse_stream`].
+#[derive(Debug, PartialEq)]
+pub(crate) enum SseOutcome {
+    /// A `content_block_delta` text fragment to append and forward.
+    Text(String),
+    /// A `message_delta` event's `stop_reason` (e.g. `"end_turn"`,
+    /// `"max_tokens"`).
+    StopReason(String),
+    /// A mid-stream `error` event — the stream has no more usable content
+    /// past this point.
+    Error { error_type: String, message: String },
+    /// Any other event (message_start, content_block_start, ping, etc.), or
+    /// a line that wasn't valid SSE data.
+    Ignored,
 }
 
 // ---------------------------------------------------------------------------
@@ -269,9 +318,27 @@ async fn read_sse_stream(
                 .to_string();
             line_buffer = line_buffer[newline_pos + 1..].to_string();
 
-            if let Some(text) = extract_sse_data_text(&line) {
-                full_text.push_str(&text);
-                token_buffer.push_str(&text);
+            match extract_sse_event(&line) {
+                SseOutcome::Text(text) => {
+                    full_text.push_str(&text);
+                    token_buffer.push_str(&text);
+                }
+                SseOutcome::StopReason(reason) if reason == "max_tokens" => {
+                    tracing::warn!(
+                        stop_reason = %reason,
+                        "Anthropic response truncated: max_tokens reached"
+                    );
+                }
+                SseOutcome::StopReason(reason) => {
+                    tracing::debug!(stop_reason = %reason, "stream stop reason");
+                }
+                SseOutcome::Error {
+                    error_type,
+                    message,
+                } => {
+                    return Err(VaaniError::Enhance(format!("{error_type}: {message}")));
+                }
+                SseOutcome::Ignored => {}
             }
         }
 
@@ -296,41 +363,55 @@ async fn read_sse_stream(
 // SSE parsing helper
 // ---------------------------------------------------------------------------
 
-/// Extract text content from a single SSE line, if it contains a text delta.
+/// Classify a single SSE line into an [`SseOutcome`].
 ///
-/// Lines that are not `data: ` prefixed, or that contain non-text events,
-/// return `None`.
-fn extract_sse_data_text(line: &str) -> Option<String> {
-    let data = line.strip_prefix("data: ")?;
-    parse_sse_text_delta(data)
+/// Lines that are not `data: ` prefixed are [`SseOutcome::Ignored`].
+pub(crate) fn extract_sse_event(line: &str) -> SseOutcome {
+    let Some(data) = line.strip_prefix("data: ") else {
+        return SseOutcome::Ignored;
+    };
+    parse_sse_event(data)
 }
 
-/// Parse a JSON `data:` payload from the SSE stream.
+/// Parse a JSON `data:` payload from the SSE stream into an [`SseOutcome`].
 ///
-/// Returns `Some(text)` if the event is a `content_block_delta` with a
-/// `text_delta` delta. Returns `None` for all other events (message_start,
-/// content_block_start, message_stop, ping, etc.) and for malformed JSON.
-fn parse_sse_text_delta(data: &str) -> Option<String> {
-    let event: SseEvent = serde_json::from_str(data).ok()?;
-
-    if event.event_type != "content_block_delta" {
-        return None;
-    }
+/// Recognizes `content_block_delta` text deltas, `message_delta` stop
+/// reasons, and `error` events. Everything else (message_start,
+/// content_block_start, message_stop, ping, etc.) and malformed JSON become
+/// [`SseOutcome::Ignored`].
+fn parse_sse_event(data: &str) -> SseOutcome {
+    let Ok(event) = serde_json::from_str::<SseEvent>(data) else {
+        return SseOutcome::Ignored;
+    };
 
-    let delta = event.delta.as_ref()?;
-    if delta.delta_type != "text_delta" {
-        return None;
+    match event.event_type.as_str() {
+        "content_block_delta" => match event.delta {
+            Some(delta) if delta.delta_type == "text_delta" => SseOutcome::Text(delta.text),
+            _ => SseOutcome::Ignored,
+        },
+        "message_delta" => event
+            .delta
+            .and_then(|delta| delta.stop_reason)
+            .map(SseOutcome::StopReason)
+            .unwrap_or(SseOutcome::Ignored),
+        "error" => match event.error {
+            Some(error) => SseOutcome::Error {
+                error_type: error.error_type,
+                message: error.message,
+            },
+            None => SseOutcome::Ignored,
+        },
+        _ => SseOutcome::Ignored,
     }
-
-    Some(delta.text.clone())
 }
 
+
 // ---------------------------------------------------------------------------
 // Shared helpers
 // ---------------------------------------------------------------------------
 
 /// Validate API key and input text before making a request.
-fn validate_inputs(api_key: &str, text: &str) -> Result<(), VaaniError> {
+pub(crate) fn validate_inputs(api_key: &str, text: &str) -> Result<(), VaaniError> {
     if api_key.is_empty() {
         return Err(VaaniError::MissingApiKey("Anthropic".into()));
     }
@@ -347,21 +428,127 @@ async fn send_request(
     api_key: &str,
     body: &AnthropicRequest<'_>,
 ) -> Result<reqwest::Response, VaaniError> {
-    let response = client
-        .post(url)
-        .header("x-api-key", api_key)
-        .header("anthropic-version", ANTHROPIC_VERSION)
-        .header("content-type", "application/json")
-        .json(body)
-        .send()
-        .await
-        .map_err(|e| VaaniError::Enhance(format!("request failed: {e}")))?;
+    let mut attempt = 0;
+    loop {
+        let response = client
+            .post(url)
+            .header("x-api-key", api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .header("content-type", "application/json")
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| VaaniError::Enhance(format!("request failed: {e}")))?;
+
+        let status = response.status();
+        if status.is_success() || !is_retryable(status) || attempt + 1 >= MAX_RETRY_ATTEMPTS {
+            return Ok(response);
+        }
 
-    Ok(response)
+        let delay = backoff_delay(&response, attempt);
+        tracing::warn!(
+            attempt = attempt + 1,
+            %status,
+            wait_ms = delay.as_millis() as u64,
+            "Anthropic request rate-limited/overloaded, retrying"
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Whether an HTTP status is worth retrying: rate-limited (429), overloaded
+/// (529, an Anthropic-specific status), or any 5xx. Other 4xx responses are
+/// permanent client errors (bad request, auth failure, etc.) and retrying
+/// them would just fail the same way again.
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.as_u16() == 429 || status.as_u16() == 529 || status.is_server_error()
+}
+
+/// How long to wait before the next attempt: the response's `retry-after`
+/// header when present and parseable, otherwise jittered exponential
+/// backoff (`base * 2^attempt`, capped, scaled by a `[0.5, 1.0)` jitter
+/// factor so concurrent retries don't all wake up at once).
+fn backoff_delay(response: &reqwest::Response, attempt: u32) -> Duration {
+    if let Some(delay) = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_retry_after)
+    {
+        return delay;
+    }
+
+    let multiplier = 2u64.saturating_pow(attempt);
+    let exp_millis = (RETRY_BASE_DELAY.as_millis() as u64).saturating_mul(multiplier);
+    let capped_millis = exp_millis.min(RETRY_MAX_DELAY.as_millis() as u64);
+    let jitter = rand::thread_rng().gen_range(0.5..1.0);
+    Duration::from_millis((capped_millis as f64 * jitter) as u64)
+}
+
+/// Parse a `retry-after` header value, which per RFC 9110 is either an
+/// integer number of seconds or an HTTP-date (e.g. `"Sun, 06 Nov 1994
+/// 08:49:37 GMT"`).
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let target = parse_http_date(value)?;
+    target.duration_since(std::time::SystemTime::now()).ok()
+}
+
+/// Parse the IMF-fixdate form of an HTTP-date, the only form RFC 9110
+/// allows senders to generate. Hand-rolled to avoid pulling in a
+/// date-time crate for a single header field.
+fn parse_http_date(s: &str) -> Option<std::time::SystemTime> {
+    // e.g. "Sun, 06 Nov 1994 08:49:37 GMT"
+    let rest = s.split_once(", ")?.1;
+    let mut parts = rest.split_whitespace();
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month = match parts.next()? {
+        "Jan" => 1,
+        "Feb" => 2,
+        "Mar" => 3,
+        "Apr" => 4,
+        "May" => 5,
+        "Jun" => 6,
+        "Jul" => 7,
+        "Aug" => 8,
+        "Sep" => 9,
+        "Oct" => 10,
+        "Nov" => 11,
+        "Dec" => 12,
+        _ => return None,
+    };
+    let year: i64 = parts.next()?.parse().ok()?;
+    let mut time_parts = parts.next()?.split(':');
+    let hour: u64 = time_parts.next()?.parse().ok()?;
+    let minute: u64 = time_parts.next()?.parse().ok()?;
+    let second: u64 = time_parts.next()?.parse().ok()?;
+
+    let days = days_from_civil(year, month, day);
+    let seconds_since_epoch = days
+        .checked_mul(86_400)?
+        .checked_add(hour * 3600 + minute * 60 + second)?;
+    Some(std::time::UNIX_EPOCH + Duration::from_secs(seconds_since_epoch))
+}
+
+/// Days since the Unix epoch for a civil (proleptic Gregorian) date.
+/// Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(y: i64, m: u32, d: u32) -> u64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = (if y >= 0 { y } else { y - 399 }) / 400;
+    let yoe = (y - era * 400) as u64;
+    let mp = (m as u64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d as u64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    (era * 146_097 + doe as i64 - 719_468) as u64
 }
 
 /// Extract all text content from an Anthropic response.
-fn extract_text_from_response(response: &AnthropicResponse) -> String {
+pub(crate) fn extract_text_from_response(response: &AnthropicResponse) -> String {
     response
         .content
         .iter()
@@ -371,6 +558,46 @@ fn extract_text_from_response(response: &AnthropicResponse) -> String {
         .join("")
 }
 
+// ---------------------------------------------------------------------------
+// LlmProvider adapter
+// ---------------------------------------------------------------------------
+
+/// [`super::LlmProvider`] backed by the Anthropic Messages API.
+pub struct AnthropicProvider {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+}
+
+impl AnthropicProvider {
+    pub fn new(api_key: String, model: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key,
+            model,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl super::LlmProvider for AnthropicProvider {
+    fn name(&self) -> &'static str {
+        "anthropic"
+    }
+
+    async fn enhance_streaming(
+        &self,
+        text: &str,
+        system_prompt: &str,
+        mut on_tokens: Box<dyn FnMut(&str) + Send>,
+    ) -> Result<String, VaaniError> {
+        enhance_streaming(&self.client, &self.api_key, text, &self.model, system_prompt, |t| {
+            on_tokens(t)
+        })
+        .await
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -433,61 +660,88 @@ mod tests {
     // ---- SSE parsing ----
 
     #[test]
-    fn parse_sse_text_delta_valid() {
+    fn parse_sse_event_text_delta() {
         let data = r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello"}}"#;
-        let result = parse_sse_text_delta(data);
-        assert_eq!(result, Some("Hello".to_string()));
+        assert_eq!(parse_sse_event(data), SseOutcome::Text("Hello".to_string()));
     }
 
     #[test]
-    fn parse_sse_text_delta_non_text_event() {
+    fn parse_sse_event_non_text_event() {
         let data = r#"{"type":"message_start","message":{"id":"msg_123"}}"#;
-        let result = parse_sse_text_delta(data);
-        assert_eq!(result, None);
+        assert_eq!(parse_sse_event(data), SseOutcome::Ignored);
     }
 
     #[test]
-    fn parse_sse_text_delta_invalid_json() {
-        let result = parse_sse_text_delta("this is not json at all");
-        assert_eq!(result, None);
+    fn parse_sse_event_invalid_json() {
+        assert_eq!(parse_sse_event("this is not json at all"), SseOutcome::Ignored);
     }
 
     #[test]
-    fn parse_sse_text_delta_message_stop() {
+    fn parse_sse_event_message_stop() {
         let data = r#"{"type":"message_stop"}"#;
-        let result = parse_sse_text_delta(data);
-        assert_eq!(result, None);
+        assert_eq!(parse_sse_event(data), SseOutcome::Ignored);
     }
 
     #[test]
-    fn parse_sse_text_delta_content_block_start() {
+    fn parse_sse_event_content_block_start() {
         let data =
             r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#;
-        let result = parse_sse_text_delta(data);
-        assert_eq!(result, None);
+        assert_eq!(parse_sse_event(data), SseOutcome::Ignored);
     }
 
     #[test]
-    fn parse_sse_text_delta_with_special_characters() {
+    fn parse_sse_event_with_special_characters() {
         let data = r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello \"world\" \n\ttab"}}"#;
-        let result = parse_sse_text_delta(data);
-        assert_eq!(result, Some("Hello \"world\" \n\ttab".to_string()));
+        assert_eq!(
+            parse_sse_event(data),
+            SseOutcome::Text("Hello \"world\" \n\ttab".to_string())
+        );
     }
 
-    // ---- extract_sse_data_text ----
+    #[test]
+    fn parse_sse_event_message_delta_stop_reason() {
+        let data = r#"{"type":"message_delta","delta":{"stop_reason":"max_tokens"}}"#;
+        assert_eq!(
+            parse_sse_event(data),
+            SseOutcome::StopReason("max_tokens".to_string())
+        );
+    }
 
     #[test]
-    fn extract_sse_data_text_strips_prefix() {
+    fn parse_sse_event_message_delta_without_stop_reason_is_ignored() {
+        let data = r#"{"type":"message_delta","delta":{"usage":{"output_tokens":12}}}"#;
+        assert_eq!(parse_sse_event(data), SseOutcome::Ignored);
+    }
+
+    #[test]
+    fn parse_sse_event_error() {
+        let data =
+            r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        assert_eq!(
+            parse_sse_event(data),
+            SseOutcome::Error {
+                error_type: "overloaded_error".to_string(),
+                message: "Overloaded".to_string(),
+            }
+        );
+    }
+
+    // ---- extract_sse_event ----
+
+    #[test]
+    fn extract_sse_event_strips_prefix() {
         let line = r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#;
-        let result = extract_sse_data_text(line);
-        assert_eq!(result, Some("Hi".to_string()));
+        assert_eq!(extract_sse_event(line), SseOutcome::Text("Hi".to_string()));
     }
 
     #[test]
-    fn extract_sse_data_text_ignores_non_data_lines() {
-        assert_eq!(extract_sse_data_text("event: content_block_delta"), None);
-        assert_eq!(extract_sse_data_text(""), None);
-        assert_eq!(extract_sse_data_text(": comment"), None);
+    fn extract_sse_event_ignores_non_data_lines() {
+        assert_eq!(
+            extract_sse_event("event: content_block_delta"),
+            SseOutcome::Ignored
+        );
+        assert_eq!(extract_sse_event(""), SseOutcome::Ignored);
+        assert_eq!(extract_sse_event(": comment"), SseOutcome::Ignored);
     }
 
     // ---- Response deserialization ----
@@ -610,4 +864,60 @@ mod tests {
         let json = serde_json::to_value(&req).expect("serialization should succeed");
         assert_eq!(json["stream"], true);
     }
+
+    // ---- LlmProvider adapter ----
+
+    #[test]
+    fn provider_name_is_anthropic() {
+        use super::super::LlmProvider;
+        let provider = AnthropicProvider::new("key".into(), "claude-haiku".into());
+        assert_eq!(provider.name(), "anthropic");
+    }
+
+    // ---- Retry logic ----
+
+    #[test]
+    fn is_retryable_accepts_429_529_and_5xx() {
+        assert!(is_retryable(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(reqwest::StatusCode::from_u16(529).unwrap()));
+        assert!(is_retryable(reqwest::StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    #[test]
+    fn is_retryable_rejects_other_4xx() {
+        assert!(!is_retryable(reqwest::StatusCode::BAD_REQUEST));
+        assert!(!is_retryable(reqwest::StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable(reqwest::StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn parse_retry_after_accepts_integer_seconds() {
+        assert_eq!(parse_retry_after("3"), Some(Duration::from_secs(3)));
+        assert_eq!(parse_retry_after("  12 "), Some(Duration::from_secs(12)));
+    }
+
+    #[test]
+    fn parse_retry_after_rejects_garbage() {
+        assert_eq!(parse_retry_after("not-a-delay"), None);
+    }
+
+    #[test]
+    fn parse_http_date_round_trips_a_known_instant() {
+        // 1994-11-06 08:49:37 UTC, the example date from RFC 9110.
+        let parsed = parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        let expected = std::time::UNIX_EPOCH + Duration::from_secs(784_111_777);
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn parse_http_date_rejects_malformed_input() {
+        assert!(parse_http_date("not a date").is_none());
+    }
+
+    #[test]
+    fn days_from_civil_matches_unix_epoch() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(1970, 1, 2), 1);
+    }
 }
@@ -0,0 +1,231 @@
+//! Blocking (non-async) Anthropic enhancement, for embedding Vaani's
+//! enhancement in synchronous tooling or tests without a Tokio runtime.
+//! Gated behind the `blocking` Cargo feature.
+//!
+//! Shares request/response types, input validation, and SSE line parsing
+//! with [`super::anthropic`] so the two code paths stay behavior-identical
+//! — only the HTTP client and read loop are duplicated for
+//! `reqwest::blocking`.
+
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use crate::error::VaaniError;
+
+use super::anthropic::{
+    extract_sse_event, extract_text_from_response, validate_inputs, AnthropicRequest,
+    AnthropicResponse, Message, SseOutcome, ANTHROPIC_VERSION, DEFAULT_ANTHROPIC_URL,
+    TOKEN_FLUSH_INTERVAL_MS,
+};
+
+/// Enhance text using the Anthropic Messages API (blocking, non-streaming).
+///
+/// # Errors
+///
+/// Same error conditions as [`super::anthropic::enhance()`].
+pub fn enhance_blocking(
+    client: &reqwest::blocking::Client,
+    api_key: &str,
+    text: &str,
+    model: &str,
+    system_prompt: &str,
+) -> Result<String, VaaniError> {
+    validate_inputs(api_key, text)?;
+
+    let body = AnthropicRequest {
+        model,
+        max_tokens: 4096,
+        system: system_prompt,
+        messages: vec![Message {
+            role: "user",
+            content: text,
+        }],
+        stream: false,
+    };
+
+    let response = client
+        .post(DEFAULT_ANTHROPIC_URL)
+        .header("x-api-key", api_key)
+        .header("anthropic-version", ANTHROPIC_VERSION)
+        .header("content-type", "application/json")
+        .json(&body)
+        .send()
+        .map_err(|e| VaaniError::Enhance(format!("request failed: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .unwrap_or_else(|_| "<unreadable body>".into());
+        return Err(VaaniError::Enhance(format!("HTTP {status}: {body}")));
+    }
+
+    let parsed: AnthropicResponse = response
+        .json()
+        .map_err(|e| VaaniError::Enhance(format!("failed to parse response: {e}")))?;
+
+    Ok(extract_text_from_response(&parsed))
+}
+
+/// Enhance text using the Anthropic Messages API with SSE streaming
+/// (blocking). `on_tokens` is invoked incrementally as lines arrive, the
+/// same contract as [`super::anthropic::enhance_streaming()`].
+///
+/// # Errors
+///
+/// Same error conditions as [`super::anthropic::enhance_streaming()`].
+pub fn enhance_streaming_blocking(
+    client: &reqwest::blocking::Client,
+    api_key: &str,
+    text: &str,
+    model: &str,
+    system_prompt: &str,
+    mut on_tokens: impl FnMut(&str),
+) -> Result<String, VaaniError> {
+    validate_inputs(api_key, text)?;
+
+    let body = AnthropicRequest {
+        model,
+        max_tokens: 4096,
+        system: system_prompt,
+        messages: vec![Message {
+            role: "user",
+            content: text,
+        }],
+        stream: true,
+    };
+
+    let mut response = client
+        .post(DEFAULT_ANTHROPIC_URL)
+        .header("x-api-key", api_key)
+        .header("anthropic-version", ANTHROPIC_VERSION)
+        .header("content-type", "application/json")
+        .json(&body)
+        .send()
+        .map_err(|e| VaaniError::Enhance(format!("request failed: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .unwrap_or_else(|_| "<unreadable body>".into());
+        return Err(VaaniError::Enhance(format!("HTTP {status}: {body}")));
+    }
+
+    read_sse_stream_blocking(&mut response, &mut on_tokens)
+}
+
+/// Read a blocking SSE response body, buffer tokens, and flush
+/// periodically — the blocking-client mirror of
+/// [`super::anthropic`]'s `read_sse_stream`.
+fn read_sse_stream_blocking(
+    response: &mut reqwest::blocking::Response,
+    on_tokens: &mut impl FnMut(&str),
+) -> Result<String, VaaniError> {
+    let mut full_text = String::new();
+    let mut token_buffer = String::new();
+    let mut last_flush = Instant::now();
+    let mut line_buffer = String::new();
+    let flush_interval = Duration::from_millis(TOKEN_FLUSH_INTERVAL_MS);
+    let mut read_buf = [0u8; 8192];
+
+    loop {
+        let n = response
+            .read(&mut read_buf)
+            .map_err(|e| VaaniError::Enhance(format!("stream read error: {e}")))?;
+        if n == 0 {
+            break;
+        }
+        line_buffer.push_str(&String::from_utf8_lossy(&read_buf[..n]));
+
+        while let Some(newline_pos) = line_buffer.find('\n') {
+            let line = line_buffer[..newline_pos]
+                .trim_end_matches('\r')
+                .to_string();
+            line_buffer = line_buffer[newline_pos + 1..].to_string();
+
+            match extract_sse_event(&line) {
+                SseOutcome::Text(text) => {
+                    full_text.push_str(&text);
+                    token_buffer.push_str(&text);
+                }
+                SseOutcome::StopReason(reason) if reason == "max_tokens" => {
+                    tracing::warn!(
+                        stop_reason = %reason,
+                        "Anthropic response truncated: max_tokens reached"
+                    );
+                }
+                SseOutcome::Error {
+                    error_type,
+                    message,
+                } => {
+                    return Err(VaaniError::Enhance(format!("{error_type}: {message}")));
+                }
+                SseOutcome::StopReason(_) | SseOutcome::Ignored => {}
+            }
+        }
+
+        if !token_buffer.is_empty() && last_flush.elapsed() >= flush_interval {
+            on_tokens(&token_buffer);
+            token_buffer.clear();
+            last_flush = Instant::now();
+        }
+    }
+
+    if !token_buffer.is_empty() {
+        on_tokens(&token_buffer);
+    }
+
+    Ok(full_text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_api_key_returns_error() {
+        let client = reqwest::blocking::Client::new();
+        let result = enhance_blocking(&client, "", "some text", "claude-haiku", "system");
+
+        match result.unwrap_err() {
+            VaaniError::MissingApiKey(provider) => assert_eq!(provider, "Anthropic"),
+            other => panic!("expected MissingApiKey, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn empty_text_returns_error() {
+        let client = reqwest::blocking::Client::new();
+        let result = enhance_blocking(&client, "sk-test", "", "claude-haiku", "system");
+
+        match result.unwrap_err() {
+            VaaniError::Enhance(msg) => assert!(msg.contains("empty")),
+            other => panic!("expected Enhance error, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn streaming_missing_api_key_returns_error() {
+        let client = reqwest::blocking::Client::new();
+        let result =
+            enhance_streaming_blocking(&client, "", "some text", "claude-haiku", "system", |_| {});
+
+        match result.unwrap_err() {
+            VaaniError::MissingApiKey(provider) => assert_eq!(provider, "Anthropic"),
+            other => panic!("expected MissingApiKey, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn streaming_empty_text_returns_error() {
+        let client = reqwest::blocking::Client::new();
+        let result =
+            enhance_streaming_blocking(&client, "sk-test", "", "claude-haiku", "system", |_| {});
+
+        match result.unwrap_err() {
+            VaaniError::Enhance(msg) => assert!(msg.contains("empty")),
+            other => panic!("expected Enhance error, got: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Pluggable LLM enhancement backends.
+//!
+//! [`LlmProvider`] is the abstraction `app::VaaniApp` enhances through;
+//! [`resolve_llm_provider`] picks the concrete implementation named by
+//! `VaaniConfig::llm_provider`, the same config-driven-selection shape as
+//! [`crate::transcribe::resolve_stt_backend`]. `anthropic` wraps the
+//! original Messages API, and `openai` posts to a chat-completions endpoint
+//! — either OpenAI's hosted one or, via `VaaniConfig::llm_base_url`, a local
+//! OpenAI-compatible server (llama.cpp, vLLM, etc).
+//!
+//! With the `blocking` Cargo feature enabled, [`enhance_blocking`] and
+//! [`enhance_streaming_blocking`] offer the same Anthropic enhancement
+//! without a Tokio runtime, for embedding in synchronous tooling or tests.
+
+mod anthropic;
+#[cfg(feature = "blocking")]
+mod blocking;
+mod openai;
+
+pub use anthropic::{enhance, enhance_streaming, enhance_streaming_with_url, AnthropicProvider};
+#[cfg(feature = "blocking")]
+pub use blocking::{enhance_blocking, enhance_streaming_blocking};
+pub use openai::{enhance as enhance_openai, OpenAiProvider};
+
+use crate::error::VaaniError;
+
+/// Names accepted by the `llm_provider` config field.
+pub const LLM_PROVIDERS: &[&str] = &["anthropic", "openai"];
+
+/// Abstraction over an LLM enhancement backend.
+#[async_trait::async_trait]
+pub trait LlmProvider: Send + Sync {
+    /// Human-readable name of this provider (e.g. `"anthropic"`, `"openai"`).
+    fn name(&self) -> &'static str;
+
+    /// Enhance `text` under `system_prompt`, streaming tokens to
+    /// `on_tokens` as they arrive, and return the full accumulated text.
+    async fn enhance_streaming(
+        &self,
+        text: &str,
+        system_prompt: &str,
+        on_tokens: Box<dyn FnMut(&str) + Send>,
+    ) -> Result<String, VaaniError>;
+}
+
+/// Resolve an [`LlmProvider`] from the `llm_provider` config field.
+///
+/// `api_key` is the key resolved via `app::resolve_api_key` for the chosen
+/// provider. `base_url`, from `VaaniConfig::llm_base_url`, overrides the
+/// provider's default endpoint when set — `"anthropic"` ignores it today
+/// since no customer has asked for a proxied Anthropic endpoint yet.
+///
+/// Returns `VaaniError::Config` if `provider_name` is unrecognised, or
+/// `VaaniError::MissingApiKey` if the chosen provider has no resolvable key.
+pub fn resolve_llm_provider(
+    provider_name: &str,
+    model: &str,
+    base_url: Option<String>,
+    api_key: Option<String>,
+) -> Result<Box<dyn LlmProvider>, VaaniError> {
+    match provider_name {
+        "anthropic" => {
+            let api_key =
+                api_key.ok_or_else(|| VaaniError::MissingApiKey("Anthropic".to_string()))?;
+            Ok(Box::new(AnthropicProvider::new(api_key, model.to_string())))
+        }
+        "openai" => {
+            let api_key =
+                api_key.ok_or_else(|| VaaniError::MissingApiKey("OpenAI".to_string()))?;
+            Ok(Box::new(OpenAiProvider::new(
+                api_key,
+                model.to_string(),
+                base_url,
+            )))
+        }
+        other => Err(VaaniError::Config(format!(
+            "Unknown llm_provider '{other}'. Valid values: {}",
+            LLM_PROVIDERS.join(", ")
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_unknown_provider_is_config_error() {
+        match resolve_llm_provider("carrier-pigeon", "model", None, Some("key".into())) {
+            Err(VaaniError::Config(msg)) => {
+                assert!(msg.contains("carrier-pigeon"));
+                assert!(msg.contains("anthropic"));
+            }
+            other => panic!("expected Config error, got provider: {}", other.is_ok()),
+        }
+    }
+
+    #[test]
+    fn resolve_anthropic_without_key_is_missing_api_key() {
+        let result = resolve_llm_provider("anthropic", "claude-haiku", None, None);
+        assert!(matches!(result, Err(VaaniError::MissingApiKey(provider)) if provider == "Anthropic"));
+    }
+
+    #[test]
+    fn resolve_openai_without_key_is_missing_api_key() {
+        let result = resolve_llm_provider("openai", "gpt-4o-mini", None, None);
+        assert!(matches!(result, Err(VaaniError::MissingApiKey(provider)) if provider == "OpenAI"));
+    }
+
+    #[test]
+    fn resolve_openai_with_custom_base_url() {
+        let provider = resolve_llm_provider(
+            "openai",
+            "llama3",
+            Some("http://localhost:8080/v1/chat/completions".into()),
+            Some("key".into()),
+        )
+        .expect("should resolve");
+        assert_eq!(provider.name(), "openai");
+    }
+}
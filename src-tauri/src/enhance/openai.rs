@@ -0,0 +1,466 @@
+//! OpenAI-compatible chat-completions enhancement backend.
+//!
+//! [`enhance_streaming()`] posts `{model, messages, stream:true}` to a
+//! chat-completions endpoint and parses `choices[].delta.content` from SSE
+//! `data:` lines, stopping at the literal `data: [DONE]` sentinel Anthropic
+//! never sends. [`enhance()`] is the non-streaming counterpart, mirroring
+//! [`super::anthropic::enhance()`]. Both default to OpenAI's hosted
+//! endpoint, but [`OpenAiProvider::new`] accepts a custom `base_url` so the
+//! same code also talks to local llama.cpp servers or any other
+//! OpenAI-compatible gateway (`VaaniConfig::llm_base_url`).
+
+use crate::error::VaaniError;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+/// Default OpenAI chat-completions endpoint.
+const DEFAULT_OPENAI_URL: &str = "https://api.openai.com/v1/chat/completions";
+
+/// How often (in milliseconds) to flush buffered tokens to the callback.
+const TOKEN_FLUSH_INTERVAL_MS: u64 = 50;
+
+/// The literal SSE payload OpenAI sends to mark the end of a stream.
+const DONE_SENTINEL: &str = "[DONE]";
+
+/// A single message in the chat-completions request.
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+/// Request body sent to the chat-completions endpoint.
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    stream: bool,
+}
+
+/// One `choices[]` entry in a streaming chat-completions chunk.
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    #[serde(default)]
+    delta: ChatDelta,
+}
+
+/// The `delta` field inside a streaming `choices[]` entry.
+#[derive(Debug, Default, Deserialize)]
+struct ChatDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// Parsed SSE `data:` payload for a streaming chat-completions chunk.
+#[derive(Debug, Deserialize)]
+struct ChatChunk {
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+}
+
+/// One `choices[]` entry in a non-streaming chat-completions response.
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChoice {
+    message: ChatCompletionMessage,
+}
+
+/// The `message` field inside a non-streaming `choices[]` entry.
+#[derive(Debug, Deserialize)]
+struct ChatCompletionMessage {
+    #[serde(default)]
+    content: String,
+}
+
+/// Parsed body of a non-streaming chat-completions response.
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    #[serde(default)]
+    choices: Vec<ChatCompletionChoice>,
+}
+
+/// Enhance text using the default OpenAI chat-completions endpoint, without
+/// streaming. Mirrors [`super::anthropic::enhance()`] for callers (and
+/// tests) that don't need incremental tokens.
+///
+/// # Errors
+///
+/// Returns [`VaaniError::MissingApiKey`] if `api_key` is empty,
+/// [`VaaniError::Enhance`] if the input text is empty or the API returns an
+/// error, and [`VaaniError::Http`] on network failures.
+pub async fn enhance(
+    client: &reqwest::Client,
+    api_key: &str,
+    text: &str,
+    model: &str,
+    system_prompt: &str,
+) -> Result<String, VaaniError> {
+    enhance_with_url(
+        client,
+        DEFAULT_OPENAI_URL,
+        api_key,
+        text,
+        model,
+        system_prompt,
+    )
+    .await
+}
+
+/// Non-streaming enhance with a configurable endpoint URL (for testing).
+async fn enhance_with_url(
+    client: &reqwest::Client,
+    url: &str,
+    api_key: &str,
+    text: &str,
+    model: &str,
+    system_prompt: &str,
+) -> Result<String, VaaniError> {
+    validate_inputs(api_key, text)?;
+
+    let body = ChatRequest {
+        model,
+        messages: vec![
+            ChatMessage {
+                role: "system",
+                content: system_prompt,
+            },
+            ChatMessage {
+                role: "user",
+                content: text,
+            },
+        ],
+        stream: false,
+    };
+
+    tracing::debug!(
+        url = url,
+        model = model,
+        "sending non-streaming chat-completions request"
+    );
+
+    let response = client
+        .post(url)
+        .header("Authorization", format!("Bearer {api_key}"))
+        .header("content-type", "application/json")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| VaaniError::Enhance(format!("request failed: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<unreadable body>".into());
+        return Err(VaaniError::Enhance(format!("HTTP {status}: {body}")));
+    }
+
+    let parsed: ChatCompletionResponse = response
+        .json()
+        .await
+        .map_err(|e| VaaniError::Enhance(format!("failed to parse response: {e}")))?;
+
+    let result = parsed
+        .choices
+        .into_iter()
+        .next()
+        .map(|c| c.message.content)
+        .unwrap_or_default();
+    tracing::debug!(chars = result.len(), "enhancement complete");
+    Ok(result)
+}
+
+/// Enhance text using the default OpenAI chat-completions endpoint.
+///
+/// # Errors
+///
+/// Returns [`VaaniError::MissingApiKey`] if `api_key` is empty,
+/// [`VaaniError::Enhance`] if the input text is empty or the API returns an
+/// error, and [`VaaniError::Http`] on network failures.
+pub async fn enhance_streaming(
+    client: &reqwest::Client,
+    api_key: &str,
+    text: &str,
+    model: &str,
+    system_prompt: &str,
+    on_tokens: impl FnMut(&str) + Send,
+) -> Result<String, VaaniError> {
+    enhance_streaming_with_url(
+        client,
+        DEFAULT_OPENAI_URL,
+        api_key,
+        text,
+        model,
+        system_prompt,
+        on_tokens,
+    )
+    .await
+}
+
+/// Streaming enhance with a configurable endpoint URL, so a custom
+/// `base_url` can point at a local OpenAI-compatible server instead of the
+/// hosted OpenAI API.
+pub async fn enhance_streaming_with_url(
+    client: &reqwest::Client,
+    url: &str,
+    api_key: &str,
+    text: &str,
+    model: &str,
+    system_prompt: &str,
+    mut on_tokens: impl FnMut(&str) + Send,
+) -> Result<String, VaaniError> {
+    validate_inputs(api_key, text)?;
+
+    let body = ChatRequest {
+        model,
+        messages: vec![
+            ChatMessage {
+                role: "system",
+                content: system_prompt,
+            },
+            ChatMessage {
+                role: "user",
+                content: text,
+            },
+        ],
+        stream: true,
+    };
+
+    tracing::debug!(
+        url = url,
+        model = model,
+        "sending streaming chat-completions request"
+    );
+
+    let response = client
+        .post(url)
+        .header("Authorization", format!("Bearer {api_key}"))
+        .header("content-type", "application/json")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| VaaniError::Enhance(format!("request failed: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<unreadable body>".into());
+        return Err(VaaniError::Enhance(format!("HTTP {status}: {body}")));
+    }
+
+    read_sse_stream(response, &mut on_tokens).await
+}
+
+/// Read an SSE response body, buffer tokens, and flush periodically.
+///
+/// Stops as soon as a `data: [DONE]` line is seen, mirroring how
+/// [`super::anthropic::read_sse_stream`] (Anthropic doesn't send a `[DONE]`
+/// sentinel; it relies on the connection closing instead) runs to EOF.
+async fn read_sse_stream(
+    mut response: reqwest::Response,
+    on_tokens: &mut (impl FnMut(&str) + Send),
+) -> Result<String, VaaniError> {
+    let mut full_text = String::new();
+    let mut token_buffer = String::new();
+    let mut last_flush = Instant::now();
+    let mut line_buffer = String::new();
+    let flush_interval = std::time::Duration::from_millis(TOKEN_FLUSH_INTERVAL_MS);
+
+    'stream: while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| VaaniError::Enhance(format!("stream read error: {e}")))?
+    {
+        let chunk_str = String::from_utf8_lossy(&chunk);
+        line_buffer.push_str(&chunk_str);
+
+        while let Some(newline_pos) = line_buffer.find('\n') {
+            let line = line_buffer[..newline_pos]
+                .trim_end_matches('\r')
+                .to_string();
+            line_buffer = line_buffer[newline_pos + 1..].to_string();
+
+            let Some(data) = line.strip_prefix("data: ") else {
+                continue;
+            };
+            if data == DONE_SENTINEL {
+                break 'stream;
+            }
+            if let Some(text) = parse_sse_text_delta(data) {
+                full_text.push_str(&text);
+                token_buffer.push_str(&text);
+            }
+        }
+
+        if !token_buffer.is_empty() && last_flush.elapsed() >= flush_interval {
+            on_tokens(&token_buffer);
+            token_buffer.clear();
+            last_flush = Instant::now();
+        }
+    }
+
+    if !token_buffer.is_empty() {
+        on_tokens(&token_buffer);
+    }
+
+    tracing::debug!(chars = full_text.len(), "streaming enhancement complete");
+    Ok(full_text)
+}
+
+/// Parse a JSON `data:` payload from the SSE stream, extracting
+/// `choices[0].delta.content` if present. Returns `None` for malformed JSON
+/// or a chunk with no content delta (e.g. the role-only opening chunk).
+fn parse_sse_text_delta(data: &str) -> Option<String> {
+    let chunk: ChatChunk = serde_json::from_str(data).ok()?;
+    chunk.choices.into_iter().find_map(|c| c.delta.content)
+}
+
+/// Validate API key and input text before making a request.
+fn validate_inputs(api_key: &str, text: &str) -> Result<(), VaaniError> {
+    if api_key.is_empty() {
+        return Err(VaaniError::MissingApiKey("OpenAI".into()));
+    }
+    if text.trim().is_empty() {
+        return Err(VaaniError::Enhance("input text is empty".into()));
+    }
+    Ok(())
+}
+
+/// [`super::LlmProvider`] backed by an OpenAI-compatible chat-completions
+/// endpoint. `base_url` defaults to OpenAI's hosted API but can point at a
+/// local server instead (see [`VaaniConfig::llm_base_url`](crate::config::VaaniConfig::llm_base_url)).
+pub struct OpenAiProvider {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+    base_url: String,
+}
+
+impl OpenAiProvider {
+    pub fn new(api_key: String, model: String, base_url: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key,
+            model,
+            base_url: base_url.unwrap_or_else(|| DEFAULT_OPENAI_URL.to_string()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl super::LlmProvider for OpenAiProvider {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    async fn enhance_streaming(
+        &self,
+        text: &str,
+        system_prompt: &str,
+        mut on_tokens: Box<dyn FnMut(&str) + Send>,
+    ) -> Result<String, VaaniError> {
+        enhance_streaming_with_url(
+            &self.client,
+            &self.base_url,
+            &self.api_key,
+            text,
+            &self.model,
+            system_prompt,
+            |t| on_tokens(t),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::LlmProvider;
+    use super::*;
+
+    #[tokio::test]
+    async fn missing_api_key_returns_error() {
+        let client = reqwest::Client::new();
+        let result = enhance_streaming(&client, "", "some text", "gpt-4o-mini", "system", |_| {}).await;
+
+        match result.unwrap_err() {
+            VaaniError::MissingApiKey(provider) => assert_eq!(provider, "OpenAI"),
+            other => panic!("expected MissingApiKey, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_text_returns_error() {
+        let client = reqwest::Client::new();
+        let result = enhance_streaming(&client, "sk-test", "", "gpt-4o-mini", "system", |_| {}).await;
+
+        match result.unwrap_err() {
+            VaaniError::Enhance(msg) => assert!(msg.contains("empty")),
+            other => panic!("expected Enhance error, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn non_streaming_missing_api_key_returns_error() {
+        let client = reqwest::Client::new();
+        let result = enhance(&client, "", "some text", "gpt-4o-mini", "system").await;
+
+        match result.unwrap_err() {
+            VaaniError::MissingApiKey(provider) => assert_eq!(provider, "OpenAI"),
+            other => panic!("expected MissingApiKey, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn non_streaming_empty_text_returns_error() {
+        let client = reqwest::Client::new();
+        let result = enhance(&client, "sk-test", "", "gpt-4o-mini", "system").await;
+
+        match result.unwrap_err() {
+            VaaniError::Enhance(msg) => assert!(msg.contains("empty")),
+            other => panic!("expected Enhance error, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn chat_completion_response_extracts_first_choice_content() {
+        let json = r#"{"choices":[{"message":{"role":"assistant","content":"Hello there"}}]}"#;
+        let parsed: ChatCompletionResponse = serde_json::from_str(json).expect("should parse");
+        assert_eq!(parsed.choices[0].message.content, "Hello there");
+    }
+
+    #[test]
+    fn parse_sse_text_delta_valid() {
+        let data = r#"{"choices":[{"delta":{"content":"Hello"}}]}"#;
+        assert_eq!(parse_sse_text_delta(data), Some("Hello".to_string()));
+    }
+
+    #[test]
+    fn parse_sse_text_delta_role_only_chunk() {
+        let data = r#"{"choices":[{"delta":{"role":"assistant"}}]}"#;
+        assert_eq!(parse_sse_text_delta(data), None);
+    }
+
+    #[test]
+    fn parse_sse_text_delta_invalid_json() {
+        assert_eq!(parse_sse_text_delta("not json"), None);
+    }
+
+    #[test]
+    fn provider_defaults_to_openai_url() {
+        let provider = OpenAiProvider::new("key".into(), "gpt-4o-mini".into(), None);
+        assert_eq!(provider.base_url, DEFAULT_OPENAI_URL);
+        assert_eq!(provider.name(), "openai");
+    }
+
+    #[test]
+    fn provider_honors_custom_base_url() {
+        let provider = OpenAiProvider::new(
+            "key".into(),
+            "llama3".into(),
+            Some("http://localhost:8080/v1/chat/completions".into()),
+        );
+        assert_eq!(provider.base_url, "http://localhost:8080/v1/chat/completions");
+    }
+}
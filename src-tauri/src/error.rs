@@ -21,6 +21,9 @@ pub enum VaaniError {
     #[error("Text enhancement failed: {0}")]
     Enhance(String),
 
+    #[error("Text-to-speech failed: {0}")]
+    Tts(String),
+
     #[error("Storage error: {0}")]
     Storage(String),
 
@@ -47,6 +50,12 @@ pub enum VaaniError {
 
     #[error("Paste error: {0}")]
     Paste(String),
+
+    #[error("Update error: {0}")]
+    Update(String),
+
+    #[error("Timed out after {0:?} waiting for state {1}")]
+    WaitTimeout(std::time::Duration, String),
 }
 
 /// Serialize implementation required for Tauri commands to return `Result<T, VaaniError>`.
@@ -0,0 +1,93 @@
+//! OS-level hotkey registration via `tauri-plugin-global-shortcut`.
+//!
+//! Unlike [`super::start_listener`] (which watches raw key press/release via
+//! `rdev` for in-process use), this module registers a real system-wide
+//! shortcut with the OS, so the configured hotkey fires even when Vaani's
+//! window isn't focused. The plugin's single global handler (installed once
+//! in `lib.rs`) emits the same `tray-toggle-recording` event the tray menu
+//! already uses, so both triggers drive the exact same code path.
+
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Shortcut};
+
+use crate::error::VaaniError;
+
+/// Parses a hotkey string (e.g. `"alt"`, `"ctrl"`) into the [`Shortcut`]
+/// accelerator registered with the OS.
+///
+/// Accepts the single-key strings [`super::parse_hotkey`] also recognizes,
+/// mapped to a bare key with no modifiers — unlike `parse_hotkey`, this does
+/// not accept `+`-joined chords: the configured hotkey is the key itself
+/// (push-to-talk style), not a modifier+key combo.
+pub fn parse_accelerator(hotkey: &str) -> Result<Shortcut, VaaniError> {
+    let code = match hotkey.trim().to_lowercase().as_str() {
+        "alt" => Code::AltLeft,
+        "ctrl" => Code::ControlLeft,
+        "shift" => Code::ShiftLeft,
+        "meta" | "cmd" => Code::MetaLeft,
+        other => return Err(VaaniError::Hotkey(format!("Unknown hotkey: {other}"))),
+    };
+    Ok(Shortcut::new(None, code))
+}
+
+/// Registers `hotkey` as the global shortcut, unregistering `previous` first
+/// if one was set.
+///
+/// Returns a [`VaaniError::Hotkey`] if `hotkey` doesn't parse or is already
+/// claimed by another application, so callers can reject the change (and
+/// leave the previous binding and persisted config untouched) rather than
+/// silently losing the shortcut.
+pub fn apply_hotkey(
+    app: &AppHandle,
+    previous: Option<&str>,
+    hotkey: &str,
+) -> Result<(), VaaniError> {
+    let shortcut = parse_accelerator(hotkey)?;
+
+    if let Some(previous) = previous {
+        match parse_accelerator(previous) {
+            Ok(previous_shortcut) => {
+                if let Err(e) = app.global_shortcut().unregister(previous_shortcut) {
+                    tracing::warn!(previous, "Failed to unregister previous hotkey: {e}");
+                }
+            }
+            Err(e) => tracing::warn!(previous, "Previous hotkey was already invalid: {e}"),
+        }
+    }
+
+    app.global_shortcut()
+        .register(shortcut)
+        .map_err(|e| VaaniError::Hotkey(format!("Failed to register hotkey '{hotkey}': {e}")))?;
+
+    tracing::info!(hotkey, "Global hotkey registered");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accelerator_alt() {
+        let shortcut = parse_accelerator("alt").expect("should parse 'alt'");
+        assert_eq!(shortcut, Shortcut::new(None, Code::AltLeft));
+    }
+
+    #[test]
+    fn parse_accelerator_case_insensitive() {
+        let shortcut = parse_accelerator("ALT").expect("should parse 'ALT'");
+        assert_eq!(shortcut, Shortcut::new(None, Code::AltLeft));
+    }
+
+    #[test]
+    fn parse_accelerator_cmd_alias() {
+        let shortcut = parse_accelerator("cmd").expect("should parse 'cmd'");
+        assert_eq!(shortcut, Shortcut::new(None, Code::MetaLeft));
+    }
+
+    #[test]
+    fn parse_accelerator_rejects_unknown_string() {
+        let err = parse_accelerator("invalid_key").unwrap_err();
+        assert!(err.to_string().contains("Unknown hotkey"));
+    }
+}
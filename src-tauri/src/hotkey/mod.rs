@@ -1,5 +1,7 @@
 pub mod macos;
+pub mod manager;
 
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
@@ -14,31 +16,58 @@ pub enum HotkeyEvent {
     Released,
 }
 
-/// Parses a hotkey string into the corresponding [`rdev::Key`].
+/// Parses a hotkey string into the [`rdev::Key`]s that make it up.
 ///
-/// Supports modifier key names (case-insensitive):
+/// A hotkey may be a single key or a `+`-joined chord (e.g.
+/// `"ctrl+shift+space"`); every member must be held at once for
+/// [`HotkeyEvent::Pressed`] to fire. Supports (case-insensitive):
 /// - `"alt"` -> [`Key::Alt`]
 /// - `"ctrl"` -> [`Key::ControlLeft`]
 /// - `"shift"` -> [`Key::ShiftLeft`]
 /// - `"meta"` / `"cmd"` -> [`Key::MetaLeft`]
+/// - `"space"` -> [`Key::Space`]
 ///
-/// Returns [`VaaniError::Hotkey`] for unrecognized strings.
-pub fn parse_hotkey(hotkey: &str) -> Result<Key, VaaniError> {
-    match hotkey.trim().to_lowercase().as_str() {
+/// Returns [`VaaniError::Hotkey`] for unrecognized strings, including an
+/// empty chord.
+pub fn parse_hotkey(hotkey: &str) -> Result<Vec<Key>, VaaniError> {
+    hotkey.split('+').map(parse_single_key).collect()
+}
+
+fn parse_single_key(part: &str) -> Result<Key, VaaniError> {
+    match part.trim().to_lowercase().as_str() {
         "alt" => Ok(Key::Alt),
         "ctrl" => Ok(Key::ControlLeft),
         "shift" => Ok(Key::ShiftLeft),
         "meta" | "cmd" => Ok(Key::MetaLeft),
+        "space" => Ok(Key::Space),
         other => Err(VaaniError::Hotkey(format!("Unknown hotkey: {other}"))),
     }
 }
 
-/// Starts a global hotkey listener that tracks press/release of the specified
-/// modifier key.
+/// Handle to a running hotkey listener, returned by [`start_listener`].
+///
+/// [`rdev::listen`] blocks its thread forever with no native way to
+/// unblock it, so `stop` doesn't kill the listener thread — it just gates
+/// the callback so no further [`HotkeyEvent`]s are delivered, which is
+/// enough for the app to cleanly rebind to a different hotkey.
+pub struct HotkeyHandle {
+    stop_flag: Arc<AtomicBool>,
+}
+
+impl HotkeyHandle {
+    /// Stops the listener from firing any further [`HotkeyEvent`]s.
+    pub fn stop(&self) {
+        self.stop_flag.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Starts a global hotkey listener that tracks press/release of the
+/// specified key or key chord (see [`parse_hotkey`]).
 ///
 /// The listener runs on a dedicated background thread because [`rdev::listen`]
 /// blocks indefinitely. The `callback` is invoked with [`HotkeyEvent::Pressed`]
-/// on key-down and [`HotkeyEvent::Released`] on key-up.
+/// once every member of the chord is held down, and with
+/// [`HotkeyEvent::Released`] as soon as any member is released.
 ///
 /// # Errors
 ///
@@ -48,30 +77,41 @@ pub fn parse_hotkey(hotkey: &str) -> Result<Key, VaaniError> {
 pub fn start_listener(
     hotkey: &str,
     callback: impl Fn(HotkeyEvent) + Send + 'static,
-) -> Result<(), VaaniError> {
-    let target_key = parse_hotkey(hotkey)?;
-    let is_pressed = Arc::new(AtomicBool::new(false));
+) -> Result<HotkeyHandle, VaaniError> {
+    let chord: HashSet<Key> = parse_hotkey(hotkey)?.into_iter().collect();
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let stop_flag_clone = Arc::clone(&stop_flag);
 
-    let is_pressed_clone = Arc::clone(&is_pressed);
-
-    tracing::info!(hotkey = %hotkey, key = ?target_key, "starting global hotkey listener");
+    tracing::info!(hotkey = %hotkey, ?chord, "starting global hotkey listener");
 
     std::thread::Builder::new()
         .name("vaani-hotkey-listener".into())
         .spawn(move || {
+            // Owned by the listener thread alone, so plain (non-atomic)
+            // state is enough — only the stop flag needs to be shared.
+            let mut held: HashSet<Key> = HashSet::new();
+            let mut chord_pressed = false;
+
             let handler = move |event: rdev::Event| {
+                if stop_flag_clone.load(Ordering::SeqCst) {
+                    return;
+                }
                 match event.event_type {
-                    EventType::KeyPress(key) if key == target_key => {
-                        // Only fire Pressed on the initial key-down, not on
-                        // auto-repeat (where is_pressed is already true).
-                        if !is_pressed_clone.swap(true, Ordering::SeqCst) {
-                            tracing::debug!(key = ?target_key, "hotkey pressed");
+                    EventType::KeyPress(key) if chord.contains(&key) => {
+                        held.insert(key);
+                        // Only fire Pressed once the whole chord is down,
+                        // and not again on auto-repeat.
+                        if held.len() == chord.len() && !chord_pressed {
+                            chord_pressed = true;
+                            tracing::debug!(?chord, "hotkey chord pressed");
                             callback(HotkeyEvent::Pressed);
                         }
                     }
-                    EventType::KeyRelease(key) if key == target_key => {
-                        if is_pressed_clone.swap(false, Ordering::SeqCst) {
-                            tracing::debug!(key = ?target_key, "hotkey released");
+                    EventType::KeyRelease(key) if chord.contains(&key) => {
+                        held.remove(&key);
+                        if chord_pressed {
+                            chord_pressed = false;
+                            tracing::debug!(?chord, "hotkey chord released");
                             callback(HotkeyEvent::Released);
                         }
                     }
@@ -85,7 +125,7 @@ pub fn start_listener(
         })
         .map_err(|e| VaaniError::Hotkey(format!("failed to spawn listener thread: {e}")))?;
 
-    Ok(())
+    Ok(HotkeyHandle { stop_flag })
 }
 
 #[cfg(test)]
@@ -97,8 +137,8 @@ mod tests {
     // -----------------------------------------------------------------------
     #[test]
     fn parse_hotkey_alt() {
-        let key = parse_hotkey("alt").expect("should parse 'alt'");
-        assert_eq!(key, Key::Alt);
+        let keys = parse_hotkey("alt").expect("should parse 'alt'");
+        assert_eq!(keys, vec![Key::Alt]);
     }
 
     // -----------------------------------------------------------------------
@@ -106,8 +146,8 @@ mod tests {
     // -----------------------------------------------------------------------
     #[test]
     fn parse_hotkey_case_insensitive() {
-        let key = parse_hotkey("ALT").expect("should parse 'ALT'");
-        assert_eq!(key, Key::Alt);
+        let keys = parse_hotkey("ALT").expect("should parse 'ALT'");
+        assert_eq!(keys, vec![Key::Alt]);
     }
 
     // -----------------------------------------------------------------------
@@ -115,8 +155,8 @@ mod tests {
     // -----------------------------------------------------------------------
     #[test]
     fn parse_hotkey_ctrl() {
-        let key = parse_hotkey("ctrl").expect("should parse 'ctrl'");
-        assert_eq!(key, Key::ControlLeft);
+        let keys = parse_hotkey("ctrl").expect("should parse 'ctrl'");
+        assert_eq!(keys, vec![Key::ControlLeft]);
     }
 
     // -----------------------------------------------------------------------
@@ -124,8 +164,8 @@ mod tests {
     // -----------------------------------------------------------------------
     #[test]
     fn parse_hotkey_shift() {
-        let key = parse_hotkey("shift").expect("should parse 'shift'");
-        assert_eq!(key, Key::ShiftLeft);
+        let keys = parse_hotkey("shift").expect("should parse 'shift'");
+        assert_eq!(keys, vec![Key::ShiftLeft]);
     }
 
     // -----------------------------------------------------------------------
@@ -133,8 +173,8 @@ mod tests {
     // -----------------------------------------------------------------------
     #[test]
     fn parse_hotkey_meta() {
-        let key = parse_hotkey("meta").expect("should parse 'meta'");
-        assert_eq!(key, Key::MetaLeft);
+        let keys = parse_hotkey("meta").expect("should parse 'meta'");
+        assert_eq!(keys, vec![Key::MetaLeft]);
     }
 
     // -----------------------------------------------------------------------
@@ -142,8 +182,8 @@ mod tests {
     // -----------------------------------------------------------------------
     #[test]
     fn parse_hotkey_cmd_alias() {
-        let key = parse_hotkey("cmd").expect("should parse 'cmd'");
-        assert_eq!(key, Key::MetaLeft);
+        let keys = parse_hotkey("cmd").expect("should parse 'cmd'");
+        assert_eq!(keys, vec![Key::MetaLeft]);
     }
 
     // -----------------------------------------------------------------------
@@ -179,4 +219,42 @@ mod tests {
     fn hotkey_event_variants_are_distinct() {
         assert_ne!(HotkeyEvent::Pressed, HotkeyEvent::Released);
     }
+
+    // -----------------------------------------------------------------------
+    // 10. parse_hotkey parses a multi-key chord in order
+    // -----------------------------------------------------------------------
+    #[test]
+    fn parse_hotkey_chord() {
+        let keys = parse_hotkey("ctrl+shift+space").expect("should parse chord");
+        assert_eq!(keys, vec![Key::ControlLeft, Key::ShiftLeft, Key::Space]);
+    }
+
+    // -----------------------------------------------------------------------
+    // 11. parse_hotkey chord is case-insensitive and trims whitespace
+    // -----------------------------------------------------------------------
+    #[test]
+    fn parse_hotkey_chord_trims_and_lowercases() {
+        let keys = parse_hotkey(" CTRL + Space ").expect("should parse chord");
+        assert_eq!(keys, vec![Key::ControlLeft, Key::Space]);
+    }
+
+    // -----------------------------------------------------------------------
+    // 12. parse_hotkey chord with an unknown member key errors
+    // -----------------------------------------------------------------------
+    #[test]
+    fn parse_hotkey_chord_with_unknown_key_errors() {
+        let err = parse_hotkey("ctrl+nope").unwrap_err();
+        assert!(err.to_string().contains("Unknown hotkey: nope"));
+    }
+
+    // -----------------------------------------------------------------------
+    // 13. start_listener returns a handle whose stop() doesn't panic, even
+    //     called more than once.
+    // -----------------------------------------------------------------------
+    #[test]
+    fn start_listener_handle_stop_is_idempotent() {
+        let handle = start_listener("ctrl", |_| {}).expect("should start listener");
+        handle.stop();
+        handle.stop();
+    }
 }
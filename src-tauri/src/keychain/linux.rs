@@ -0,0 +1,149 @@
+//! Linux secret storage using the freedesktop Secret Service (via D-Bus).
+//!
+//! Works with GNOME Keyring, KWallet's Secret Service shim, and similar.
+//! If no Secret Service is reachable (no D-Bus session, or no provider
+//! registered — common on headless boxes), [`SecretServiceKeyring::connect`]
+//! fails and the caller falls back to [`super::vault::EncryptedVault`].
+
+use secret_service::blocking::SecretService;
+use secret_service::EncryptionType;
+use std::collections::HashMap;
+use tracing::debug;
+
+use super::{SecretStorage, SERVICE_NAME};
+use crate::error::VaaniError;
+
+/// Attribute used to scope lookups to secrets stored by this app.
+const ATTR_SERVICE: &str = "service";
+/// Attribute holding the logical key name (e.g. `"openai_api_key"`).
+const ATTR_KEY: &str = "key";
+
+/// Secret Service-backed secret storage (GNOME Keyring, KWallet, etc).
+pub struct SecretServiceKeyring {
+    service: SecretService<'static>,
+}
+
+impl SecretServiceKeyring {
+    /// Connect to the session's Secret Service over D-Bus. Fails if no
+    /// session bus or Secret Service provider is available.
+    pub fn connect() -> Result<Self, VaaniError> {
+        let service = SecretService::connect(EncryptionType::Dh)
+            .map_err(|e| VaaniError::Keychain(format!("Failed to connect to Secret Service: {e}")))?;
+        Ok(Self { service })
+    }
+
+    fn attributes(key: &str) -> HashMap<&str, &str> {
+        HashMap::from([(ATTR_SERVICE, SERVICE_NAME), (ATTR_KEY, key)])
+    }
+}
+
+impl SecretStorage for SecretServiceKeyring {
+    fn set(&self, key: &str, value: &str) -> Result<(), VaaniError> {
+        let collection = self
+            .service
+            .get_default_collection()
+            .map_err(|e| VaaniError::Keychain(format!("Failed to open default keyring collection: {e}")))?;
+
+        collection
+            .create_item(
+                &format!("{SERVICE_NAME}: {key}"),
+                Self::attributes(key),
+                value.as_bytes(),
+                true, // replace an existing item with the same attributes
+                "text/plain",
+            )
+            .map_err(|e| VaaniError::Keychain(format!("Failed to store secret '{key}': {e}")))?;
+
+        debug!(key = key, "Secret stored in Secret Service");
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>, VaaniError> {
+        let items = self
+            .service
+            .search_items(Self::attributes(key))
+            .map_err(|e| VaaniError::Keychain(format!("Failed to search Secret Service: {e}")))?;
+
+        let Some(item) = items.unlocked.first() else {
+            debug!(key = key, "Secret not found in Secret Service");
+            return Ok(None);
+        };
+
+        let bytes = item
+            .get_secret()
+            .map_err(|e| VaaniError::Keychain(format!("Failed to retrieve secret '{key}': {e}")))?;
+
+        let value = String::from_utf8(bytes)
+            .map_err(|e| VaaniError::Keychain(format!("Invalid UTF-8 in secret '{key}': {e}")))?;
+
+        debug!(key = key, "Secret retrieved from Secret Service");
+        Ok(Some(value))
+    }
+
+    fn delete(&self, key: &str) -> Result<(), VaaniError> {
+        let items = self
+            .service
+            .search_items(Self::attributes(key))
+            .map_err(|e| VaaniError::Keychain(format!("Failed to search Secret Service: {e}")))?;
+
+        for item in items.unlocked.iter().chain(items.locked.iter()) {
+            item.delete()
+                .map_err(|e| VaaniError::Keychain(format!("Failed to delete secret '{key}': {e}")))?;
+        }
+
+        debug!(key = key, "Secret deleted from Secret Service");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod tests {
+    use super::*;
+
+    const TEST_KEY_PREFIX: &str = "vaani_test_";
+
+    fn test_key(suffix: &str) -> String {
+        format!("{TEST_KEY_PREFIX}{suffix}")
+    }
+
+    /// These tests require a running Secret Service (e.g. `gnome-keyring-daemon`
+    /// or a D-Bus session bus); they're skipped rather than failed when one
+    /// isn't reachable, since CI/build machines often lack both.
+    fn connect_or_skip() -> Option<SecretServiceKeyring> {
+        SecretServiceKeyring::connect().ok()
+    }
+
+    #[test]
+    fn secret_service_roundtrip() {
+        let Some(kc) = connect_or_skip() else { return };
+        let key = test_key("roundtrip");
+
+        let _ = kc.delete(&key);
+        kc.set(&key, "test_secret_value").expect("set should succeed");
+
+        let value = kc.get(&key).expect("get should succeed");
+        assert_eq!(value, Some("test_secret_value".to_string()));
+
+        kc.delete(&key).expect("delete should succeed");
+        assert!(kc.get(&key).expect("get after delete").is_none());
+    }
+
+    #[test]
+    fn secret_service_get_nonexistent_returns_none() {
+        let Some(kc) = connect_or_skip() else { return };
+        let key = test_key("nonexistent");
+
+        let _ = kc.delete(&key);
+        assert!(kc.get(&key).expect("get should succeed").is_none());
+    }
+
+    #[test]
+    fn secret_service_delete_nonexistent_is_ok() {
+        let Some(kc) = connect_or_skip() else { return };
+        let key = test_key("delete_missing");
+
+        let _ = kc.delete(&key);
+        kc.delete(&key).expect("delete of non-existent key should succeed");
+    }
+}
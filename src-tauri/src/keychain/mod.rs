@@ -1,8 +1,16 @@
 //! Secure secret storage using the system keychain.
 //!
 //! On macOS, secrets are stored in the system Keychain via `security-framework`.
-//! On other platforms, a stub implementation returns errors guiding the user
-//! to set environment variables.
+//! On Windows, they're stored via the Credential Manager. On Linux, they're
+//! stored via the freedesktop Secret Service (D-Bus) when one is reachable,
+//! falling back to [`vault::EncryptedVault`] otherwise. On any other
+//! platform, a stub implementation returns errors guiding the user to set
+//! environment variables.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tracing::warn;
 
 use crate::error::VaaniError;
 
@@ -28,18 +36,48 @@ pub fn create_secret_storage() -> Box<dyn SecretStorage> {
         Box::new(macos::MacKeychain)
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "windows")]
+    {
+        Box::new(windows::WindowsCredentialManager)
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        match linux::SecretServiceKeyring::connect() {
+            Ok(keyring) => Box::new(keyring),
+            Err(e) => {
+                warn!("No Secret Service available ({e}), falling back to encrypted vault");
+                match vault::EncryptedVault::from_env(vault::default_vault_path()) {
+                    Ok(v) => Box::new(v),
+                    Err(e) => {
+                        warn!("Encrypted vault unavailable ({e}), falling back to environment variables");
+                        Box::new(StubStorage)
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     {
         Box::new(StubStorage)
     }
 }
 
-/// Stub storage for platforms without keychain support.
+/// Stub storage used when no other backend is available: on platforms with
+/// no keychain integration, and on Linux when neither the Secret Service
+/// nor the encrypted vault fallback could be set up.
 /// Guides the user to use environment variables instead.
-#[cfg(any(not(target_os = "macos"), test))]
+#[cfg(any(
+    not(any(target_os = "macos", target_os = "windows")),
+    test
+))]
 struct StubStorage;
 
-#[cfg(any(not(target_os = "macos"), test))]
+#[cfg(any(
+    not(any(target_os = "macos", target_os = "windows")),
+    test
+))]
 impl SecretStorage for StubStorage {
     fn set(&self, _key: &str, _value: &str) -> Result<(), VaaniError> {
         Err(VaaniError::Keychain(
@@ -56,9 +94,59 @@ impl SecretStorage for StubStorage {
     }
 }
 
+/// In-memory `SecretStorage` test double, so callers can exercise the trait
+/// — including a real set/get/delete round trip, unlike [`StubStorage`] —
+/// without touching the OS keystore.
+#[derive(Default)]
+pub struct InMemoryStorage {
+    secrets: Mutex<HashMap<String, String>>,
+}
+
+impl InMemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SecretStorage for InMemoryStorage {
+    fn set(&self, key: &str, value: &str) -> Result<(), VaaniError> {
+        self.secrets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>, VaaniError> {
+        Ok(self
+            .secrets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .cloned())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), VaaniError> {
+        self.secrets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key);
+        Ok(())
+    }
+}
+
 #[cfg(target_os = "macos")]
 pub mod macos;
 
+#[cfg(target_os = "linux")]
+pub mod linux;
+
+#[cfg(target_os = "windows")]
+pub mod windows;
+
+#[cfg(target_os = "linux")]
+pub mod vault;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,4 +180,45 @@ mod tests {
         assert!(!SERVICE_NAME.is_empty());
         assert!(SERVICE_NAME.contains("vaani"));
     }
+
+    #[test]
+    fn in_memory_storage_get_missing_key_returns_none() {
+        let storage = InMemoryStorage::new();
+        assert_eq!(storage.get("missing").expect("get should not error"), None);
+    }
+
+    #[test]
+    fn in_memory_storage_round_trips_a_secret() {
+        let storage = InMemoryStorage::new();
+        storage.set("openai_api_key", "sk-test").expect("set should succeed");
+        assert_eq!(
+            storage.get("openai_api_key").expect("get should not error"),
+            Some("sk-test".to_string())
+        );
+    }
+
+    #[test]
+    fn in_memory_storage_set_overwrites_existing_value() {
+        let storage = InMemoryStorage::new();
+        storage.set("key", "first").expect("set should succeed");
+        storage.set("key", "second").expect("set should succeed");
+        assert_eq!(
+            storage.get("key").expect("get should not error"),
+            Some("second".to_string())
+        );
+    }
+
+    #[test]
+    fn in_memory_storage_delete_removes_the_secret() {
+        let storage = InMemoryStorage::new();
+        storage.set("key", "value").expect("set should succeed");
+        storage.delete("key").expect("delete should succeed");
+        assert_eq!(storage.get("key").expect("get should not error"), None);
+    }
+
+    #[test]
+    fn in_memory_storage_delete_of_missing_key_is_ok() {
+        let storage = InMemoryStorage::new();
+        assert!(storage.delete("never-existed").is_ok());
+    }
 }
@@ -0,0 +1,339 @@
+//! AES-encrypted on-disk vault, used when no OS secret service is reachable
+//! (e.g. headless Linux without a running keyring daemon).
+//!
+//! Secrets are encrypted individually with AES-256-GCM using the same
+//! nonce-prefixed scheme as [`crate::storage`]'s history encryption, and
+//! stored as a small JSON map on disk. The encryption key is derived from a
+//! user-supplied passphrase read from `VAANI_VAULT_PASSPHRASE` — there is no
+//! way to prompt for a passphrase from this trait's synchronous, parameterless
+//! methods, so the environment variable is the escape hatch, matching the
+//! guidance [`super::StubStorage`] already gives other unsupported platforms.
+//!
+//! The key itself is derived with Argon2id against a random salt persisted
+//! in [`VaultFile`], the same way [`crate::storage::HistoryStore`] persists
+//! its salt in `store_meta`. The salt is generated (and the file written)
+//! on first use, so every later open against the same file reproduces the
+//! same key.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use aes_gcm::aead::generic_array::typenum;
+use aes_gcm::aead::generic_array::GenericArray;
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::SecretStorage;
+use crate::error::VaaniError;
+use crate::storage::{derive_key_argon2, random_salt, Argon2Params};
+
+/// Environment variable holding the passphrase used to derive the vault's
+/// encryption key.
+pub const VAULT_PASSPHRASE_ENV_VAR: &str = "VAANI_VAULT_PASSPHRASE";
+
+/// Size of AES-256-GCM nonce in bytes.
+const NONCE_SIZE: usize = 12;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct VaultFile {
+    /// Random salt used to derive the AES-256-GCM key via Argon2id,
+    /// generated once on first use and persisted so later opens against
+    /// this file derive the same key. Empty for vault files written before
+    /// this field existed.
+    #[serde(default)]
+    salt: Vec<u8>,
+
+    /// Maps a secret's key to `base64(nonce || ciphertext || tag)`.
+    #[serde(default)]
+    entries: HashMap<String, String>,
+}
+
+/// AES-256-GCM-encrypted on-disk fallback for [`SecretStorage`].
+pub struct EncryptedVault {
+    path: PathBuf,
+    key: GenericArray<u8, typenum::U32>,
+}
+
+impl EncryptedVault {
+    /// Create a vault backed by the file at `path`, deriving its encryption
+    /// key from `passphrase` via Argon2id.
+    ///
+    /// If `path` already holds a vault file, its persisted salt is reused
+    /// so the derived key matches whatever was used to encrypt its
+    /// existing entries. Otherwise a fresh random salt is generated and
+    /// written to `path` immediately (with no entries yet), so a second
+    /// process opening the same path derives the same key even before the
+    /// first `set`.
+    pub fn new(path: PathBuf, passphrase: &str) -> Result<Self, VaaniError> {
+        let mut file = Self::load_file(&path)?;
+
+        if file.salt.is_empty() {
+            file.salt = random_salt().to_vec();
+            Self::save_file(&path, &file)?;
+        }
+
+        let key = derive_key_argon2(passphrase, &file.salt, &Argon2Params::CURRENT)?;
+
+        Ok(Self {
+            path,
+            key: *GenericArray::from_slice(&key),
+        })
+    }
+
+    /// Create a vault reading its passphrase from
+    /// [`VAULT_PASSPHRASE_ENV_VAR`]. Returns an error (rather than falling
+    /// back to an empty/default key) if the variable isn't set, since a
+    /// missing passphrase would otherwise silently produce a predictable,
+    /// insecure key.
+    pub fn from_env(path: PathBuf) -> Result<Self, VaaniError> {
+        let passphrase = std::env::var(VAULT_PASSPHRASE_ENV_VAR).map_err(|_| {
+            VaaniError::Keychain(format!(
+                "No OS secret service is available, and ${VAULT_PASSPHRASE_ENV_VAR} is not set. \
+                 Set it to a passphrase to use the encrypted fallback vault."
+            ))
+        })?;
+        Self::new(path, &passphrase)
+    }
+
+    fn load_file(path: &PathBuf) -> Result<VaultFile, VaaniError> {
+        if !path.exists() {
+            return Ok(VaultFile::default());
+        }
+
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| VaaniError::Keychain(format!("Failed to read vault file: {e}")))?;
+
+        serde_json::from_str(&contents)
+            .map_err(|e| VaaniError::Keychain(format!("Failed to parse vault file: {e}")))
+    }
+
+    fn save_file(path: &PathBuf, file: &VaultFile) -> Result<(), VaaniError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| VaaniError::Keychain(format!("Failed to create vault directory: {e}")))?;
+        }
+
+        let contents = serde_json::to_string(file)
+            .map_err(|e| VaaniError::Keychain(format!("Failed to serialize vault file: {e}")))?;
+
+        std::fs::write(path, contents)
+            .map_err(|e| VaaniError::Keychain(format!("Failed to write vault file: {e}")))
+    }
+
+    fn load(&self) -> Result<VaultFile, VaaniError> {
+        Self::load_file(&self.path)
+    }
+
+    fn save(&self, file: &VaultFile) -> Result<(), VaaniError> {
+        Self::save_file(&self.path, file)
+    }
+
+    fn encrypt(&self, plaintext: &str) -> Result<String, VaaniError> {
+        let cipher = Aes256Gcm::new(&self.key);
+
+        let mut nonce_bytes = [0u8; NONCE_SIZE];
+        rand::thread_rng().fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+
+        let ciphertext = cipher
+            .encrypt(nonce, plaintext.as_bytes())
+            .map_err(|e| VaaniError::Keychain(format!("Vault encryption failed: {e}")))?;
+
+        let mut combined = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+        combined.extend_from_slice(&nonce_bytes);
+        combined.extend_from_slice(&ciphertext);
+
+        Ok(BASE64.encode(&combined))
+    }
+
+    fn decrypt(&self, ciphertext_b64: &str) -> Result<String, VaaniError> {
+        let combined = BASE64
+            .decode(ciphertext_b64)
+            .map_err(|e| VaaniError::Keychain(format!("Vault base64 decode failed: {e}")))?;
+
+        if combined.len() < NONCE_SIZE + 1 {
+            return Err(VaaniError::Keychain("Vault entry is too short".to_string()));
+        }
+
+        let (nonce_bytes, ciphertext) = combined.split_at(NONCE_SIZE);
+        let nonce = Nonce::from_slice(nonce_bytes);
+        let cipher = Aes256Gcm::new(&self.key);
+
+        let plaintext = cipher.decrypt(nonce, ciphertext).map_err(|e| {
+            VaaniError::Keychain(format!(
+                "Vault decryption failed (wrong passphrase, or corrupted vault): {e}"
+            ))
+        })?;
+
+        String::from_utf8(plaintext)
+            .map_err(|e| VaaniError::Keychain(format!("Decrypted vault entry is not valid UTF-8: {e}")))
+    }
+}
+
+impl SecretStorage for EncryptedVault {
+    fn set(&self, key: &str, value: &str) -> Result<(), VaaniError> {
+        let mut file = self.load()?;
+        file.entries.insert(key.to_string(), self.encrypt(value)?);
+        self.save(&file)?;
+
+        debug!(key, path = %self.path.display(), "Secret stored in encrypted vault");
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>, VaaniError> {
+        let file = self.load()?;
+        match file.entries.get(key) {
+            Some(ciphertext) => Ok(Some(self.decrypt(ciphertext)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn delete(&self, key: &str) -> Result<(), VaaniError> {
+        let mut file = self.load()?;
+        if file.entries.remove(key).is_some() {
+            self.save(&file)?;
+            debug!(key, "Secret deleted from encrypted vault");
+        }
+        Ok(())
+    }
+}
+
+/// Default path for the fallback vault: `~/.vaani/secrets.vault`.
+pub fn default_vault_path() -> PathBuf {
+    crate::config::config_dir().join("secrets.vault")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn test_vault(dir: &Path) -> EncryptedVault {
+        EncryptedVault::new(dir.join("secrets.vault"), "test-passphrase").expect("create vault")
+    }
+
+    #[test]
+    fn roundtrip_set_and_get() {
+        let dir = TempDir::new().expect("temp dir");
+        let vault = test_vault(dir.path());
+
+        vault.set("openai_api_key", "sk-test-123").expect("set");
+        let value = vault.get("openai_api_key").expect("get");
+        assert_eq!(value, Some("sk-test-123".to_string()));
+    }
+
+    #[test]
+    fn get_missing_key_returns_none() {
+        let dir = TempDir::new().expect("temp dir");
+        let vault = test_vault(dir.path());
+
+        assert_eq!(vault.get("nonexistent").expect("get"), None);
+    }
+
+    #[test]
+    fn delete_removes_entry() {
+        let dir = TempDir::new().expect("temp dir");
+        let vault = test_vault(dir.path());
+
+        vault.set("key", "value").expect("set");
+        vault.delete("key").expect("delete");
+        assert_eq!(vault.get("key").expect("get"), None);
+    }
+
+    #[test]
+    fn delete_nonexistent_key_is_ok() {
+        let dir = TempDir::new().expect("temp dir");
+        let vault = test_vault(dir.path());
+
+        vault.delete("nonexistent").expect("delete should not fail");
+    }
+
+    #[test]
+    fn vault_file_on_disk_is_not_plaintext() {
+        let dir = TempDir::new().expect("temp dir");
+        let vault = test_vault(dir.path());
+
+        let secret = "super-secret-api-key";
+        vault.set("key", secret).expect("set");
+
+        let raw = std::fs::read_to_string(dir.path().join("secrets.vault")).expect("read vault file");
+        assert!(!raw.contains(secret));
+    }
+
+    #[test]
+    fn wrong_passphrase_fails_to_decrypt() {
+        let dir = TempDir::new().expect("temp dir");
+        let vault_path = dir.path().join("secrets.vault");
+
+        let vault_a =
+            EncryptedVault::new(vault_path.clone(), "passphrase-a").expect("create vault a");
+        vault_a.set("key", "value").expect("set");
+
+        let vault_b = EncryptedVault::new(vault_path, "passphrase-b").expect("create vault b");
+        assert!(vault_b.get("key").is_err());
+    }
+
+    #[test]
+    fn multiple_keys_coexist() {
+        let dir = TempDir::new().expect("temp dir");
+        let vault = test_vault(dir.path());
+
+        vault.set("openai_api_key", "sk-one").expect("set 1");
+        vault.set("anthropic_api_key", "sk-two").expect("set 2");
+
+        assert_eq!(vault.get("openai_api_key").expect("get 1"), Some("sk-one".to_string()));
+        assert_eq!(vault.get("anthropic_api_key").expect("get 2"), Some("sk-two".to_string()));
+    }
+
+    #[test]
+    fn salt_is_persisted_and_reused_across_opens() {
+        let dir = TempDir::new().expect("temp dir");
+        let vault_path = dir.path().join("secrets.vault");
+
+        let vault_a =
+            EncryptedVault::new(vault_path.clone(), "test-passphrase").expect("create vault a");
+        vault_a.set("key", "value").expect("set");
+
+        // A second open with the same path and passphrase must derive the
+        // same key (i.e. reuse the persisted salt), or it couldn't decrypt
+        // what the first open wrote.
+        let vault_b =
+            EncryptedVault::new(vault_path, "test-passphrase").expect("create vault b");
+        assert_eq!(vault_b.get("key").expect("get"), Some("value".to_string()));
+    }
+
+    #[test]
+    fn vault_file_persists_a_nonempty_salt_before_any_secret_is_set() {
+        let dir = TempDir::new().expect("temp dir");
+        let vault_path = dir.path().join("secrets.vault");
+
+        let _vault = EncryptedVault::new(vault_path.clone(), "test-passphrase").expect("create vault");
+
+        let raw = std::fs::read_to_string(&vault_path).expect("read vault file");
+        let file: VaultFile = serde_json::from_str(&raw).expect("parse vault file");
+        assert!(!file.salt.is_empty());
+    }
+
+    #[test]
+    fn from_env_errors_when_passphrase_not_set() {
+        // SAFETY-equivalent: just ensure the var is unset for this check;
+        // we don't mutate global env state for other tests.
+        if std::env::var(VAULT_PASSPHRASE_ENV_VAR).is_ok() {
+            // If the CI/dev environment happens to have this set, skip rather
+            // than risk flaking on shared env state.
+            return;
+        }
+        let result = EncryptedVault::from_env(PathBuf::from("/tmp/unused.vault"));
+        let err = match result {
+            Ok(_) => panic!("expected an error when passphrase env var is unset"),
+            Err(e) => e,
+        };
+        assert!(err.to_string().contains("VAANI_VAULT_PASSPHRASE"));
+    }
+}
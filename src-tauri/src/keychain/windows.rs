@@ -0,0 +1,155 @@
+//! Windows secret storage using the Credential Manager.
+//!
+//! Secrets are stored as generic credentials scoped to a target name of
+//! `"{SERVICE_NAME}/{key}"`, mirroring how [`super::macos::MacKeychain`]
+//! scopes Keychain entries by service name.
+
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
+use std::ptr;
+
+use tracing::debug;
+use windows::core::PWSTR;
+use windows::Win32::Foundation::{ERROR_NOT_FOUND, FILETIME};
+use windows::Win32::Security::Credentials::{
+    CredDeleteW, CredFree, CredReadW, CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE,
+    CRED_TYPE_GENERIC,
+};
+
+use super::{SecretStorage, SERVICE_NAME};
+use crate::error::VaaniError;
+
+/// Windows Credential Manager-backed secret storage.
+pub struct WindowsCredentialManager;
+
+fn target_name(key: &str) -> Vec<u16> {
+    wide_string(&format!("{SERVICE_NAME}/{key}"))
+}
+
+fn wide_string(s: &str) -> Vec<u16> {
+    OsStr::new(s).encode_wide().chain(std::iter::once(0)).collect()
+}
+
+impl SecretStorage for WindowsCredentialManager {
+    fn set(&self, key: &str, value: &str) -> Result<(), VaaniError> {
+        let mut target = target_name(key);
+        let mut blob = value.as_bytes().to_vec();
+
+        let credential = CREDENTIALW {
+            Flags: 0,
+            Type: CRED_TYPE_GENERIC,
+            TargetName: PWSTR(target.as_mut_ptr()),
+            Comment: PWSTR::null(),
+            LastWritten: FILETIME::default(),
+            CredentialBlobSize: blob.len() as u32,
+            CredentialBlob: blob.as_mut_ptr(),
+            Persist: CRED_PERSIST_LOCAL_MACHINE,
+            AttributeCount: 0,
+            Attributes: ptr::null_mut(),
+            TargetAlias: PWSTR::null(),
+            UserName: PWSTR::null(),
+        };
+
+        unsafe { CredWriteW(&credential, 0) }
+            .map_err(|e| VaaniError::Keychain(format!("Failed to store secret '{key}': {e}")))?;
+
+        debug!(key = key, "Secret stored in Credential Manager");
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>, VaaniError> {
+        let target = target_name(key);
+        let mut credential: *mut CREDENTIALW = ptr::null_mut();
+
+        let result = unsafe { CredReadW(PWSTR(target.as_ptr() as *mut u16), CRED_TYPE_GENERIC.0, 0, &mut credential) };
+
+        match result {
+            Ok(()) => {
+                let value = unsafe {
+                    let cred = &*credential;
+                    let blob =
+                        std::slice::from_raw_parts(cred.CredentialBlob, cred.CredentialBlobSize as usize);
+                    let value = String::from_utf8(blob.to_vec()).map_err(|e| {
+                        VaaniError::Keychain(format!("Invalid UTF-8 in secret '{key}': {e}"))
+                    });
+                    CredFree(credential as *const _);
+                    value?
+                };
+                debug!(key = key, "Secret retrieved from Credential Manager");
+                Ok(Some(value))
+            }
+            Err(e) if e.code() == ERROR_NOT_FOUND.to_hresult() => {
+                debug!(key = key, "Secret not found in Credential Manager");
+                Ok(None)
+            }
+            Err(e) => Err(VaaniError::Keychain(format!(
+                "Failed to retrieve secret '{key}': {e}"
+            ))),
+        }
+    }
+
+    fn delete(&self, key: &str) -> Result<(), VaaniError> {
+        let target = target_name(key);
+
+        match unsafe { CredDeleteW(PWSTR(target.as_ptr() as *mut u16), CRED_TYPE_GENERIC.0, 0) } {
+            Ok(()) => {
+                debug!(key = key, "Secret deleted from Credential Manager");
+                Ok(())
+            }
+            Err(e) if e.code() == ERROR_NOT_FOUND.to_hresult() => Ok(()),
+            Err(e) => Err(VaaniError::Keychain(format!(
+                "Failed to delete secret '{key}': {e}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_os = "windows")]
+mod tests {
+    use super::*;
+
+    const TEST_KEY_PREFIX: &str = "vaani_test_";
+
+    fn test_key(suffix: &str) -> String {
+        format!("{TEST_KEY_PREFIX}{suffix}")
+    }
+
+    fn cleanup(key: &str) {
+        let cm = WindowsCredentialManager;
+        let _ = cm.delete(key);
+    }
+
+    #[test]
+    fn credential_manager_roundtrip() {
+        let key = test_key("roundtrip");
+        let cm = WindowsCredentialManager;
+
+        cleanup(&key);
+        cm.set(&key, "test_secret_value").expect("set should succeed");
+
+        let value = cm.get(&key).expect("get should succeed");
+        assert_eq!(value, Some("test_secret_value".to_string()));
+
+        cm.delete(&key).expect("delete should succeed");
+        assert!(cm.get(&key).expect("get after delete").is_none());
+    }
+
+    #[test]
+    fn credential_manager_get_nonexistent_returns_none() {
+        let key = test_key("nonexistent");
+        let cm = WindowsCredentialManager;
+
+        cleanup(&key);
+        assert!(cm.get(&key).expect("get should succeed").is_none());
+    }
+
+    #[test]
+    fn credential_manager_delete_nonexistent_is_ok() {
+        let key = test_key("delete_missing");
+        let cm = WindowsCredentialManager;
+
+        cleanup(&key);
+        cm.delete(&key).expect("delete of non-existent key should succeed");
+    }
+}
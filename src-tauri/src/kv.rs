@@ -0,0 +1,204 @@
+//! Generic encrypted key-value store, sharing its connection and
+//! [`EncryptionCipher`] with [`crate::storage::HistoryStore`] via
+//! [`crate::storage::HistoryStore::kv_store`].
+//!
+//! Namespaced `(namespace, key) -> value` pairs, for small app state that
+//! doesn't belong in the history table — last-used mode, cached
+//! enhancement prompts, per-mode preferences — protected by the same
+//! AES-256-GCM key as history text.
+
+use std::rc::Rc;
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::crypto::EncryptionCipher;
+use crate::error::VaaniError;
+
+/// Encrypted namespaced key-value store backed by the same SQLite
+/// connection as a [`crate::storage::HistoryStore`].
+pub struct KvStore {
+    conn: Rc<Connection>,
+    cipher: Rc<EncryptionCipher>,
+}
+
+impl KvStore {
+    /// Wrap an existing connection and cipher, creating the `kv` table if
+    /// it doesn't exist yet.
+    ///
+    /// Constructed via [`crate::storage::HistoryStore::kv_store`] rather
+    /// than directly, so callers always share the history store's
+    /// connection and key instead of deriving their own.
+    pub(crate) fn new(conn: Rc<Connection>, cipher: Rc<EncryptionCipher>) -> Result<Self, VaaniError> {
+        create_schema(&conn)?;
+        Ok(Self { conn, cipher })
+    }
+
+    /// Set `key` to `value` within `namespace`, overwriting any existing
+    /// value. `value` is encrypted before storage.
+    pub fn set(&self, namespace: &str, key: &str, value: &str) -> Result<(), VaaniError> {
+        let encrypted = self.cipher.encrypt(value)?;
+        self.conn
+            .execute(
+                "INSERT INTO kv (namespace, key, value, updated_at)
+                 VALUES (?1, ?2, ?3, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+                 ON CONFLICT(namespace, key) DO UPDATE SET
+                     value = excluded.value,
+                     updated_at = excluded.updated_at",
+                params![namespace, key, encrypted],
+            )
+            .map_err(|e| VaaniError::Storage(format!("kv set failed: {e}")))?;
+        Ok(())
+    }
+
+    /// Get the value for `key` within `namespace`, or `None` if it isn't
+    /// set. The stored value is decrypted before returning.
+    pub fn get(&self, namespace: &str, key: &str) -> Result<Option<String>, VaaniError> {
+        let encrypted: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT value FROM kv WHERE namespace = ?1 AND key = ?2",
+                params![namespace, key],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| VaaniError::Storage(format!("kv get failed: {e}")))?;
+
+        match encrypted {
+            Some(enc) => Ok(Some(self.cipher.decrypt(&enc)?.expose_secret().to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Delete `key` within `namespace`, if it exists.
+    pub fn delete(&self, namespace: &str, key: &str) -> Result<(), VaaniError> {
+        self.conn
+            .execute(
+                "DELETE FROM kv WHERE namespace = ?1 AND key = ?2",
+                params![namespace, key],
+            )
+            .map_err(|e| VaaniError::Storage(format!("kv delete failed: {e}")))?;
+        Ok(())
+    }
+
+    /// List every `(key, value)` pair stored within `namespace`, values
+    /// decrypted.
+    pub fn list(&self, namespace: &str) -> Result<Vec<(String, String)>, VaaniError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT key, value FROM kv WHERE namespace = ?1")
+            .map_err(|e| VaaniError::Storage(format!("kv query prepare failed: {e}")))?;
+
+        let rows = stmt
+            .query_map(params![namespace], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| VaaniError::Storage(format!("kv query failed: {e}")))?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (key, encrypted) =
+                row.map_err(|e| VaaniError::Storage(format!("kv row read failed: {e}")))?;
+            let value = self.cipher.decrypt(&encrypted)?.expose_secret().to_string();
+            entries.push((key, value));
+        }
+        Ok(entries)
+    }
+}
+
+/// Create the `kv` table if it doesn't exist.
+fn create_schema(conn: &Connection) -> Result<(), VaaniError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS kv (
+             namespace  TEXT NOT NULL,
+             key        TEXT NOT NULL,
+             value      BLOB NOT NULL,
+             updated_at TEXT NOT NULL,
+             PRIMARY KEY (namespace, key)
+         );",
+    )
+    .map_err(|e| VaaniError::Storage(format!("kv schema creation failed: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::HistoryStore;
+    use tempfile::TempDir;
+
+    fn test_kv_store() -> (KvStore, TempDir) {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let db_path = dir.path().join("history.db");
+        let history = HistoryStore::open(&db_path, "kv-test-passphrase").expect("open store");
+        let kv = history.kv_store().expect("kv_store");
+        (kv, dir)
+    }
+
+    #[test]
+    fn set_then_get_round_trips() {
+        let (kv, _dir) = test_kv_store();
+        kv.set("prefs", "last_mode", "professional").expect("set");
+
+        let value = kv.get("prefs", "last_mode").expect("get");
+        assert_eq!(value.as_deref(), Some("professional"));
+    }
+
+    #[test]
+    fn get_returns_none_for_missing_key() {
+        let (kv, _dir) = test_kv_store();
+        assert_eq!(kv.get("prefs", "missing").expect("get"), None);
+    }
+
+    #[test]
+    fn set_overwrites_an_existing_value() {
+        let (kv, _dir) = test_kv_store();
+        kv.set("prefs", "last_mode", "professional").expect("set 1");
+        kv.set("prefs", "last_mode", "casual").expect("set 2");
+
+        assert_eq!(
+            kv.get("prefs", "last_mode").expect("get"),
+            Some("casual".to_string())
+        );
+    }
+
+    #[test]
+    fn delete_removes_the_key() {
+        let (kv, _dir) = test_kv_store();
+        kv.set("prefs", "last_mode", "professional").expect("set");
+        kv.delete("prefs", "last_mode").expect("delete");
+
+        assert_eq!(kv.get("prefs", "last_mode").expect("get"), None);
+    }
+
+    #[test]
+    fn list_returns_only_entries_in_the_given_namespace() {
+        let (kv, _dir) = test_kv_store();
+        kv.set("prefs", "a", "1").expect("set a");
+        kv.set("prefs", "b", "2").expect("set b");
+        kv.set("other", "c", "3").expect("set c");
+
+        let mut entries = kv.list("prefs").expect("list");
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![("a".to_string(), "1".to_string()), ("b".to_string(), "2".to_string())]
+        );
+    }
+
+    #[test]
+    fn kv_store_shares_the_history_store_connection_and_key() {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let db_path = dir.path().join("history.db");
+        let history = HistoryStore::open(&db_path, "shared-passphrase").expect("open store");
+        let kv = history.kv_store().expect("kv_store");
+
+        kv.set("prefs", "last_mode", "professional").expect("set");
+
+        // Re-deriving a second KvStore handle from the same store should
+        // see the value written through the first.
+        let kv2 = history.kv_store().expect("kv_store again");
+        assert_eq!(
+            kv2.get("prefs", "last_mode").expect("get"),
+            Some("professional".to_string())
+        );
+    }
+}
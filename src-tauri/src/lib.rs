@@ -7,23 +7,35 @@ pub mod app;
 pub mod audio;
 pub mod commands;
 pub mod config;
+pub mod crypto;
 pub mod enhance;
 pub mod error;
 pub mod hotkey;
 pub mod keychain;
+pub mod kv;
 pub mod output;
+pub mod permissions;
 pub mod prompts;
 pub mod sounds;
 pub mod state;
 pub mod storage;
+pub mod sync;
 pub mod transcribe;
 pub mod tray;
+pub mod tts;
 pub mod updater;
 
 use app::VaaniApp;
+use commands::PermissionsStatus;
 use config::load_config;
+use state::AppState;
 use std::sync::Arc;
-use tauri::Manager;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+/// How often the background permissions poll re-queries microphone and
+/// Accessibility status while the app is running.
+const PERMISSIONS_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 /// Tauri entry point — called from main.rs.
 ///
@@ -47,6 +59,17 @@ pub fn run() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, _shortcut, event| {
+                    if event.state() == tauri_plugin_global_shortcut::ShortcutState::Pressed {
+                        if let Err(e) = app.emit("tray-toggle-recording", ()) {
+                            tracing::error!("Failed to emit toggle event: {e}");
+                        }
+                    }
+                })
+                .build(),
+        )
         .manage(vaani)
         .invoke_handler(tauri::generate_handler![
             commands::get_config,
@@ -54,11 +77,14 @@ pub fn run() {
             commands::get_api_keys_status,
             commands::set_api_key,
             commands::list_microphones,
+            commands::get_default_microphone,
             commands::start_mic_test,
             commands::get_mic_level,
             commands::stop_mic_test,
             commands::get_hotkey,
             commands::set_hotkey,
+            commands::set_mic_sensitivity,
+            commands::set_noise_gate,
             commands::check_permissions,
             commands::request_accessibility,
             commands::open_accessibility_settings,
@@ -66,16 +92,113 @@ pub fn run() {
             commands::get_version,
             commands::open_log_file,
             commands::open_config_dir,
+            commands::config_doctor,
             commands::close_window,
         ])
         .setup(|app| {
             // Set up system tray
-            tray::setup_tray(app.handle())?;
+            let tray_animator = tray::setup_tray(app.handle())?;
+
+            // Bind the configured hotkey as a system-wide shortcut
+            let hotkey = app
+                .state::<Arc<VaaniApp>>()
+                .config
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .hotkey
+                .clone();
+            if let Err(e) = hotkey::manager::apply_hotkey(app.handle(), None, &hotkey) {
+                tracing::error!("Failed to register hotkey '{hotkey}' on startup: {e}");
+            }
+
+            // Watch the config file on disk so users can retune
+            // `vad_threshold`, `active_mode`, or `hotkey` without restarting
+            // the app. The watcher handle is moved into the thread so it
+            // stays alive (and keeps watching) for as long as the app runs.
+            match config::watch_config() {
+                Ok((config_rx, watcher_handle)) => {
+                    let vaani_for_watcher = app.state::<Arc<VaaniApp>>().inner().clone();
+                    let app_handle_for_watcher = app.handle().clone();
+                    let mut applied_hotkey = hotkey;
+                    std::thread::Builder::new()
+                        .name("vaani-config-apply".into())
+                        .spawn(move || {
+                            let _watcher_handle = watcher_handle;
+                            for new_config in config_rx {
+                                tracing::info!(
+                                    mode = %new_config.active_mode,
+                                    "Config file changed on disk, applying"
+                                );
+                                if new_config.hotkey != applied_hotkey {
+                                    if let Err(e) = hotkey::manager::apply_hotkey(
+                                        &app_handle_for_watcher,
+                                        Some(&applied_hotkey),
+                                        &new_config.hotkey,
+                                    ) {
+                                        tracing::error!("Failed to apply new hotkey: {e}");
+                                    } else {
+                                        applied_hotkey = new_config.hotkey.clone();
+                                    }
+                                }
+                                *vaani_for_watcher
+                                    .config
+                                    .lock()
+                                    .unwrap_or_else(|e| e.into_inner()) = new_config;
+                            }
+                        })
+                        .expect("Failed to spawn config-apply thread");
+                }
+                Err(e) => {
+                    tracing::error!("Failed to start config watcher: {e}");
+                }
+            }
+
+            // Blink the tray icon while recording, reverting to solid once
+            // back to idle or processing.
+            let vaani_for_tray = app.state::<Arc<VaaniApp>>().inner().clone();
+            let vaani_for_listener = vaani_for_tray.clone();
+            vaani_for_tray
+                .state
+                .lock()
+                .on_transition(Box::new(move |old, new| match new {
+                    AppState::Recording | AppState::Streaming
+                        if !matches!(old, AppState::Recording | AppState::Streaming) =>
+                    {
+                        let interval_ms = vaani_for_listener
+                            .config
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .tray_blink_interval_ms;
+                        tray_animator.start(interval_ms);
+                    }
+                    AppState::Idle | AppState::Processing => tray_animator.stop(),
+                    _ => {}
+                }));
+
+            // Re-query permission status on a timer and notify the UI the
+            // moment it changes (e.g. the user grants Accessibility in
+            // System Settings), rather than requiring an app restart.
+            let app_handle_for_permissions = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut last = PermissionsStatus::current();
+                loop {
+                    tokio::time::sleep(PERMISSIONS_POLL_INTERVAL).await;
+                    let current = PermissionsStatus::current();
+                    if current != last {
+                        if let Err(e) =
+                            app_handle_for_permissions.emit("permissions-changed", current)
+                        {
+                            tracing::error!("Failed to emit permissions-changed event: {e}");
+                        }
+                        last = current;
+                    }
+                }
+            });
 
             // Background update check (non-blocking)
             let vaani_ref = app.state::<Arc<VaaniApp>>().inner().clone();
             tauri::async_runtime::spawn(async move {
-                match updater::check_for_update(&vaani_ref.http_client).await {
+                match updater::check_for_update(&vaani_ref.http_client, false).await {
                     Ok(Some(status)) if status.update_available => {
                         tracing::info!(
                             latest = %status.latest,
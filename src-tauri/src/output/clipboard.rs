@@ -0,0 +1,530 @@
+//! Pluggable clipboard backends.
+//!
+//! `arboard` alone silently fails in several common Linux setups — bare
+//! Wayland compositors without clipboard support, headless servers, WSL — so
+//! this module detects the running environment and prefers whatever clipboard
+//! tool actually works there, falling back to `arboard` when nothing more
+//! specific is available.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use crate::error::VaaniError;
+
+// ── ClipboardType ────────────────────────────────────────────────────────────
+
+/// Which clipboard a provider reads from or writes to.
+///
+/// X11 and Wayland expose two independent clipboards: the regular Ctrl+V
+/// `Clipboard`, and the middle-click "primary selection" (`Selection`),
+/// which is set automatically whenever the user selects text and is pasted
+/// without ever touching `Clipboard`. macOS and the `arboard` fallback have
+/// no concept of a primary selection, so `Selection` behaves identically to
+/// `Clipboard` on those backends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClipboardType {
+    #[default]
+    Clipboard,
+    Selection,
+}
+
+// ── Trait ────────────────────────────────────────────────────────────────────
+
+/// Abstraction over a system clipboard backend.
+pub trait ClipboardProvider: Send + Sync {
+    /// Human-readable name of this provider (e.g. `"arboard"`, `"wl-clipboard"`).
+    fn name(&self) -> &'static str;
+
+    /// Read the current clipboard contents as text.
+    fn get_contents(&self) -> Result<String, VaaniError>;
+
+    /// Write `text` to the clipboard.
+    fn set_contents(&self, text: &str) -> Result<(), VaaniError>;
+
+    /// Whether this provider operates on a clipboard that a simulated local
+    /// paste gesture (keystroke or middle-click) can inject into the
+    /// focused application.
+    ///
+    /// Remote-delivery providers such as [`TermcodeProvider`] return
+    /// `false`: the text lands on whatever clipboard the terminal/SSH
+    /// client is attached to, not necessarily this machine, so there is
+    /// nothing local to save, restore, or paste into.
+    fn is_local(&self) -> bool {
+        true
+    }
+}
+
+// ── ArboardProvider ──────────────────────────────────────────────────────────
+
+/// Default cross-platform provider backed by the `arboard` crate.
+pub struct ArboardProvider;
+
+impl ClipboardProvider for ArboardProvider {
+    fn name(&self) -> &'static str {
+        "arboard"
+    }
+
+    fn get_contents(&self) -> Result<String, VaaniError> {
+        let mut clipboard = arboard::Clipboard::new()
+            .map_err(|e| VaaniError::Paste(format!("Failed to access clipboard: {e}")))?;
+        clipboard
+            .get_text()
+            .map_err(|e| VaaniError::Paste(format!("Failed to read clipboard: {e}")))
+    }
+
+    fn set_contents(&self, text: &str) -> Result<(), VaaniError> {
+        let mut clipboard = arboard::Clipboard::new()
+            .map_err(|e| VaaniError::Paste(format!("Failed to access clipboard: {e}")))?;
+        clipboard
+            .set_text(text)
+            .map_err(|e| VaaniError::Paste(format!("Failed to access clipboard: {e}")))
+    }
+}
+
+// ── CommandProvider ──────────────────────────────────────────────────────────
+
+/// A clipboard backend driven by external copy/paste commands.
+///
+/// `set_contents` spawns `copy_cmd` and pipes `text` to its stdin.
+/// `get_contents` spawns `paste_cmd` and reads its stdout.
+pub struct CommandProvider {
+    provider_name: &'static str,
+    copy_cmd: (String, Vec<String>),
+    paste_cmd: (String, Vec<String>),
+}
+
+impl CommandProvider {
+    fn new(
+        provider_name: &'static str,
+        copy_cmd: (&str, &[&str]),
+        paste_cmd: (&str, &[&str]),
+    ) -> Self {
+        Self {
+            provider_name,
+            copy_cmd: (
+                copy_cmd.0.to_string(),
+                copy_cmd.1.iter().map(|s| s.to_string()).collect(),
+            ),
+            paste_cmd: (
+                paste_cmd.0.to_string(),
+                paste_cmd.1.iter().map(|s| s.to_string()).collect(),
+            ),
+        }
+    }
+}
+
+impl ClipboardProvider for CommandProvider {
+    fn name(&self) -> &'static str {
+        self.provider_name
+    }
+
+    fn get_contents(&self) -> Result<String, VaaniError> {
+        let (program, args) = &self.paste_cmd;
+        let output = Command::new(program)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|e| VaaniError::Paste(format!("Failed to run {program}: {e}")))?;
+
+        String::from_utf8(output.stdout)
+            .map_err(|e| VaaniError::Paste(format!("{program} returned invalid UTF-8: {e}")))
+    }
+
+    fn set_contents(&self, text: &str) -> Result<(), VaaniError> {
+        let (program, args) = &self.copy_cmd;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| VaaniError::Paste(format!("Failed to run {program}: {e}")))?;
+
+        child
+            .stdin
+            .take()
+            .ok_or_else(|| VaaniError::Paste(format!("Failed to open stdin for {program}")))?
+            .write_all(text.as_bytes())
+            .map_err(|e| VaaniError::Paste(format!("Failed to write to {program}: {e}")))?;
+
+        let status = child
+            .wait()
+            .map_err(|e| VaaniError::Paste(format!("Failed to wait on {program}: {e}")))?;
+
+        if !status.success() {
+            return Err(VaaniError::Paste(format!(
+                "{program} exited with status {status}"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+// ── TermcodeProvider ─────────────────────────────────────────────────────────
+
+/// Clipboard provider for headless/SSH sessions: sets the clipboard by
+/// emitting an OSC 52 terminal escape sequence (`ESC ] 52 ; c ; <base64> BEL`)
+/// to the controlling terminal, the same mechanism Helix offers as
+/// `clipboard-provider = "termcode"`.
+///
+/// This is the only way to reach the user's actual machine clipboard when
+/// Vaani runs over SSH or inside a multiplexer with no local clipboard
+/// binary reachable — the terminal emulator (or an OSC-52-forwarding SSH
+/// client/multiplexer) is responsible for placing the text on that
+/// clipboard. OSC 52 is write-only in practice, so reading it back is not
+/// supported.
+pub struct TermcodeProvider;
+
+impl ClipboardProvider for TermcodeProvider {
+    fn name(&self) -> &'static str {
+        "termcode"
+    }
+
+    fn get_contents(&self) -> Result<String, VaaniError> {
+        Err(VaaniError::Paste(
+            "termcode provider cannot read the clipboard (OSC 52 is write-only)".to_string(),
+        ))
+    }
+
+    fn set_contents(&self, text: &str) -> Result<(), VaaniError> {
+        let encoded = BASE64.encode(text.as_bytes());
+        let sequence = format!("\x1b]52;c;{encoded}\x07");
+
+        let mut tty = std::fs::OpenOptions::new()
+            .write(true)
+            .open("/dev/tty")
+            .map_err(|e| VaaniError::Paste(format!("Failed to open controlling terminal: {e}")))?;
+
+        tty.write_all(sequence.as_bytes())
+            .map_err(|e| VaaniError::Paste(format!("Failed to write OSC 52 sequence: {e}")))
+    }
+
+    fn is_local(&self) -> bool {
+        false
+    }
+}
+
+// ── Detection ────────────────────────────────────────────────────────────────
+
+/// Returns `true` if `command` can be found on `$PATH`.
+fn command_exists(command: &str) -> bool {
+    let Ok(path_var) = std::env::var("PATH") else {
+        return false;
+    };
+
+    std::env::split_paths(&path_var).any(|dir| dir.join(command).is_file())
+}
+
+/// Build the `pbcopy`/`pbpaste` provider. macOS has no primary selection, so
+/// `clipboard_type` is ignored.
+fn pasteboard_provider() -> Box<dyn ClipboardProvider> {
+    Box::new(CommandProvider::new(
+        "pasteboard",
+        ("pbcopy", &[]),
+        ("pbpaste", &[]),
+    ))
+}
+
+/// Build the `wl-copy`/`wl-paste` provider for `clipboard_type`.
+fn wayland_provider(clipboard_type: ClipboardType) -> Box<dyn ClipboardProvider> {
+    match clipboard_type {
+        ClipboardType::Clipboard => Box::new(CommandProvider::new(
+            "wayland",
+            ("wl-copy", &[]),
+            ("wl-paste", &["--no-newline"]),
+        )),
+        ClipboardType::Selection => Box::new(CommandProvider::new(
+            "wayland-primary",
+            ("wl-copy", &["--primary"]),
+            ("wl-paste", &["--primary", "--no-newline"]),
+        )),
+    }
+}
+
+/// Build the `xclip` provider for `clipboard_type`.
+fn x_clip_provider(clipboard_type: ClipboardType) -> Box<dyn ClipboardProvider> {
+    match clipboard_type {
+        ClipboardType::Clipboard => Box::new(CommandProvider::new(
+            "x-clip",
+            ("xclip", &["-selection", "clipboard"]),
+            ("xclip", &["-selection", "clipboard", "-o"]),
+        )),
+        ClipboardType::Selection => Box::new(CommandProvider::new(
+            "x-clip-primary",
+            ("xclip", &["-selection", "primary"]),
+            ("xclip", &["-selection", "primary", "-o"]),
+        )),
+    }
+}
+
+/// Build the `xsel` provider for `clipboard_type`.
+fn x_sel_provider(clipboard_type: ClipboardType) -> Box<dyn ClipboardProvider> {
+    match clipboard_type {
+        ClipboardType::Clipboard => Box::new(CommandProvider::new(
+            "x-sel",
+            ("xsel", &["--clipboard", "--input"]),
+            ("xsel", &["--clipboard", "--output"]),
+        )),
+        ClipboardType::Selection => Box::new(CommandProvider::new(
+            "x-sel-primary",
+            ("xsel", &["--primary", "--input"]),
+            ("xsel", &["--primary", "--output"]),
+        )),
+    }
+}
+
+/// Detect the best clipboard provider for the current environment.
+///
+/// Preference order:
+/// 1. macOS: `pbcopy`/`pbpaste`
+/// 2. Wayland (`$WAYLAND_DISPLAY` set): `wl-copy`/`wl-paste`
+/// 3. X11 (`$DISPLAY` set): `xclip`, then `xsel`
+/// 4. Fallback: `arboard`
+///
+/// `clipboard_type` selects between the regular clipboard and the primary
+/// selection; it has no effect on macOS or the `arboard` fallback, neither
+/// of which has a primary selection.
+pub fn get_clipboard_provider(clipboard_type: ClipboardType) -> Box<dyn ClipboardProvider> {
+    if cfg!(target_os = "macos") && command_exists("pbcopy") && command_exists("pbpaste") {
+        return pasteboard_provider();
+    }
+
+    if std::env::var("WAYLAND_DISPLAY").is_ok()
+        && command_exists("wl-copy")
+        && command_exists("wl-paste")
+    {
+        return wayland_provider(clipboard_type);
+    }
+
+    if std::env::var("DISPLAY").is_ok() {
+        if command_exists("xclip") {
+            return x_clip_provider(clipboard_type);
+        }
+        if command_exists("xsel") {
+            return x_sel_provider(clipboard_type);
+        }
+    }
+
+    Box::new(ArboardProvider)
+}
+
+// ── Config-driven selection ─────────────────────────────────────────────────
+
+/// A user-supplied external command and its arguments, used by the `custom`
+/// clipboard provider (config field `clipboard_custom_copy`/`clipboard_custom_paste`).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CustomClipboardCommand {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// Names accepted by the `clipboard_provider` config field.
+pub const CLIPBOARD_PROVIDERS: &[&str] = &[
+    "auto",
+    "arboard",
+    "wayland",
+    "x-clip",
+    "x-sel",
+    "pasteboard",
+    "termcode",
+    "custom",
+];
+
+/// Resolve a clipboard provider from explicit config, bypassing auto-detection
+/// unless `provider_name` is `"auto"`.
+///
+/// `clipboard_type` selects between the regular clipboard and the primary
+/// selection for backends that support both; it is ignored by `"custom"`,
+/// which always targets whatever the user's command operates on.
+///
+/// Returns `VaaniError::Config` if `provider_name` is unrecognised, or if
+/// `"custom"` is selected without both `custom_copy` and `custom_paste` set.
+pub fn resolve_clipboard_provider(
+    provider_name: &str,
+    clipboard_type: ClipboardType,
+    custom_copy: Option<&CustomClipboardCommand>,
+    custom_paste: Option<&CustomClipboardCommand>,
+) -> Result<Box<dyn ClipboardProvider>, VaaniError> {
+    match provider_name {
+        "auto" => Ok(get_clipboard_provider(clipboard_type)),
+        "arboard" => Ok(Box::new(ArboardProvider)),
+        "wayland" => Ok(wayland_provider(clipboard_type)),
+        "x-clip" => Ok(x_clip_provider(clipboard_type)),
+        "x-sel" => Ok(x_sel_provider(clipboard_type)),
+        "pasteboard" => Ok(pasteboard_provider()),
+        "termcode" => Ok(Box::new(TermcodeProvider)),
+        "custom" => {
+            let copy = custom_copy.ok_or_else(|| {
+                VaaniError::Config(
+                    "clipboard_provider is 'custom' but clipboard_custom_copy is not set"
+                        .to_string(),
+                )
+            })?;
+            let paste = custom_paste.ok_or_else(|| {
+                VaaniError::Config(
+                    "clipboard_provider is 'custom' but clipboard_custom_paste is not set"
+                        .to_string(),
+                )
+            })?;
+            let copy_args: Vec<&str> = copy.args.iter().map(String::as_str).collect();
+            let paste_args: Vec<&str> = paste.args.iter().map(String::as_str).collect();
+            Ok(Box::new(CommandProvider::new(
+                "custom",
+                (copy.command.as_str(), &copy_args),
+                (paste.command.as_str(), &paste_args),
+            )))
+        }
+        other => Err(VaaniError::Config(format!(
+            "Unknown clipboard_provider '{other}'. Valid values: {}",
+            CLIPBOARD_PROVIDERS.join(", ")
+        ))),
+    }
+}
+
+// ── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_exists_finds_a_real_binary() {
+        // `sh` should exist on every platform we target (macOS, Linux).
+        assert!(command_exists("sh"));
+    }
+
+    #[test]
+    fn command_exists_rejects_bogus_name() {
+        assert!(!command_exists("this-binary-does-not-exist-anywhere-xyz"));
+    }
+
+    #[test]
+    fn get_clipboard_provider_returns_a_named_provider() {
+        let provider = get_clipboard_provider(ClipboardType::Clipboard);
+        assert!(!provider.name().is_empty());
+    }
+
+    #[test]
+    fn arboard_provider_name() {
+        assert_eq!(ArboardProvider.name(), "arboard");
+    }
+
+    #[test]
+    fn arboard_provider_is_local() {
+        assert!(ArboardProvider.is_local());
+    }
+
+    #[test]
+    fn termcode_provider_name() {
+        assert_eq!(TermcodeProvider.name(), "termcode");
+    }
+
+    #[test]
+    fn termcode_provider_is_not_local() {
+        assert!(!TermcodeProvider.is_local());
+    }
+
+    #[test]
+    fn termcode_provider_get_contents_is_unsupported() {
+        assert!(TermcodeProvider.get_contents().is_err());
+    }
+
+    #[test]
+    fn resolve_clipboard_provider_auto_uses_detection() {
+        let provider = resolve_clipboard_provider("auto", ClipboardType::Clipboard, None, None)
+            .expect("auto should resolve");
+        assert!(!provider.name().is_empty());
+    }
+
+    #[test]
+    fn resolve_clipboard_provider_arboard_is_explicit() {
+        let provider =
+            resolve_clipboard_provider("arboard", ClipboardType::Clipboard, None, None)
+                .expect("arboard should resolve");
+        assert_eq!(provider.name(), "arboard");
+    }
+
+    #[test]
+    fn resolve_clipboard_provider_termcode_is_explicit() {
+        let provider =
+            resolve_clipboard_provider("termcode", ClipboardType::Clipboard, None, None)
+                .expect("termcode should resolve");
+        assert_eq!(provider.name(), "termcode");
+    }
+
+    #[test]
+    fn resolve_clipboard_provider_unknown_name_errors() {
+        let err =
+            resolve_clipboard_provider("not-a-real-provider", ClipboardType::Clipboard, None, None)
+                .unwrap_err();
+        assert!(matches!(err, VaaniError::Config(_)));
+    }
+
+    #[test]
+    fn resolve_clipboard_provider_custom_without_copy_errors() {
+        let paste = CustomClipboardCommand {
+            command: "cat".to_string(),
+            args: vec![],
+        };
+        let err =
+            resolve_clipboard_provider("custom", ClipboardType::Clipboard, None, Some(&paste))
+                .unwrap_err();
+        assert!(matches!(err, VaaniError::Config(_)));
+    }
+
+    #[test]
+    fn resolve_clipboard_provider_custom_without_paste_errors() {
+        let copy = CustomClipboardCommand {
+            command: "cat".to_string(),
+            args: vec![],
+        };
+        let err =
+            resolve_clipboard_provider("custom", ClipboardType::Clipboard, Some(&copy), None)
+                .unwrap_err();
+        assert!(matches!(err, VaaniError::Config(_)));
+    }
+
+    #[test]
+    fn resolve_clipboard_provider_custom_with_both_succeeds() {
+        let copy = CustomClipboardCommand {
+            command: "cat".to_string(),
+            args: vec![],
+        };
+        let paste = CustomClipboardCommand {
+            command: "cat".to_string(),
+            args: vec![],
+        };
+        let provider = resolve_clipboard_provider(
+            "custom",
+            ClipboardType::Clipboard,
+            Some(&copy),
+            Some(&paste),
+        )
+        .expect("custom with both commands should resolve");
+        assert_eq!(provider.name(), "custom");
+    }
+
+    #[test]
+    fn wayland_primary_provider_has_distinct_name() {
+        let provider = wayland_provider(ClipboardType::Selection);
+        assert_eq!(provider.name(), "wayland-primary");
+    }
+
+    #[test]
+    fn x_clip_primary_provider_has_distinct_name() {
+        let provider = x_clip_provider(ClipboardType::Selection);
+        assert_eq!(provider.name(), "x-clip-primary");
+    }
+
+    #[test]
+    fn x_sel_primary_provider_has_distinct_name() {
+        let provider = x_sel_provider(ClipboardType::Selection);
+        assert_eq!(provider.name(), "x-sel-primary");
+    }
+}
@@ -0,0 +1,4 @@
+//! Text output: clipboard access and simulated paste/type keystrokes.
+
+pub mod clipboard;
+pub mod paste;
@@ -1,73 +1,155 @@
 //! Clipboard-based text pasting at the current cursor position.
 //!
-//! The primary workflow is:
+//! The primary workflow targets the regular (Ctrl+V) clipboard:
 //! 1. Save the user's current clipboard contents.
 //! 2. Place new text on the clipboard.
 //! 3. Simulate the platform paste keystroke (Cmd+V on macOS, Ctrl+V on Linux).
 //! 4. Wait for the target application to consume the paste.
 //! 5. Restore the original clipboard contents.
+//!
+//! Passing [`ClipboardType::Selection`] instead targets the X11/Wayland
+//! primary selection (middle-click paste). That clipboard is never touched
+//! outside of this request, so steps 1, 4 and 5 are skipped entirely —
+//! there is nothing to save or restore, and no timing race with whatever
+//! the user has selected elsewhere.
 
 use std::thread;
 use std::time::Duration;
 
-use arboard::Clipboard;
-use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+use enigo::{Button, Direction, Enigo, Key, Keyboard, Mouse, Settings};
 use tracing::debug;
 
+use super::clipboard::{resolve_clipboard_provider, ClipboardType};
+use crate::config::VaaniConfig;
 use crate::error::VaaniError;
 
+// ── PasteMode ────────────────────────────────────────────────────────────────
+
+/// How `paste_text` should inject text into the focused application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PasteMode {
+    /// Try the clipboard first; if no working clipboard provider is found
+    /// (no clipboard binary, no compositor, headless display), fall back to
+    /// synthetic typing instead of returning an error.
+    #[default]
+    Auto,
+    /// Always use the clipboard; propagate clipboard errors instead of
+    /// falling back.
+    ClipboardOnly,
+    /// Always type the text character by character via [`type_text`],
+    /// bypassing the clipboard entirely.
+    TypeOnly,
+}
+
 // ── Public API ──────────────────────────────────────────────────────────────
 
-/// Paste `text` at the current cursor position by writing it to the clipboard
-/// and simulating the platform paste keystroke.
+/// Paste `text` at the current cursor position, via the clipboard or
+/// synthetic typing depending on `mode`.
 ///
-/// The user's original clipboard contents are saved before the operation and
-/// restored after a configurable delay (`restore_delay_ms`), giving the
-/// foreground application time to consume the paste event.
+/// - [`PasteMode::Auto`] (the default): use the clipboard, falling back to
+///   [`type_text`] with a `debug!` if no working clipboard provider is found
+///   for the current environment.
+/// - [`PasteMode::ClipboardOnly`]: use the clipboard only; clipboard errors
+///   propagate instead of falling back.
+/// - [`PasteMode::TypeOnly`]: skip the clipboard and type directly.
 ///
 /// Returns `Ok(())` immediately if `text` is empty.
-pub fn paste_text(text: &str, restore_delay_ms: u64) -> Result<(), VaaniError> {
+pub fn paste_text(
+    text: &str,
+    config: &VaaniConfig,
+    clipboard_type: ClipboardType,
+    mode: PasteMode,
+) -> Result<(), VaaniError> {
     if text.is_empty() {
         debug!("paste_text called with empty string, nothing to do");
         return Ok(());
     }
 
-    let mut clipboard = Clipboard::new()
-        .map_err(|e| VaaniError::Paste(format!("Failed to access clipboard: {e}")))?;
+    if mode == PasteMode::TypeOnly {
+        debug!("PasteMode::TypeOnly requested; typing text directly");
+        return type_text(text);
+    }
+
+    match paste_via_clipboard(text, config, clipboard_type) {
+        Ok(()) => Ok(()),
+        Err(VaaniError::Paste(reason)) if mode == PasteMode::Auto => {
+            debug!(
+                reason,
+                "No working clipboard provider; falling back to synthetic typing"
+            );
+            type_text(text)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Paste `text` via the clipboard backend resolved from `config`.
+///
+/// See [`paste_text`] for the save/restore and paste-gesture behavior per
+/// `clipboard_type`; this is the `ClipboardOnly` half of that contract, with
+/// no typing fallback.
+fn paste_via_clipboard(
+    text: &str,
+    config: &VaaniConfig,
+    clipboard_type: ClipboardType,
+) -> Result<(), VaaniError> {
+    let clipboard = resolve_clipboard_provider(
+        &config.clipboard_provider,
+        clipboard_type,
+        config.clipboard_custom_copy.as_ref(),
+        config.clipboard_custom_paste.as_ref(),
+    )?;
+    debug!(
+        provider = clipboard.name(),
+        clipboard_type = ?clipboard_type,
+        "Using clipboard provider"
+    );
+
+    if !clipboard.is_local() {
+        // Remote-delivery providers (e.g. OSC 52 over SSH) place the text on
+        // whatever clipboard the terminal is attached to; there is nothing
+        // local to save, restore, or paste into, so the terminal/user
+        // handles the rest.
+        clipboard.set_contents(text)?;
+        debug!(
+            provider = clipboard.name(),
+            "Remote clipboard provider; skipping save/restore and paste gesture"
+        );
+        return Ok(());
+    }
 
-    // ── Save original clipboard contents ────────────────────────────────
-    let original = clipboard.get_text().ok();
+    // ── Save original clipboard contents (Clipboard only) ───────────────
+    let original = match clipboard_type {
+        ClipboardType::Clipboard => clipboard.get_contents().ok(),
+        ClipboardType::Selection => None,
+    };
     debug!(
         original_len = original.as_ref().map_or(0, |s| s.len()),
         "Saved original clipboard contents"
     );
 
     // ── Set clipboard to the new text ───────────────────────────────────
-    clipboard
-        .set_text(text)
-        .map_err(|e| VaaniError::Paste(format!("Failed to access clipboard: {e}")))?;
+    clipboard.set_contents(text)?;
     debug!(text_len = text.len(), "Clipboard set with new text");
 
-    // ── Simulate paste keystroke ────────────────────────────────────────
-    simulate_paste()?;
-    debug!("Paste keystroke simulated");
-
-    // ── Wait, then restore original clipboard ───────────────────────────
-    thread::sleep(Duration::from_millis(restore_delay_ms));
-
-    match original {
-        Some(ref contents) => {
-            clipboard
-                .set_text(contents)
-                .map_err(|e| VaaniError::Paste(format!("Failed to access clipboard: {e}")))?;
-            debug!("Original clipboard contents restored");
-        }
-        None => {
-            // The clipboard was empty (or non-text) before; clear it.
-            clipboard
-                .clear()
-                .map_err(|e| VaaniError::Paste(format!("Failed to access clipboard: {e}")))?;
-            debug!("Clipboard cleared (was empty before paste)");
+    // ── Simulate paste gesture ───────────────────────────────────────────
+    simulate_paste(clipboard_type)?;
+    debug!("Paste gesture simulated");
+
+    // ── Wait, then restore original clipboard (Clipboard only) ──────────
+    if clipboard_type == ClipboardType::Clipboard {
+        thread::sleep(Duration::from_millis(config.paste_restore_delay_ms as u64));
+
+        match original {
+            Some(ref contents) => {
+                clipboard.set_contents(contents)?;
+                debug!("Original clipboard contents restored");
+            }
+            None => {
+                // The clipboard was empty (or non-text) before; clear it.
+                clipboard.set_contents("")?;
+                debug!("Clipboard cleared (was empty before paste)");
+            }
         }
     }
 
@@ -100,13 +182,55 @@ pub fn type_text(text: &str) -> Result<(), VaaniError> {
     Ok(())
 }
 
+/// Replace a previously typed interim string with a revised one, by
+/// backspacing past the end of their common prefix and typing the rest.
+///
+/// Used for streaming transcription's interim hypotheses, which get
+/// revised in place as more audio arrives — unlike [`type_text`]'s
+/// append-only token deltas from `enhance::enhance_streaming`.
+///
+/// Returns `Ok(())` immediately if `previous == current`.
+pub fn retype_text(previous: &str, current: &str) -> Result<(), VaaniError> {
+    if previous == current {
+        return Ok(());
+    }
+
+    let common_prefix_len = previous
+        .chars()
+        .zip(current.chars())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let to_erase = previous.chars().count() - common_prefix_len;
+    if to_erase > 0 {
+        let mut enigo = Enigo::new(&Settings::default())
+            .map_err(|e| VaaniError::Paste(format!("Failed to simulate keystroke: {e}")))?;
+        for _ in 0..to_erase {
+            enigo
+                .key(Key::Backspace, Direction::Click)
+                .map_err(|e| VaaniError::Paste(format!("Failed to simulate keystroke: {e}")))?;
+        }
+    }
+
+    let suffix: String = current.chars().skip(common_prefix_len).collect();
+    type_text(&suffix)
+}
+
 // ── Internal helpers ────────────────────────────────────────────────────────
 
-/// Simulate the platform-specific paste keystroke.
+/// Simulate the paste gesture matching `clipboard_type`.
+fn simulate_paste(clipboard_type: ClipboardType) -> Result<(), VaaniError> {
+    match clipboard_type {
+        ClipboardType::Clipboard => simulate_clipboard_paste(),
+        ClipboardType::Selection => simulate_primary_selection_paste(),
+    }
+}
+
+/// Simulate the platform-specific clipboard paste keystroke.
 ///
 /// - macOS: Cmd+V (`Meta` + `v`)
 /// - Linux: Ctrl+V (`Control` + `v`)
-fn simulate_paste() -> Result<(), VaaniError> {
+fn simulate_clipboard_paste() -> Result<(), VaaniError> {
     let mut enigo = Enigo::new(&Settings::default())
         .map_err(|e| VaaniError::Paste(format!("Failed to simulate keystroke: {e}")))?;
 
@@ -132,6 +256,22 @@ fn simulate_paste() -> Result<(), VaaniError> {
     Ok(())
 }
 
+/// Simulate a primary-selection paste.
+///
+/// Unlike the regular clipboard, the primary selection has no standard
+/// keyboard shortcut — it is conventionally pasted with a middle-click at
+/// the current pointer position.
+fn simulate_primary_selection_paste() -> Result<(), VaaniError> {
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| VaaniError::Paste(format!("Failed to simulate middle-click: {e}")))?;
+
+    enigo
+        .button(Button::Middle, Direction::Click)
+        .map_err(|e| VaaniError::Paste(format!("Failed to simulate middle-click: {e}")))?;
+
+    Ok(())
+}
+
 /// Return the platform-specific modifier key used for paste.
 #[cfg(target_os = "macos")]
 fn platform_paste_modifier() -> Key {
@@ -160,7 +300,40 @@ mod tests {
     fn paste_text_empty_string_returns_ok() {
         // An empty string should short-circuit without touching the clipboard
         // or simulating any keystrokes.
-        let result = paste_text("", 50);
+        let result = paste_text(
+            "",
+            &VaaniConfig::default(),
+            ClipboardType::Clipboard,
+            PasteMode::Auto,
+        );
+        assert!(
+            result.is_ok(),
+            "paste_text with empty string should return Ok"
+        );
+    }
+
+    #[test]
+    fn paste_text_empty_string_returns_ok_for_selection() {
+        let result = paste_text(
+            "",
+            &VaaniConfig::default(),
+            ClipboardType::Selection,
+            PasteMode::Auto,
+        );
+        assert!(
+            result.is_ok(),
+            "paste_text with empty string should return Ok"
+        );
+    }
+
+    #[test]
+    fn paste_text_empty_string_returns_ok_for_type_only() {
+        let result = paste_text(
+            "",
+            &VaaniConfig::default(),
+            ClipboardType::Clipboard,
+            PasteMode::TypeOnly,
+        );
         assert!(
             result.is_ok(),
             "paste_text with empty string should return Ok"
@@ -177,6 +350,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn retype_text_is_a_noop_when_unchanged() {
+        // Identical strings should short-circuit without touching enigo at
+        // all, so this passes even on a headless CI display.
+        let result = retype_text("hello", "hello");
+        assert!(result.is_ok(), "retype_text with no change should return Ok");
+    }
+
     #[test]
     fn platform_paste_modifier_is_defined() {
         // Ensure the compile-time platform selection yields a valid key.
@@ -0,0 +1,58 @@
+//! macOS permission queries.
+//!
+//! Accessibility/Input-Monitoring trust is a plain C API
+//! (`AXIsProcessTrusted`/`AXIsProcessTrustedWithOptions`), linked directly
+//! against `ApplicationServices` the same way `output::paste` already shells
+//! out to platform tools rather than pulling in a bridge crate for a single
+//! call. Microphone authorization is an Objective-C class method on
+//! `AVCaptureDevice`, so that half goes through `objc2`/`objc2-av-foundation`
+//! instead.
+
+use core_foundation::base::TCFType;
+use core_foundation::boolean::CFBoolean;
+use core_foundation::dictionary::CFDictionary;
+use core_foundation::string::CFString;
+use objc2_av_foundation::{AVAuthorizationStatus, AVCaptureDevice, AVMediaTypeAudio};
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn AXIsProcessTrusted() -> bool;
+    fn AXIsProcessTrustedWithOptions(
+        options: core_foundation::dictionary::CFDictionaryRef,
+    ) -> bool;
+}
+
+/// Returns whether Vaani is currently trusted for Accessibility/Input
+/// Monitoring, without triggering the system prompt.
+pub fn is_accessibility_trusted() -> bool {
+    unsafe { AXIsProcessTrusted() }
+}
+
+/// Triggers the Accessibility trust prompt if it hasn't been granted yet,
+/// and returns whether it's trusted at the moment the call returns.
+///
+/// macOS doesn't grant trust synchronously while the System Settings dialog
+/// is open, so this typically still reports `false` right after the user
+/// is prompted — `commands::check_permissions`, re-polled in the
+/// background (see `lib.rs`), is what picks up the change once they flip
+/// the toggle.
+pub fn prompt_for_accessibility() -> bool {
+    // `kAXTrustedCheckOptionPrompt`: not exposed as a constant by any
+    // binding crate, so it's spelled out verbatim — it's a stable,
+    // documented Apple API string.
+    let key = CFString::from_static_string("AXTrustedCheckOptionPrompt");
+    let options =
+        CFDictionary::from_CFType_pairs(&[(key.as_CFType(), CFBoolean::true_value().as_CFType())]);
+
+    unsafe { AXIsProcessTrustedWithOptions(options.as_concrete_TypeRef()) }
+}
+
+/// Returns whether Vaani currently has microphone access. `NotDetermined`
+/// (the user has never been asked) is reported as `false` — the first
+/// recording attempt is what triggers the real, asynchronous
+/// `AVCaptureDevice` prompt, via cpal's stream setup on the mic-test worker
+/// thread.
+pub fn is_mic_authorized() -> bool {
+    let status = unsafe { AVCaptureDevice::authorizationStatusForMediaType(AVMediaTypeAudio) };
+    status == AVAuthorizationStatus::Authorized
+}
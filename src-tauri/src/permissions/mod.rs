@@ -0,0 +1,48 @@
+//! Platform permission checks for microphone access and Accessibility
+//! (Input Monitoring) trust, used by `commands::check_permissions` and the
+//! onboarding flow to decide whether Vaani can actually record and paste.
+//!
+//! On macOS these map to the `AVCaptureDevice` authorization status and the
+//! TCC "process trusted" check. Every other platform has no equivalent
+//! privacy gate, so both report already-granted, mirroring how
+//! [`crate::keychain`] falls back to a stub outside its supported
+//! platforms.
+
+#[cfg(target_os = "macos")]
+mod macos;
+
+#[cfg(target_os = "macos")]
+pub use macos::{is_accessibility_trusted, is_mic_authorized, prompt_for_accessibility};
+
+/// Returns whether Vaani currently has microphone access.
+#[cfg(not(target_os = "macos"))]
+pub fn is_mic_authorized() -> bool {
+    true
+}
+
+/// Returns whether Vaani is currently trusted for Accessibility/Input
+/// Monitoring.
+#[cfg(not(target_os = "macos"))]
+pub fn is_accessibility_trusted() -> bool {
+    true
+}
+
+/// Triggers the Accessibility trust prompt. No-op where there's no such
+/// prompt to trigger.
+#[cfg(not(target_os = "macos"))]
+pub fn prompt_for_accessibility() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn non_macos_permissions_are_always_granted() {
+        assert!(is_mic_authorized());
+        assert!(is_accessibility_trusted());
+        assert!(prompt_for_accessibility());
+    }
+}
@@ -1,11 +1,25 @@
 //! Sound effect playback for recording start/stop feedback.
 //!
-//! Uses `rodio` to play bundled WAV files asynchronously.
-//! Sound playback is non-blocking and tolerates missing files
-//! or unavailable audio output devices gracefully.
+//! [`SoundPlayer`] owns a single `rodio::OutputStream`/`Sink` pair on a
+//! dedicated worker thread for the lifetime of the process — the same
+//! command-channel shape as [`crate::audio::mic_test::MicTestHandle`].
+//! Every bundled sound is decoded into PCM once at [`SoundPlayer::spawn`],
+//! so `play_sound`/`play_bytes` just hand a message to the worker instead of
+//! spawning a thread, reopening the output device, and re-reading the file
+//! on every call. Playback is non-blocking and tolerates missing files or
+//! unavailable audio output devices gracefully.
+//!
+//! [`sound_file_path`] probes for `.wav`, `.ogg`, `.flac`, and `.mp3` under
+//! the same base name, and [`decode`] sniffs the container/codec rather
+//! than trusting the extension, so a custom sound theme can drop in a
+//! smaller, pre-compressed file without touching any code.
 
 use std::io::Cursor;
 use std::path::PathBuf;
+use std::sync::{mpsc, OnceLock};
+use std::thread;
+
+use rodio::Source;
 
 use crate::error::VaaniError;
 
@@ -16,101 +30,263 @@ pub enum SoundEffect {
     RecordStop,
 }
 
-/// Returns the filename for a sound effect.
-fn sound_filename(effect: SoundEffect) -> &'static str {
+/// Every sound effect, used to preload the bundled sounds at startup.
+const ALL_EFFECTS: [SoundEffect; 2] = [SoundEffect::RecordStart, SoundEffect::RecordStop];
+
+/// Base filename (without extension) for a sound effect.
+fn sound_basename(effect: SoundEffect) -> &'static str {
     match effect {
-        SoundEffect::RecordStart => "record_start.wav",
-        SoundEffect::RecordStop => "record_stop.wav",
+        SoundEffect::RecordStart => "record_start",
+        SoundEffect::RecordStop => "record_stop",
     }
 }
 
+/// Extensions probed for each sound effect, in preference order. `.wav` is
+/// checked first so the bundled defaults keep winning; the rest let a
+/// custom sound theme ship smaller, pre-compressed assets that `decode`'s
+/// format-sniffing `rodio::Decoder` can already play without knowing the
+/// extension in advance.
+const CANDIDATE_EXTENSIONS: &[&str] = &["wav", "ogg", "flac", "mp3"];
+
+/// Returns the first `{dir}/{basename}.{ext}` that exists, trying
+/// [`CANDIDATE_EXTENSIONS`] in order.
+fn find_candidate(dir: &std::path::Path, basename: &str) -> Option<PathBuf> {
+    CANDIDATE_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{basename}.{ext}")))
+        .find(|path| path.exists())
+}
+
 /// Returns the expected file path for a sound effect.
 ///
-/// Looks in the `sounds/` directory relative to the executable,
-/// falling back to `src-tauri/sounds/` for development.
+/// Looks in the `sounds/` directory relative to the executable, falling
+/// back to `src-tauri/sounds/` for development. At each candidate
+/// directory, [`CANDIDATE_EXTENSIONS`] are tried in order so a user-supplied
+/// `.ogg`/`.flac`/`.mp3` theme file is found before falling through to the
+/// next directory. If nothing exists anywhere, returns the bundled `.wav`
+/// path so the caller's `std::fs::read` reports a real "file not found".
 pub fn sound_file_path(effect: SoundEffect) -> PathBuf {
-    let filename = sound_filename(effect);
+    let basename = sound_basename(effect);
 
     // Try relative to executable first (production)
     if let Ok(exe) = std::env::current_exe() {
         if let Some(dir) = exe.parent() {
-            let prod_path = dir.join("sounds").join(filename);
-            if prod_path.exists() {
-                return prod_path;
+            if let Some(found) = find_candidate(&dir.join("sounds"), basename) {
+                return found;
             }
             // macOS .app bundle: Resources directory
-            let resources_path = dir
-                .join("..")
-                .join("Resources")
-                .join("sounds")
-                .join(filename);
-            if resources_path.exists() {
-                return resources_path;
+            let resources_dir = dir.join("..").join("Resources").join("sounds");
+            if let Some(found) = find_candidate(&resources_dir, basename) {
+                return found;
             }
         }
     }
 
     // Fallback: development path
-    PathBuf::from("src-tauri").join("sounds").join(filename)
+    let dev_dir = PathBuf::from("src-tauri").join("sounds");
+    find_candidate(&dev_dir, basename).unwrap_or_else(|| dev_dir.join(format!("{basename}.wav")))
 }
 
-/// Play a sound effect asynchronously (non-blocking).
-///
-/// If the sound file is missing or audio output is unavailable,
-/// logs a warning and returns Ok â€” sounds are optional.
-pub fn play_sound(effect: SoundEffect) -> Result<(), VaaniError> {
+/// Decoded PCM for one sound, cached so playback never re-reads or
+/// re-decodes the source file.
+struct DecodedSound {
+    channels: u16,
+    sample_rate: u32,
+    samples: Vec<f32>,
+}
+
+/// Decode `audio` (any format `rodio`'s `Decoder` can sniff, e.g. WAV or
+/// MP3) into PCM, logging and returning `None` on failure.
+fn decode(audio: Vec<u8>, label: &'static str) -> Option<DecodedSound> {
+    let cursor = Cursor::new(audio);
+    let source = match rodio::Decoder::new(cursor) {
+        Ok(source) => source,
+        Err(e) => {
+            tracing::warn!("Failed to decode {label} audio: {e}");
+            return None;
+        }
+    };
+
+    let channels = source.channels();
+    let sample_rate = source.sample_rate();
+    let samples: Vec<f32> = source.convert_samples().collect();
+
+    Some(DecodedSound {
+        channels,
+        sample_rate,
+        samples,
+    })
+}
+
+/// Read and decode the sound file for `effect` (whichever extension
+/// [`sound_file_path`] found), logging and returning `None` if the file is
+/// missing or undecodable.
+fn preload(effect: SoundEffect) -> Option<DecodedSound> {
     let path = sound_file_path(effect);
 
-    let wav_bytes = match std::fs::read(&path) {
+    let bytes = match std::fs::read(&path) {
         Ok(bytes) => bytes,
         Err(e) => {
             tracing::warn!(
                 path = %path.display(),
                 error = %e,
-                "Sound file not found, skipping playback"
+                "Sound file not found, skipping preload"
             );
-            return Ok(());
+            return None;
         }
     };
 
-    // Spawn a thread for playback so we don't block the caller.
-    // The thread owns the OutputStream and Sink, keeping them alive
-    // until playback completes.
-    std::thread::Builder::new()
-        .name("vaani-sound".into())
-        .spawn(move || {
-            let output = match rodio::OutputStream::try_default() {
-                Ok(output) => output,
-                Err(e) => {
-                    tracing::warn!("No audio output device: {e}");
-                    return;
-                }
-            };
-            let (_stream, handle) = output;
-
-            let cursor = Cursor::new(wav_bytes);
-            let source = match rodio::Decoder::new(cursor) {
-                Ok(source) => source,
-                Err(e) => {
-                    tracing::warn!("Failed to decode sound: {e}");
-                    return;
-                }
-            };
+    decode(bytes, "sound effect")
+}
 
-            let sink = match rodio::Sink::try_new(&handle) {
-                Ok(sink) => sink,
-                Err(e) => {
-                    tracing::warn!("Failed to create audio sink: {e}");
-                    return;
+/// Commands accepted by the sound-player worker thread.
+enum SoundCommand {
+    /// Play a preloaded effect.
+    Play(SoundEffect),
+    /// Decode and play audio that wasn't preloaded at startup (e.g. TTS
+    /// output from [`crate::tts`]).
+    PlayBytes { audio: Vec<u8>, label: &'static str },
+}
+
+/// Handle to the long-lived sound-player worker thread.
+///
+/// Cloning is cheap (it's just a channel sender); every clone talks to the
+/// same worker thread, which owns the output device and preloaded sound
+/// buffers for the life of the process.
+#[derive(Clone)]
+pub struct SoundPlayer {
+    commands: mpsc::Sender<SoundCommand>,
+}
+
+impl SoundPlayer {
+    /// Spawns the worker thread, preloading and decoding every bundled
+    /// sound effect up front so later `play`/`play_bytes` calls only ever
+    /// touch cached PCM and an already-open output device.
+    pub fn spawn() -> Result<Self, VaaniError> {
+        let (tx, rx) = mpsc::channel();
+        let preloaded: Vec<(SoundEffect, Option<DecodedSound>)> =
+            ALL_EFFECTS.iter().map(|&effect| (effect, preload(effect))).collect();
+
+        thread::Builder::new()
+            .name("vaani-sound".into())
+            .spawn(move || worker_loop(rx, preloaded))
+            .map_err(|e| VaaniError::Audio(format!("Failed to spawn sound-player thread: {e}")))?;
+
+        Ok(Self { commands: tx })
+    }
+
+    /// Play a preloaded sound effect.
+    pub fn play(&self, effect: SoundEffect) -> Result<(), VaaniError> {
+        self.commands
+            .send(SoundCommand::Play(effect))
+            .map_err(|_| VaaniError::Audio("Sound-player worker thread is not running".to_string()))
+    }
+
+    /// Decode and play audio bytes that weren't preloaded at startup.
+    pub fn play_bytes(&self, audio: Vec<u8>, label: &'static str) -> Result<(), VaaniError> {
+        self.commands
+            .send(SoundCommand::PlayBytes { audio, label })
+            .map_err(|_| VaaniError::Audio("Sound-player worker thread is not running".to_string()))
+    }
+}
+
+/// Runs on the dedicated sound-player thread. Owns the output stream and
+/// preloaded PCM for the life of the thread, so the `!Send` `OutputStream`
+/// never has to cross a thread boundary.
+fn worker_loop(commands: mpsc::Receiver<SoundCommand>, preloaded: Vec<(SoundEffect, Option<DecodedSound>)>) {
+    let stream = match rodio::OutputStream::try_default() {
+        Ok((stream, handle)) => Some((stream, handle)),
+        Err(e) => {
+            tracing::warn!("No audio output device, sound playback disabled: {e}");
+            None
+        }
+    };
+
+    for command in commands {
+        let Some((_stream, handle)) = &stream else {
+            tracing::debug!("No audio output device, skipping playback");
+            continue;
+        };
+
+        let sound = match command {
+            SoundCommand::Play(effect) => {
+                let cached = preloaded
+                    .iter()
+                    .find(|(e, _)| *e == effect)
+                    .and_then(|(_, sound)| sound.as_ref());
+                match cached {
+                    Some(sound) => Some((sound.channels, sound.sample_rate, sound.samples.clone())),
+                    None => {
+                        tracing::debug!(?effect, "No preloaded sound for effect, skipping playback");
+                        None
+                    }
                 }
-            };
+            }
+            SoundCommand::PlayBytes { audio, label } => {
+                decode(audio, label).map(|s| (s.channels, s.sample_rate, s.samples))
+            }
+        };
+
+        let Some((channels, sample_rate, samples)) = sound else {
+            continue;
+        };
+
+        let sink = match rodio::Sink::try_new(handle) {
+            Ok(sink) => sink,
+            Err(e) => {
+                tracing::warn!("Failed to create audio sink: {e}");
+                continue;
+            }
+        };
+
+        sink.append(rodio::buffer::SamplesBuffer::new(channels, sample_rate, samples));
+        // Let the sink keep playing after this loop iteration moves on to
+        // the next command, instead of blocking the worker until it ends.
+        sink.detach();
+    }
+}
 
-            sink.append(source);
-            sink.sleep_until_end();
+/// Returns the process-wide [`SoundPlayer`], spawning its worker thread
+/// (and preloading the bundled effects) on first use.
+fn global_player() -> &'static SoundPlayer {
+    static PLAYER: OnceLock<SoundPlayer> = OnceLock::new();
+    PLAYER.get_or_init(|| {
+        SoundPlayer::spawn().unwrap_or_else(|e| {
+            tracing::warn!("Failed to start sound player, sounds will be skipped: {e}");
+            // `spawn` only fails if the OS refuses to create a thread. Hand
+            // back a player whose channel has no receiver, so later
+            // `play`/`play_bytes` calls take the same disconnected-channel
+            // path as a missing device or file -- a logged warning, no-op.
+            let (commands, _rx) = mpsc::channel();
+            SoundPlayer { commands }
         })
-        .map_err(|e| VaaniError::Audio(format!("Failed to spawn sound thread: {e}")))?;
+    })
+}
+
+/// Play a sound effect asynchronously (non-blocking), via the process-wide
+/// [`SoundPlayer`].
+///
+/// If the sound file was missing at preload time or audio output is
+/// unavailable, logs a warning and returns Ok — sounds are optional.
+pub fn play_sound(effect: SoundEffect) -> Result<(), VaaniError> {
+    if let Err(e) = global_player().play(effect) {
+        tracing::warn!("Failed to queue sound playback: {e}");
+    }
+    tracing::debug!(?effect, "Playing sound");
+    Ok(())
+}
 
-    tracing::debug!(effect = ?effect, "Playing sound");
+/// Play raw audio bytes (any format `rodio`'s `Decoder` can sniff, e.g. WAV
+/// or MP3) asynchronously, via the process-wide [`SoundPlayer`].
+///
+/// `label` is only used in log messages to identify the caller (`"sound
+/// effect"`, `"tts"`, ...). If audio output is unavailable or the bytes
+/// can't be decoded, logs a warning rather than failing the caller —
+/// playback is always best-effort.
+pub fn play_bytes(audio: Vec<u8>, label: &'static str) -> Result<(), VaaniError> {
+    if let Err(e) = global_player().play_bytes(audio, label) {
+        tracing::warn!("Failed to queue {label} playback: {e}");
+    }
     Ok(())
 }
 
@@ -146,6 +322,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_candidate_returns_none_when_nothing_exists() {
+        let dir = tempfile::TempDir::new().expect("tempdir should create");
+        assert!(find_candidate(dir.path(), "record_start").is_none());
+    }
+
+    #[test]
+    fn find_candidate_finds_a_non_wav_theme_file() {
+        let dir = tempfile::TempDir::new().expect("tempdir should create");
+        std::fs::write(dir.path().join("record_start.ogg"), b"fake ogg bytes")
+            .expect("write should succeed");
+
+        let found = find_candidate(dir.path(), "record_start").expect("should find the .ogg file");
+        assert_eq!(found, dir.path().join("record_start.ogg"));
+    }
+
+    #[test]
+    fn find_candidate_prefers_wav_over_other_extensions() {
+        let dir = tempfile::TempDir::new().expect("tempdir should create");
+        std::fs::write(dir.path().join("record_start.ogg"), b"fake ogg bytes")
+            .expect("write should succeed");
+        std::fs::write(dir.path().join("record_start.wav"), b"fake wav bytes")
+            .expect("write should succeed");
+
+        let found = find_candidate(dir.path(), "record_start").expect("should find a file");
+        assert_eq!(found, dir.path().join("record_start.wav"));
+    }
+
     #[test]
     fn play_sound_if_enabled_false_returns_ok() {
         let result = play_sound_if_enabled(SoundEffect::RecordStart, false);
@@ -161,4 +365,35 @@ mod tests {
             "Missing sound file should not cause an error"
         );
     }
+
+    #[test]
+    fn play_bytes_spawns_even_with_undecodable_audio() {
+        // Decode failure is only logged on the worker thread, so a garbage
+        // buffer should still be accepted successfully.
+        let result = play_bytes(vec![0, 1, 2, 3], "test");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn sound_player_spawn_returns_a_usable_handle() {
+        let player = SoundPlayer::spawn().expect("spawn should succeed");
+        // With no sound files present in the test environment this is a
+        // no-op on the worker thread, but the send itself must still
+        // succeed — the worker is running and listening.
+        assert!(player.play(SoundEffect::RecordStart).is_ok());
+    }
+
+    #[test]
+    fn sound_player_play_bytes_sends_successfully() {
+        let player = SoundPlayer::spawn().expect("spawn should succeed");
+        assert!(player.play_bytes(vec![0, 1, 2, 3], "test").is_ok());
+    }
+
+    #[test]
+    fn dropping_all_handles_lets_worker_exit_without_panicking() {
+        let player = SoundPlayer::spawn().expect("spawn should succeed");
+        drop(player);
+        // Nothing to assert — the worker thread should exit cleanly once
+        // its receiver is disconnected, without panicking.
+    }
 }
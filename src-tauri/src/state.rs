@@ -1,13 +1,27 @@
 use std::fmt;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 
 use crate::error::VaaniError;
 
-/// The three states the Vaani app can be in.
+/// Default number of transitions [`StateMachine::history`] retains before it
+/// starts evicting the oldest entry. Override via
+/// [`StateMachine::with_history_capacity`].
+const DEFAULT_HISTORY_CAPACITY: usize = 20;
+
+/// The five states the Vaani app can be in.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppState {
     Idle,
     Recording,
+    /// Recording is suspended: the mic stream stays open but captured frames
+    /// are dropped until `resume_recording()` brings it back to `Recording`.
+    Paused,
+    /// Audio is being streamed to the transcriber while still recording;
+    /// interim hypotheses arrive via [`StateMachine::on_partial`] without
+    /// leaving this state. See [`StateMachine::start_streaming`].
+    Streaming,
     Processing,
 }
 
@@ -16,6 +30,8 @@ impl fmt::Display for AppState {
         match self {
             AppState::Idle => write!(f, "idle"),
             AppState::Recording => write!(f, "recording"),
+            AppState::Paused => write!(f, "paused"),
+            AppState::Streaming => write!(f, "streaming"),
             AppState::Processing => write!(f, "processing"),
         }
     }
@@ -25,6 +41,133 @@ impl fmt::Display for AppState {
 /// successful state change.
 type Listener = Box<dyn Fn(AppState, AppState) + Send>;
 
+/// A partial-result listener that is called with `(text, is_final)` for
+/// every interim transcription hypothesis received while [`AppState::Streaming`].
+type PartialListener = Box<dyn Fn(&str, bool) + Send>;
+
+/// A processing-progress listener, called with every [`ProgressEvent`]
+/// raised over the lifetime of a `Processing` episode.
+type ProgressListener = Box<dyn Fn(ProgressEvent) + Send>;
+
+/// A progress notification for a `Processing` episode, following the
+/// begin/report/end model long-running LSP servers use to drive a
+/// determinate progress UI.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressEvent {
+    /// Fired exactly once when a `Processing` episode starts.
+    Begin { title: String },
+    /// Fired any number of times while the episode is in progress.
+    /// `percentage` is clamped to `0..=100`.
+    Report {
+        percentage: Option<u8>,
+        message: Option<String>,
+    },
+    /// Fired exactly once when the episode ends (`finish_processing`).
+    End,
+}
+
+/// Shared progress-notification state for the `Processing` episode
+/// currently in flight, if any. Held both by the [`StateMachine`] (to fire
+/// `Begin`/`End`) and by the [`ProgressHandle`] it hands out (to fire
+/// `Report`), independently of whichever lock wraps the state machine
+/// itself.
+struct ProgressInner {
+    listeners: Mutex<Vec<ProgressListener>>,
+    /// Whether a `Processing` episode is currently active. A [`ProgressHandle`]
+    /// whose episode has since ended is a no-op rather than misreporting
+    /// into whatever episode comes next.
+    active: AtomicBool,
+    /// Bumped on every `Begin`, so a stale handle from a prior episode can
+    /// tell it no longer owns the current one.
+    episode: AtomicU64,
+}
+
+impl ProgressInner {
+    fn new() -> Self {
+        Self {
+            listeners: Mutex::new(Vec::new()),
+            active: AtomicBool::new(false),
+            episode: AtomicU64::new(0),
+        }
+    }
+
+    /// Fires all registered progress listeners, catching panics so a
+    /// misbehaving listener cannot corrupt the state machine.
+    fn notify(&self, event: ProgressEvent) {
+        let listeners = self.listeners.lock().unwrap_or_else(|e| e.into_inner());
+        for (i, listener) in listeners.iter().enumerate() {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                listener(event.clone());
+            }));
+
+            if let Err(panic_info) = result {
+                let msg = if let Some(s) = panic_info.downcast_ref::<&str>() {
+                    (*s).to_string()
+                } else if let Some(s) = panic_info.downcast_ref::<String>() {
+                    s.clone()
+                } else {
+                    "unknown panic".to_string()
+                };
+                tracing::error!(
+                    listener_index = i,
+                    panic_message = %msg,
+                    "progress listener panicked"
+                );
+            }
+        }
+    }
+}
+
+/// A handle to a single `Processing` episode, handed out by
+/// [`StateMachine::stop_recording`] / [`StateMachine::stop_streaming`].
+///
+/// Call [`report`](Self::report) as work progresses; the episode ends
+/// implicitly (firing [`ProgressEvent::End`]) when
+/// [`StateMachine::finish_processing`] is called. Reports made after that
+/// point, or after a newer episode has started, are silently dropped.
+pub struct ProgressHandle {
+    inner: Arc<ProgressInner>,
+    episode: u64,
+}
+
+impl fmt::Debug for ProgressHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressHandle")
+            .field("episode", &self.episode)
+            .finish()
+    }
+}
+
+impl ProgressHandle {
+    /// Reports progress: `fraction` is clamped to `0.0..=1.0` and converted
+    /// to a `0..=100` percentage. A no-op once this handle's episode has
+    /// ended.
+    pub fn report(&self, fraction: f32, message: &str) {
+        if !self.inner.active.load(Ordering::SeqCst)
+            || self.inner.episode.load(Ordering::SeqCst) != self.episode
+        {
+            return;
+        }
+
+        let percentage = Some((fraction.clamp(0.0, 1.0) * 100.0).round() as u8);
+        self.inner.notify(ProgressEvent::Report {
+            percentage,
+            message: Some(message.to_string()),
+        });
+    }
+}
+
+/// One recorded state transition, for reconstructing what happened in bug
+/// reports like "it got stuck in Processing". See
+/// [`StateMachine::history`].
+#[derive(Debug, Clone)]
+pub struct TransitionRecord {
+    pub from: AppState,
+    pub to: AppState,
+    pub action: String,
+    pub at: Instant,
+}
+
 /// Manages Vaani's application state with validated transitions and listener
 /// callbacks.
 ///
@@ -36,27 +179,87 @@ type Listener = Box<dyn Fn(AppState, AppState) + Send>;
 pub struct StateMachine {
     state: AppState,
     listeners: Vec<Listener>,
+    partial_listeners: Vec<PartialListener>,
+    progress: Arc<ProgressInner>,
+    /// Ring buffer of the most recent successful transitions, oldest first.
+    /// See [`history`](Self::history).
+    history: Vec<TransitionRecord>,
+    history_capacity: usize,
 }
 
 impl fmt::Debug for StateMachine {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let last_transition = self
+            .history
+            .last()
+            .map(|r| format!("{}->{}", r.from, r.to))
+            .unwrap_or_else(|| "none".to_string());
+
         f.debug_struct("StateMachine")
             .field("state", &self.state)
             .field(
                 "listeners",
                 &format!("[{} listener(s)]", self.listeners.len()),
             )
+            .field(
+                "partial_listeners",
+                &format!("[{} listener(s)]", self.partial_listeners.len()),
+            )
+            .field("last transition", &last_transition)
             .finish()
     }
 }
 
 impl StateMachine {
-    /// Creates a new state machine starting in `Idle`.
+    /// Creates a new state machine starting in `Idle`, retaining up to
+    /// [`DEFAULT_HISTORY_CAPACITY`] transitions in [`history`](Self::history).
     pub fn new() -> Self {
         Self {
             state: AppState::Idle,
             listeners: Vec::new(),
+            partial_listeners: Vec::new(),
+            progress: Arc::new(ProgressInner::new()),
+            history: Vec::new(),
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
+        }
+    }
+
+    /// Like [`new`](Self::new), but retains up to `capacity` transitions in
+    /// [`history`](Self::history) instead of the default.
+    pub fn with_history_capacity(capacity: usize) -> Self {
+        Self {
+            history_capacity: capacity,
+            ..Self::new()
+        }
+    }
+
+    /// Returns the most recent successful transitions, oldest first, capped
+    /// at this machine's history capacity. Useful for reconstructing what
+    /// happened leading up to a bug report.
+    pub fn history(&self) -> &[TransitionRecord] {
+        &self.history
+    }
+
+    /// Discards all recorded transition history.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Appends a transition to [`history`](Self::history), evicting the
+    /// oldest entry if over capacity. A capacity of `0` disables history.
+    fn record_transition(&mut self, from: AppState, to: AppState, action: &str) {
+        if self.history_capacity == 0 {
+            return;
+        }
+        if self.history.len() >= self.history_capacity {
+            self.history.remove(0);
         }
+        self.history.push(TransitionRecord {
+            from,
+            to,
+            action: action.to_string(),
+            at: Instant::now(),
+        });
     }
 
     /// Returns the current state.
@@ -74,6 +277,16 @@ impl StateMachine {
         self.state == AppState::Recording
     }
 
+    /// Returns `true` if the current state is `Paused`.
+    pub fn is_paused(&self) -> bool {
+        self.state == AppState::Paused
+    }
+
+    /// Returns `true` if the current state is `Streaming`.
+    pub fn is_streaming(&self) -> bool {
+        self.state == AppState::Streaming
+    }
+
     /// Returns `true` if the current state is `Processing`.
     pub fn is_processing(&self) -> bool {
         self.state == AppState::Processing
@@ -88,27 +301,138 @@ impl StateMachine {
         self.listeners.push(listener);
     }
 
+    /// Registers a listener that will be called with every interim
+    /// transcription hypothesis received while [`AppState::Streaming`]. The
+    /// listener receives `(text, is_final)`.
+    ///
+    /// If a listener panics, the panic is caught and logged, exactly like
+    /// [`on_transition`](Self::on_transition) listeners.
+    pub fn on_partial(&mut self, listener: PartialListener) {
+        self.partial_listeners.push(listener);
+    }
+
+    /// Registers a listener that will be called with every [`ProgressEvent`]
+    /// raised over the lifetime of each `Processing` episode: one `Begin`,
+    /// any number of `Report`s, one `End`.
+    ///
+    /// If a listener panics, the panic is caught and logged, exactly like
+    /// [`on_transition`](Self::on_transition) listeners.
+    pub fn on_progress(&mut self, listener: ProgressListener) {
+        self.progress
+            .listeners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(listener);
+    }
+
     /// Attempts to transition from `Idle` to `Recording`.
     pub fn start_recording(&mut self) -> Result<(), VaaniError> {
         self.transition(AppState::Idle, AppState::Recording, "start recording")
     }
 
-    /// Attempts to transition from `Recording` to `Processing` (stop recording
-    /// and begin transcription/enhancement).
-    pub fn stop_recording(&mut self) -> Result<(), VaaniError> {
-        self.transition(AppState::Recording, AppState::Processing, "stop recording")
+    /// Attempts to transition from `Recording` or `Paused` to `Processing`
+    /// (stop recording and begin transcription/enhancement). On success,
+    /// returns a [`ProgressHandle`] for reporting progress on the episode
+    /// this transition just began.
+    pub fn stop_recording(&mut self) -> Result<ProgressHandle, VaaniError> {
+        self.transition_from_any(
+            &[AppState::Recording, AppState::Paused],
+            AppState::Processing,
+            "stop recording",
+        )?;
+        Ok(self.begin_progress("Processing"))
     }
 
-    /// Attempts to transition from `Recording` back to `Idle` (cancel without
-    /// processing).
+    /// Attempts to transition from `Recording`, `Paused`, or `Streaming`
+    /// back to `Idle` (cancel without processing). Interim partial results
+    /// never fire again after this: [`emit_partial`](Self::emit_partial)
+    /// only invokes listeners while the state is still `Streaming`.
     pub fn cancel_recording(&mut self) -> Result<(), VaaniError> {
-        self.transition(AppState::Recording, AppState::Idle, "cancel recording")
+        self.transition_from_any(
+            &[AppState::Recording, AppState::Paused, AppState::Streaming],
+            AppState::Idle,
+            "cancel recording",
+        )
+    }
+
+    /// Attempts to transition from `Recording` to `Paused`. The mic stream
+    /// stays open; the audio module is expected to keep capturing but drop
+    /// frames while paused.
+    pub fn pause_recording(&mut self) -> Result<(), VaaniError> {
+        self.transition(AppState::Recording, AppState::Paused, "pause recording")
+    }
+
+    /// Attempts to transition from `Paused` back to `Recording`.
+    pub fn resume_recording(&mut self) -> Result<(), VaaniError> {
+        self.transition(AppState::Paused, AppState::Recording, "resume recording")
     }
 
     /// Attempts to transition from `Processing` back to `Idle` (processing
-    /// complete or failed).
+    /// complete or failed). Implicitly ends the current episode's progress
+    /// reporting, firing [`ProgressEvent::End`] to every
+    /// [`on_progress`](Self::on_progress) listener.
     pub fn finish_processing(&mut self) -> Result<(), VaaniError> {
-        self.transition(AppState::Processing, AppState::Idle, "finish processing")
+        self.transition(AppState::Processing, AppState::Idle, "finish processing")?;
+        self.end_progress();
+        Ok(())
+    }
+
+    /// Attempts to transition from `Recording` to `Streaming`: audio keeps
+    /// being captured, but is now also streamed to the transcriber, which
+    /// will report interim hypotheses via [`emit_partial`](Self::emit_partial).
+    pub fn start_streaming(&mut self) -> Result<(), VaaniError> {
+        self.transition(AppState::Recording, AppState::Streaming, "start streaming")
+    }
+
+    /// Attempts to transition from `Streaming` to `Processing`, flushing the
+    /// transcriber for a final result. On success, returns a
+    /// [`ProgressHandle`] for this episode, like [`stop_recording`](Self::stop_recording).
+    pub fn stop_streaming(&mut self) -> Result<ProgressHandle, VaaniError> {
+        self.transition(AppState::Streaming, AppState::Processing, "stop streaming")?;
+        Ok(self.begin_progress("Processing"))
+    }
+
+    /// Delivers an interim transcription hypothesis to every listener
+    /// registered via [`on_partial`](Self::on_partial), without changing
+    /// state. Only valid while [`AppState::Streaming`]; called from any
+    /// other state (e.g. after a cancel) this is a no-op error instead of a
+    /// silent drop, so a caller that keeps streaming after cancellation
+    /// notices rather than leaking stale partials into listeners.
+    pub fn emit_partial(&mut self, text: &str, is_final: bool) -> Result<(), VaaniError> {
+        if self.state != AppState::Streaming {
+            return Err(VaaniError::InvalidTransition {
+                action: "emit partial result".to_string(),
+                state: self.state.to_string(),
+            });
+        }
+
+        self.notify_partial_listeners(text, is_final);
+
+        Ok(())
+    }
+
+    /// Starts a new progress episode: bumps the episode counter (so any
+    /// handle from a prior episode stops reporting), marks progress active,
+    /// fires [`ProgressEvent::Begin`], and returns a handle scoped to this
+    /// episode.
+    fn begin_progress(&mut self, title: &str) -> ProgressHandle {
+        let episode = self.progress.episode.fetch_add(1, Ordering::SeqCst) + 1;
+        self.progress.active.store(true, Ordering::SeqCst);
+        self.progress.notify(ProgressEvent::Begin {
+            title: title.to_string(),
+        });
+
+        ProgressHandle {
+            inner: Arc::clone(&self.progress),
+            episode,
+        }
+    }
+
+    /// Ends the current progress episode: marks progress inactive (so its
+    /// handle stops reporting) and fires [`ProgressEvent::End`].
+    fn end_progress(&mut self) {
+        self.progress.active.store(false, Ordering::SeqCst);
+        self.progress.notify(ProgressEvent::End);
     }
 
     /// Core transition logic. Validates that the current state matches
@@ -128,6 +452,35 @@ impl StateMachine {
 
         let old = self.state;
         self.state = next;
+        self.record_transition(old, next, action);
+
+        tracing::info!(from = %old, to = %next, "state transition: {action}");
+
+        self.notify_listeners(old, next);
+
+        Ok(())
+    }
+
+    /// Like [`transition`](Self::transition), but accepts any of `expected`
+    /// as the current state rather than a single one. Used where more than
+    /// one state may validly lead to `next` (e.g. both `Recording` and
+    /// `Paused` can stop or cancel).
+    fn transition_from_any(
+        &mut self,
+        expected: &[AppState],
+        next: AppState,
+        action: &str,
+    ) -> Result<(), VaaniError> {
+        if !expected.contains(&self.state) {
+            return Err(VaaniError::InvalidTransition {
+                action: action.to_string(),
+                state: self.state.to_string(),
+            });
+        }
+
+        let old = self.state;
+        self.state = next;
+        self.record_transition(old, next, action);
 
         tracing::info!(from = %old, to = %next, "state transition: {action}");
 
@@ -160,6 +513,31 @@ impl StateMachine {
             }
         }
     }
+
+    /// Fires all registered partial-result listeners, catching panics so a
+    /// misbehaving listener cannot corrupt the state machine.
+    fn notify_partial_listeners(&self, text: &str, is_final: bool) {
+        for (i, listener) in self.partial_listeners.iter().enumerate() {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                listener(text, is_final);
+            }));
+
+            if let Err(panic_info) = result {
+                let msg = if let Some(s) = panic_info.downcast_ref::<&str>() {
+                    (*s).to_string()
+                } else if let Some(s) = panic_info.downcast_ref::<String>() {
+                    s.clone()
+                } else {
+                    "unknown panic".to_string()
+                };
+                tracing::error!(
+                    listener_index = i,
+                    panic_message = %msg,
+                    "partial result listener panicked"
+                );
+            }
+        }
+    }
 }
 
 impl Default for StateMachine {
@@ -168,8 +546,143 @@ impl Default for StateMachine {
     }
 }
 
-/// Convenience type alias for thread-safe shared ownership of a `StateMachine`.
-pub type SharedStateMachine = Mutex<StateMachine>;
+/// Thread-safe shared ownership of a `StateMachine`, paired with a
+/// [`Condvar`] so callers can [`wait_for`](Self::wait_for) a target state
+/// instead of polling `current()` behind the mutex.
+///
+/// Every transition method notifies all waiters once the transition (and
+/// its listeners) has completed, mirroring the condvar-coordinated handoff
+/// used elsewhere for toggle-recording.
+pub struct SharedStateMachine {
+    state: Mutex<StateMachine>,
+    condvar: Condvar,
+}
+
+impl SharedStateMachine {
+    /// Wraps `state` for shared, waitable access.
+    pub fn new(state: StateMachine) -> Self {
+        Self {
+            state: Mutex::new(state),
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Locks the inner state machine for direct access — e.g. to register
+    /// listeners or read `current()`.
+    pub fn lock(&self) -> MutexGuard<'_, StateMachine> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Blocks the calling thread until the state machine reaches `target`
+    /// or, failing that, settles in the terminal [`AppState::Idle`] state
+    /// (e.g. because the operation was cancelled instead of completing) —
+    /// without this, waiting for a state an in-flight cancel makes
+    /// unreachable would block forever. Returns the state actually reached,
+    /// so callers can tell a cancel-to-idle apart from reaching `target`.
+    ///
+    /// With `timeout: None` this blocks indefinitely. With `timeout:
+    /// Some(_)`, returns [`VaaniError::WaitTimeout`] if neither `target`
+    /// nor `Idle` is reached before it elapses.
+    ///
+    /// # Spurious wakeups
+    ///
+    /// `Condvar::wait`/`wait_timeout` may wake spuriously per the platform;
+    /// this loops internally (via `wait_while`/`wait_timeout_while`) so a
+    /// spurious wakeup is never mistaken for reaching `target`.
+    pub fn wait_for(
+        &self,
+        target: AppState,
+        timeout: Option<Duration>,
+    ) -> Result<AppState, VaaniError> {
+        let guard = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let reached = |sm: &StateMachine| sm.current() == target || sm.current() == AppState::Idle;
+
+        if reached(&guard) {
+            return Ok(guard.current());
+        }
+
+        match timeout {
+            None => {
+                let guard = self
+                    .condvar
+                    .wait_while(guard, |sm| !reached(sm))
+                    .unwrap_or_else(|e| e.into_inner());
+                Ok(guard.current())
+            }
+            Some(timeout) => {
+                let (guard, wait_result) = self
+                    .condvar
+                    .wait_timeout_while(guard, timeout, |sm| !reached(sm))
+                    .unwrap_or_else(|e| e.into_inner());
+                if wait_result.timed_out() {
+                    Err(VaaniError::WaitTimeout(timeout, target.to_string()))
+                } else {
+                    Ok(guard.current())
+                }
+            }
+        }
+    }
+
+    /// Runs `f` against the locked state machine, notifying all
+    /// [`wait_for`](Self::wait_for) callers once it succeeds. Shared by
+    /// every transition method below.
+    fn transition_and_notify<T>(
+        &self,
+        f: impl FnOnce(&mut StateMachine) -> Result<T, VaaniError>,
+    ) -> Result<T, VaaniError> {
+        let mut guard = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let result = f(&mut guard);
+        if result.is_ok() {
+            self.condvar.notify_all();
+        }
+        result
+    }
+
+    /// See [`StateMachine::start_recording`].
+    pub fn start_recording(&self) -> Result<(), VaaniError> {
+        self.transition_and_notify(StateMachine::start_recording)
+    }
+
+    /// See [`StateMachine::stop_recording`].
+    pub fn stop_recording(&self) -> Result<ProgressHandle, VaaniError> {
+        self.transition_and_notify(StateMachine::stop_recording)
+    }
+
+    /// See [`StateMachine::cancel_recording`].
+    pub fn cancel_recording(&self) -> Result<(), VaaniError> {
+        self.transition_and_notify(StateMachine::cancel_recording)
+    }
+
+    /// See [`StateMachine::pause_recording`].
+    pub fn pause_recording(&self) -> Result<(), VaaniError> {
+        self.transition_and_notify(StateMachine::pause_recording)
+    }
+
+    /// See [`StateMachine::resume_recording`].
+    pub fn resume_recording(&self) -> Result<(), VaaniError> {
+        self.transition_and_notify(StateMachine::resume_recording)
+    }
+
+    /// See [`StateMachine::start_streaming`].
+    pub fn start_streaming(&self) -> Result<(), VaaniError> {
+        self.transition_and_notify(StateMachine::start_streaming)
+    }
+
+    /// See [`StateMachine::stop_streaming`].
+    pub fn stop_streaming(&self) -> Result<ProgressHandle, VaaniError> {
+        self.transition_and_notify(StateMachine::stop_streaming)
+    }
+
+    /// See [`StateMachine::emit_partial`].
+    pub fn emit_partial(&self, text: &str, is_final: bool) -> Result<(), VaaniError> {
+        self.transition_and_notify(|sm| sm.emit_partial(text, is_final))
+    }
+
+    /// See [`StateMachine::finish_processing`].
+    pub fn finish_processing(&self) -> Result<(), VaaniError> {
+        self.transition_and_notify(StateMachine::finish_processing)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -222,6 +735,34 @@ mod tests {
         assert_eq!(sm.current(), AppState::Idle);
     }
 
+    #[test]
+    fn valid_transition_recording_to_paused_and_back() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        sm.pause_recording().unwrap();
+        assert_eq!(sm.current(), AppState::Paused);
+        sm.resume_recording().unwrap();
+        assert_eq!(sm.current(), AppState::Recording);
+    }
+
+    #[test]
+    fn valid_transition_paused_to_processing() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        sm.pause_recording().unwrap();
+        sm.stop_recording().unwrap();
+        assert_eq!(sm.current(), AppState::Processing);
+    }
+
+    #[test]
+    fn valid_transition_paused_to_idle_cancel() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        sm.pause_recording().unwrap();
+        sm.cancel_recording().unwrap();
+        assert_eq!(sm.current(), AppState::Idle);
+    }
+
     // -----------------------------------------------------------------------
     // 3. Invalid transitions
     // -----------------------------------------------------------------------
@@ -271,6 +812,33 @@ mod tests {
         assert_eq!(sm.current(), AppState::Processing);
     }
 
+    #[test]
+    fn invalid_transition_idle_to_paused() {
+        let mut sm = StateMachine::new();
+        let err = sm.pause_recording().unwrap_err();
+        assert!(err.to_string().contains("idle"));
+        assert_eq!(sm.current(), AppState::Idle);
+    }
+
+    #[test]
+    fn invalid_transition_paused_to_paused() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        sm.pause_recording().unwrap();
+        let err = sm.pause_recording().unwrap_err();
+        assert!(err.to_string().contains("paused"));
+        assert_eq!(sm.current(), AppState::Paused);
+    }
+
+    #[test]
+    fn invalid_transition_recording_to_recording_via_resume() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        let err = sm.resume_recording().unwrap_err();
+        assert!(err.to_string().contains("recording"));
+        assert_eq!(sm.current(), AppState::Recording);
+    }
+
     // -----------------------------------------------------------------------
     // 4. Helper methods
     // -----------------------------------------------------------------------
@@ -281,18 +849,29 @@ mod tests {
         // Idle
         assert!(sm.is_idle());
         assert!(!sm.is_recording());
+        assert!(!sm.is_paused());
         assert!(!sm.is_processing());
 
         // Recording
         sm.start_recording().unwrap();
         assert!(!sm.is_idle());
         assert!(sm.is_recording());
+        assert!(!sm.is_paused());
         assert!(!sm.is_processing());
 
-        // Processing
+        // Paused
+        sm.pause_recording().unwrap();
+        assert!(!sm.is_idle());
+        assert!(!sm.is_recording());
+        assert!(sm.is_paused());
+        assert!(!sm.is_processing());
+
+        // Back to Recording, then Processing
+        sm.resume_recording().unwrap();
         sm.stop_recording().unwrap();
         assert!(!sm.is_idle());
         assert!(!sm.is_recording());
+        assert!(!sm.is_paused());
         assert!(sm.is_processing());
     }
 
@@ -437,6 +1016,8 @@ mod tests {
     fn app_state_display() {
         assert_eq!(AppState::Idle.to_string(), "idle");
         assert_eq!(AppState::Recording.to_string(), "recording");
+        assert_eq!(AppState::Paused.to_string(), "paused");
+        assert_eq!(AppState::Streaming.to_string(), "streaming");
         assert_eq!(AppState::Processing.to_string(), "processing");
     }
 
@@ -450,4 +1031,424 @@ mod tests {
         assert!(debug.contains("Idle"));
         assert!(debug.contains("0 listener(s)"));
     }
+
+    // -----------------------------------------------------------------------
+    // Streaming transcription
+    // -----------------------------------------------------------------------
+    #[test]
+    fn valid_transition_recording_to_streaming() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        sm.start_streaming().unwrap();
+        assert_eq!(sm.current(), AppState::Streaming);
+        assert!(sm.is_streaming());
+    }
+
+    #[test]
+    fn valid_transition_streaming_to_processing() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        sm.start_streaming().unwrap();
+        sm.stop_streaming().unwrap();
+        assert_eq!(sm.current(), AppState::Processing);
+    }
+
+    #[test]
+    fn valid_transition_streaming_to_idle_cancel() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        sm.start_streaming().unwrap();
+        sm.cancel_recording().unwrap();
+        assert_eq!(sm.current(), AppState::Idle);
+    }
+
+    #[test]
+    fn invalid_transition_idle_to_streaming() {
+        let mut sm = StateMachine::new();
+        let err = sm.start_streaming().unwrap_err();
+        assert!(err.to_string().contains("idle"));
+        assert_eq!(sm.current(), AppState::Idle);
+    }
+
+    #[test]
+    fn invalid_transition_paused_to_streaming() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        sm.pause_recording().unwrap();
+        let err = sm.start_streaming().unwrap_err();
+        assert!(err.to_string().contains("paused"));
+        assert_eq!(sm.current(), AppState::Paused);
+    }
+
+    #[test]
+    fn partial_listener_fires_repeatedly_without_changing_state() {
+        let log = Arc::new(Mutex::new(Vec::<(String, bool)>::new()));
+        let log_clone = Arc::clone(&log);
+
+        let mut sm = StateMachine::new();
+        sm.on_partial(Box::new(move |text, is_final| {
+            log_clone.lock().unwrap().push((text.to_string(), is_final));
+        }));
+
+        sm.start_recording().unwrap();
+        sm.start_streaming().unwrap();
+        sm.emit_partial("hel", false).unwrap();
+        sm.emit_partial("hello", false).unwrap();
+        sm.emit_partial("hello world", true).unwrap();
+
+        assert_eq!(sm.current(), AppState::Streaming);
+        let partials = log.lock().unwrap();
+        assert_eq!(partials.len(), 3);
+        assert_eq!(partials[2], ("hello world".to_string(), true));
+    }
+
+    #[test]
+    fn emit_partial_outside_streaming_is_rejected() {
+        let mut sm = StateMachine::new();
+        let err = sm.emit_partial("stray", false).unwrap_err();
+        assert!(err.to_string().contains("idle"));
+    }
+
+    #[test]
+    fn partial_results_never_leak_after_cancel() {
+        let call_count = Arc::new(AtomicUsize::new(0));
+        let count_clone = Arc::clone(&call_count);
+
+        let mut sm = StateMachine::new();
+        sm.on_partial(Box::new(move |_, _| {
+            count_clone.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        sm.start_recording().unwrap();
+        sm.start_streaming().unwrap();
+        sm.emit_partial("partial", false).unwrap();
+        sm.cancel_recording().unwrap();
+
+        // Once cancelled, the state is no longer Streaming, so further
+        // partials are rejected rather than silently notifying listeners.
+        assert!(sm.emit_partial("late partial", true).is_err());
+        assert_eq!(call_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn panicking_partial_listener_does_not_corrupt_state() {
+        let survived = Arc::new(AtomicUsize::new(0));
+        let survived_clone = Arc::clone(&survived);
+
+        let mut sm = StateMachine::new();
+        sm.on_partial(Box::new(|_, _| {
+            panic!("boom");
+        }));
+        sm.on_partial(Box::new(move |_, _| {
+            survived_clone.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        sm.start_recording().unwrap();
+        sm.start_streaming().unwrap();
+        sm.emit_partial("text", false).unwrap();
+
+        assert_eq!(sm.current(), AppState::Streaming);
+        assert_eq!(survived.load(Ordering::SeqCst), 1);
+    }
+
+    // -----------------------------------------------------------------------
+    // Processing progress reporting
+    // -----------------------------------------------------------------------
+    #[test]
+    fn progress_brackets_processing_with_exactly_one_begin_and_end() {
+        let events = Arc::new(Mutex::new(Vec::<ProgressEvent>::new()));
+        let events_clone = Arc::clone(&events);
+
+        let mut sm = StateMachine::new();
+        sm.on_progress(Box::new(move |event| {
+            events_clone.lock().unwrap().push(event);
+        }));
+
+        sm.start_recording().unwrap();
+        let handle = sm.stop_recording().unwrap();
+        handle.report(0.5, "halfway");
+        sm.finish_processing().unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0],
+            ProgressEvent::Begin {
+                title: "Processing".to_string()
+            }
+        );
+        assert_eq!(
+            events[1],
+            ProgressEvent::Report {
+                percentage: Some(50),
+                message: Some("halfway".to_string())
+            }
+        );
+        assert_eq!(events[2], ProgressEvent::End);
+    }
+
+    #[test]
+    fn progress_percentage_is_clamped_to_0_100() {
+        let events = Arc::new(Mutex::new(Vec::<ProgressEvent>::new()));
+        let events_clone = Arc::clone(&events);
+
+        let mut sm = StateMachine::new();
+        sm.on_progress(Box::new(move |event| {
+            events_clone.lock().unwrap().push(event);
+        }));
+
+        sm.start_recording().unwrap();
+        let handle = sm.stop_recording().unwrap();
+        handle.report(-1.0, "below zero");
+        handle.report(4.0, "over one");
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            events[1],
+            ProgressEvent::Report {
+                percentage: Some(0),
+                message: Some("below zero".to_string())
+            }
+        );
+        assert_eq!(
+            events[2],
+            ProgressEvent::Report {
+                percentage: Some(100),
+                message: Some("over one".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn progress_handle_is_inert_after_finish_processing() {
+        let report_count = Arc::new(AtomicUsize::new(0));
+        let count_clone = Arc::clone(&report_count);
+
+        let mut sm = StateMachine::new();
+        sm.on_progress(Box::new(move |event| {
+            if matches!(event, ProgressEvent::Report { .. }) {
+                count_clone.fetch_add(1, Ordering::SeqCst);
+            }
+        }));
+
+        sm.start_recording().unwrap();
+        let handle = sm.stop_recording().unwrap();
+        sm.finish_processing().unwrap();
+
+        // The episode already ended; this report is dropped, not misfired
+        // into whatever comes next.
+        handle.report(0.9, "too late");
+        assert_eq!(report_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn stale_progress_handle_does_not_leak_into_next_episode() {
+        let events = Arc::new(Mutex::new(Vec::<ProgressEvent>::new()));
+        let events_clone = Arc::clone(&events);
+
+        let mut sm = StateMachine::new();
+        sm.on_progress(Box::new(move |event| {
+            events_clone.lock().unwrap().push(event);
+        }));
+
+        sm.start_recording().unwrap();
+        let stale_handle = sm.stop_recording().unwrap();
+        sm.finish_processing().unwrap();
+
+        sm.start_recording().unwrap();
+        let _current_handle = sm.stop_recording().unwrap();
+        stale_handle.report(0.5, "from the first episode");
+
+        let events = events.lock().unwrap();
+        // Begin, End, Begin — the stale handle's report never lands.
+        assert_eq!(events.len(), 3);
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, ProgressEvent::Report { .. })));
+    }
+
+    #[test]
+    fn progress_brackets_streaming_finalize_too() {
+        let events = Arc::new(Mutex::new(Vec::<ProgressEvent>::new()));
+        let events_clone = Arc::clone(&events);
+
+        let mut sm = StateMachine::new();
+        sm.on_progress(Box::new(move |event| {
+            events_clone.lock().unwrap().push(event);
+        }));
+
+        sm.start_recording().unwrap();
+        sm.start_streaming().unwrap();
+        let handle = sm.stop_streaming().unwrap();
+        handle.report(1.0, "done");
+        sm.finish_processing().unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], ProgressEvent::Begin { .. }));
+        assert_eq!(events[2], ProgressEvent::End);
+    }
+
+    // -----------------------------------------------------------------------
+    // Transition history (diagnostics)
+    // -----------------------------------------------------------------------
+    #[test]
+    fn history_starts_empty() {
+        let sm = StateMachine::new();
+        assert!(sm.history().is_empty());
+    }
+
+    #[test]
+    fn history_records_successive_transitions_in_order() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        sm.stop_recording().unwrap();
+        sm.finish_processing().unwrap();
+
+        let history = sm.history();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].from, AppState::Idle);
+        assert_eq!(history[0].to, AppState::Recording);
+        assert_eq!(history[0].action, "start recording");
+        assert_eq!(history[1].to, AppState::Processing);
+        assert_eq!(history[2].to, AppState::Idle);
+    }
+
+    #[test]
+    fn history_is_not_recorded_for_invalid_transitions() {
+        let mut sm = StateMachine::new();
+        let _ = sm.stop_recording();
+        assert!(sm.history().is_empty());
+    }
+
+    #[test]
+    fn history_evicts_oldest_entry_once_over_capacity() {
+        let mut sm = StateMachine::with_history_capacity(2);
+        sm.start_recording().unwrap(); // idle -> recording
+        sm.pause_recording().unwrap(); // recording -> paused
+        sm.resume_recording().unwrap(); // paused -> recording
+
+        let history = sm.history();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].action, "pause recording");
+        assert_eq!(history[1].action, "resume recording");
+    }
+
+    #[test]
+    fn zero_capacity_history_records_nothing() {
+        let mut sm = StateMachine::with_history_capacity(0);
+        sm.start_recording().unwrap();
+        assert!(sm.history().is_empty());
+    }
+
+    #[test]
+    fn clear_history_empties_the_buffer() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        assert!(!sm.history().is_empty());
+
+        sm.clear_history();
+        assert!(sm.history().is_empty());
+    }
+
+    #[test]
+    fn debug_impl_summarizes_last_transition() {
+        let mut sm = StateMachine::new();
+        sm.start_recording().unwrap();
+        sm.stop_recording().unwrap();
+
+        let debug = format!("{:?}", sm);
+        assert!(debug.contains("recording->processing"));
+    }
+
+    #[test]
+    fn debug_impl_reports_none_before_any_transition() {
+        let sm = StateMachine::new();
+        let debug = format!("{:?}", sm);
+        assert!(debug.contains("none"));
+    }
+
+    // -----------------------------------------------------------------------
+    // SharedStateMachine: wait_for and notify-on-transition
+    // -----------------------------------------------------------------------
+    #[test]
+    fn wait_for_returns_immediately_if_already_at_target() {
+        let shared = SharedStateMachine::new(StateMachine::new());
+        let reached = shared.wait_for(AppState::Idle, Some(Duration::from_millis(50)));
+        assert_eq!(reached.unwrap(), AppState::Idle);
+    }
+
+    #[test]
+    fn wait_for_times_out_if_target_is_never_reached() {
+        let shared = SharedStateMachine::new(StateMachine::new());
+        shared.start_recording().unwrap();
+
+        let err = shared
+            .wait_for(AppState::Processing, Some(Duration::from_millis(20)))
+            .unwrap_err();
+        assert!(err.to_string().contains("Timed out"));
+    }
+
+    #[test]
+    fn wait_for_wakes_once_another_thread_reaches_target() {
+        let shared = Arc::new(SharedStateMachine::new(StateMachine::new()));
+        shared.start_recording().unwrap();
+
+        let waiter = Arc::clone(&shared);
+        let handle = std::thread::spawn(move || waiter.wait_for(AppState::Processing, None));
+
+        // Give the waiter a moment to block, then drive the transition.
+        std::thread::sleep(Duration::from_millis(20));
+        shared.stop_recording().unwrap();
+
+        assert_eq!(handle.join().unwrap().unwrap(), AppState::Processing);
+    }
+
+    #[test]
+    fn wait_for_unblocks_on_fallback_to_idle_instead_of_hanging() {
+        let shared = Arc::new(SharedStateMachine::new(StateMachine::new()));
+        shared.start_recording().unwrap();
+
+        // Waiting for Processing, but the recording gets cancelled instead —
+        // without the Idle fallback this would block until the timeout.
+        let waiter = Arc::clone(&shared);
+        let handle = std::thread::spawn(move || waiter.wait_for(AppState::Processing, None));
+
+        std::thread::sleep(Duration::from_millis(20));
+        shared.cancel_recording().unwrap();
+
+        assert_eq!(handle.join().unwrap().unwrap(), AppState::Idle);
+    }
+
+    #[test]
+    fn shared_state_machine_transitions_forward_to_the_inner_machine() {
+        let shared = SharedStateMachine::new(StateMachine::new());
+        shared.start_recording().unwrap();
+        assert_eq!(shared.lock().current(), AppState::Recording);
+
+        let err = shared.start_recording().unwrap_err();
+        assert!(err.to_string().contains("recording"));
+    }
+
+    #[test]
+    fn shared_state_machine_emit_partial_forwards_to_the_inner_machine() {
+        let log = Arc::new(Mutex::new(Vec::<(String, bool)>::new()));
+        let log_clone = Arc::clone(&log);
+
+        let shared = SharedStateMachine::new(StateMachine::new());
+        shared
+            .lock()
+            .on_partial(Box::new(move |text, is_final| {
+                log_clone.lock().unwrap().push((text.to_string(), is_final));
+            }));
+
+        shared.start_recording().unwrap();
+        shared.start_streaming().unwrap();
+        shared.emit_partial("hello", true).unwrap();
+
+        assert_eq!(
+            log.lock().unwrap().as_slice(),
+            [("hello".to_string(), true)]
+        );
+    }
 }
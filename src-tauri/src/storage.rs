@@ -1,24 +1,81 @@
 //! Encrypted history storage using SQLite + AES-256-GCM.
 //!
-//! Text fields (`original_text`, `enhanced_text`) are encrypted at rest.
+//! Text fields (`original_text`, `enhanced_text`) are encrypted at rest
+//! via [`crate::crypto::EncryptionCipher`], shared with [`crate::kv`] so
+//! both stores protect their data under the same key.
 //! Non-sensitive fields (`mode`, `duration_secs`, `timestamp`) are stored in plaintext.
-//! Each encryption operation uses a fresh random 12-byte nonce.
-
-use aes_gcm::aead::generic_array::typenum;
-use aes_gcm::aead::generic_array::GenericArray;
-use aes_gcm::aead::Aead;
-use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
-use base64::engine::general_purpose::STANDARD as BASE64;
-use base64::Engine;
+//!
+//! The encryption key is derived from a user passphrase with Argon2id. A
+//! random salt and the KDF parameters used are persisted in the single-row
+//! `store_meta` table on first [`HistoryStore::open`], so later opens
+//! reproduce the same key; a known-plaintext verification token, also
+//! stored there, lets `open` reject a wrong passphrase immediately instead
+//! of surfacing it later as corrupted rows.
+//!
+//! Key material and decrypted text don't linger in memory: the AES key is
+//! wrapped in `Zeroizing` and scrubbed on drop, and `HistoryRecord`'s text
+//! fields are [`SecretString`], not `String`, so their backing allocation
+//! is zeroed too.
+//!
+//! Every history row belongs to the append-only sequence of a single host
+//! (`host_uuid`, `idx`), so [`crate::sync`] can replicate rows across
+//! devices by diffing `{host -> max idx}` maps rather than walking a
+//! parent-pointer chain. This device's own `host_uuid` is generated once
+//! and persisted in the single-row `host` table.
+//!
+//! [`HistoryStore::open_with_search`] additionally enables [`search`],
+//! backed by blind indexing: each distinct word in a record is tagged
+//! with a keyed MAC under an index key derived from the master key via
+//! HKDF (a separate `info` label from any other derivation, so the index
+//! key can tag tokens but never decrypt content). The tags are stored
+//! alongside the ciphertext in `history_tokens`; a search MACs the query
+//! the same way and matches on tag equality, so plaintext is never
+//! visible to the database and only matching rows are decrypted.
+//!
+//! [`search`]: HistoryStore::search
+
+use argon2::{Algorithm, Argon2, Params as Argon2LibParams, Version};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
 use rand::RngCore;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension, ToSql};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
+use std::rc::Rc;
+use uuid::Uuid;
+use zeroize::{Zeroize, Zeroizing};
 
+use crate::crypto::EncryptionCipher;
+pub use crate::crypto::SecretString;
 use crate::error::VaaniError;
 
-/// Size of AES-256-GCM nonce in bytes.
-const NONCE_SIZE: usize = 12;
+/// Size of the random salt persisted in `store_meta`, in bytes.
+const SALT_SIZE: usize = 16;
+
+/// Argon2id memory cost, in KiB (64 MiB).
+const ARGON2_MEMORY_KIB: u32 = 64 * 1024;
+
+/// Argon2id iteration count.
+const ARGON2_ITERATIONS: u32 = 3;
+
+/// Argon2id parallelism (lanes).
+const ARGON2_PARALLELISM: u32 = 1;
+
+/// Known plaintext encrypted under the derived key on first `open` and
+/// re-decrypted on every later `open` to confirm the passphrase is right.
+const VERIFICATION_PLAINTEXT: &str = "vaani-history-store-verification-token";
+
+/// HKDF `info` label used to derive the blind-index key from the master
+/// key. Distinct from any other derivation so the index key can tag
+/// tokens but never decrypt history content.
+const INDEX_KEY_INFO: &[u8] = b"vaani-history-search-index-key-v1";
+
+/// Length, in bytes, of a truncated blind-index tag. Long enough to make
+/// false-positive token collisions negligible, short enough to stay
+/// cheap to store and index.
+const TOKEN_TAG_SIZE: usize = 8;
 
 // ---------------------------------------------------------------------------
 // Public types
@@ -31,13 +88,34 @@ pub struct HistoryRecord {
     /// ISO 8601 timestamp.
     pub timestamp: String,
     /// Raw transcription text.
-    pub original_text: String,
+    pub original_text: SecretString,
     /// Text after Claude enhancement.
-    pub enhanced_text: String,
+    pub enhanced_text: SecretString,
     /// Enhancement mode that was used.
     pub mode: String,
     /// Recording duration in seconds.
     pub duration_secs: f32,
+    /// UUID of the host that created this record.
+    pub host_uuid: String,
+    /// This record's position in its host's append-only sequence.
+    pub idx: i64,
+}
+
+/// An encrypted history row as exchanged with a sync peer via
+/// [`crate::sync`] — ciphertext only, never decrypted before upload or
+/// after download.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedHistoryRecord {
+    pub host_uuid: String,
+    pub idx: i64,
+    /// ISO 8601 timestamp.
+    pub timestamp: String,
+    /// `base64(nonce || ciphertext || tag)`.
+    pub original_text: String,
+    /// `base64(nonce || ciphertext || tag)`.
+    pub enhanced_text: String,
+    pub mode: String,
+    pub duration_secs: f32,
 }
 
 /// Input struct for adding a record (no ID or timestamp — the DB assigns those).
@@ -51,116 +129,218 @@ pub struct NewHistoryRecord<'a> {
 
 /// Encrypted history database backed by SQLite.
 pub struct HistoryStore {
-    conn: Connection,
-    cipher: EncryptionCipher,
+    conn: Rc<Connection>,
+    cipher: Rc<EncryptionCipher>,
+    /// Present only when opened via [`HistoryStore::open_with_search`];
+    /// `add` and `search` no-op/error on the blind index without it.
+    index_key: Option<Zeroizing<[u8; 32]>>,
 }
 
 // ---------------------------------------------------------------------------
-// EncryptionCipher
+// HistoryStore
 // ---------------------------------------------------------------------------
 
-/// Wraps an AES-256-GCM key and provides encrypt/decrypt helpers.
-struct EncryptionCipher {
-    key: GenericArray<u8, typenum::U32>,
-}
-
-impl EncryptionCipher {
-    /// Create a new cipher from a 32-byte key.
-    fn new(key: &[u8; 32]) -> Self {
-        Self {
-            key: *GenericArray::from_slice(key),
-        }
-    }
-
-    /// Encrypt `plaintext` and return `base64(nonce || ciphertext || tag)`.
-    fn encrypt(&self, plaintext: &str) -> Result<String, VaaniError> {
-        let cipher = Aes256Gcm::new(&self.key);
-
-        let mut nonce_bytes = [0u8; NONCE_SIZE];
-        rand::thread_rng().fill_bytes(&mut nonce_bytes);
-        let nonce = Nonce::from_slice(&nonce_bytes);
-
-        let ciphertext = cipher
-            .encrypt(nonce, plaintext.as_bytes())
-            .map_err(|e| VaaniError::Storage(format!("encryption failed: {e}")))?;
-
-        let mut combined = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
-        combined.extend_from_slice(&nonce_bytes);
-        combined.extend_from_slice(&ciphertext);
-
-        Ok(BASE64.encode(&combined))
-    }
-
-    /// Decrypt a base64-encoded blob produced by [`encrypt`].
-    fn decrypt(&self, ciphertext_b64: &str) -> Result<String, VaaniError> {
-        let combined = BASE64
-            .decode(ciphertext_b64)
-            .map_err(|e| VaaniError::Storage(format!("base64 decode failed: {e}")))?;
+impl HistoryStore {
+    /// Open (or create) the history database at `db_path`, deriving the
+    /// encryption key from `passphrase` with Argon2id.
+    ///
+    /// On first open, a random salt is generated and persisted in
+    /// `store_meta` together with the Argon2id parameters used, so later
+    /// opens reproduce the same key. A database created before Argon2id
+    /// was introduced (detected by an empty `store_meta`, but an existing
+    /// `history` table) is migrated in place: its rows, encrypted under
+    /// the legacy XOR-folded key, are decrypted and re-encrypted under the
+    /// newly derived key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VaaniError::Storage`] if `passphrase` is wrong (the
+    /// verification token, or a legacy row, fails to decrypt) or the
+    /// database cannot be read or written.
+    pub fn open(db_path: &Path, passphrase: &str) -> Result<Self, VaaniError> {
+        let conn = Connection::open(db_path)
+            .map_err(|e| VaaniError::Storage(format!("failed to open database: {e}")))?;
 
-        if combined.len() < NONCE_SIZE + 1 {
-            return Err(VaaniError::Storage("encrypted data too short".to_string()));
-        }
+        create_schema(&conn)?;
 
-        let (nonce_bytes, ciphertext) = combined.split_at(NONCE_SIZE);
-        let nonce = Nonce::from_slice(nonce_bytes);
-        let cipher = Aes256Gcm::new(&self.key);
+        let cipher = match read_store_meta(&conn)? {
+            Some(meta) => {
+                let kdf_params = Argon2Params::parse(&meta.kdf_params)?;
+                let mut key = derive_key_argon2(passphrase, &meta.salt, &kdf_params)?;
+                let cipher = EncryptionCipher::new(&key);
+                key.zeroize();
+                cipher.decrypt(&meta.verify_token).map_err(|_| {
+                    VaaniError::Storage("incorrect passphrase for this history database".into())
+                })?;
+                cipher
+            }
+            None => {
+                let salt = random_salt();
+                let kdf_params = Argon2Params::CURRENT;
+                let mut key = derive_key_argon2(passphrase, &salt, &kdf_params)?;
+                let cipher = EncryptionCipher::new(&key);
+                key.zeroize();
+
+                let mut legacy_key = derive_key(passphrase);
+                let legacy_cipher = EncryptionCipher::new(&legacy_key);
+                legacy_key.zeroize();
+
+                migrate_legacy_rows(&conn, &legacy_cipher, &cipher)?;
+                write_store_meta(&conn, &salt, kdf_params, &cipher)?;
+                cipher
+            }
+        };
 
-        let plaintext = cipher
-            .decrypt(nonce, ciphertext)
-            .map_err(|e| VaaniError::Storage(format!("decryption failed: {e}")))?;
+        tracing::debug!(?db_path, "history database opened");
 
-        String::from_utf8(plaintext)
-            .map_err(|e| VaaniError::Storage(format!("decrypted text is not valid UTF-8: {e}")))
+        Ok(Self {
+            conn: Rc::new(conn),
+            cipher: Rc::new(cipher),
+            index_key: None,
+        })
     }
-}
 
-// ---------------------------------------------------------------------------
-// HistoryStore
-// ---------------------------------------------------------------------------
-
-impl HistoryStore {
-    /// Open (or create) the history database at `db_path`.
+    /// Open (or create) the history database at `db_path` using a raw
+    /// 32-byte key directly, bypassing Argon2id derivation and the
+    /// `store_meta` verification token entirely.
     ///
-    /// `encryption_key` must be exactly 32 bytes (AES-256 key size).
-    pub fn open(db_path: &Path, encryption_key: &[u8; 32]) -> Result<Self, VaaniError> {
+    /// Intended for callers that already manage key material themselves
+    /// (tests, or a key sourced from an OS keychain rather than a
+    /// passphrase).
+    pub fn open_with_key(db_path: &Path, encryption_key: &[u8; 32]) -> Result<Self, VaaniError> {
         let conn = Connection::open(db_path)
             .map_err(|e| VaaniError::Storage(format!("failed to open database: {e}")))?;
 
         create_schema(&conn)?;
 
-        tracing::debug!(?db_path, "history database opened");
+        tracing::debug!(?db_path, "history database opened with raw key");
 
         Ok(Self {
-            conn,
-            cipher: EncryptionCipher::new(encryption_key),
+            conn: Rc::new(conn),
+            cipher: Rc::new(EncryptionCipher::new(encryption_key)),
+            index_key: None,
         })
     }
 
-    /// Insert a new record. Text fields are encrypted before storage.
+    /// A [`crate::kv::KvStore`] sharing this store's connection and
+    /// encryption key — the minimal foundation for syncing non-history
+    /// state (last-used mode, cached prompts, per-mode preferences)
+    /// through the same channel as history.
+    pub fn kv_store(&self) -> Result<crate::kv::KvStore, VaaniError> {
+        crate::kv::KvStore::new(Rc::clone(&self.conn), Rc::clone(&self.cipher))
+    }
+
+    /// Like [`HistoryStore::open`], but also enables searchable blind
+    /// indexing (see the module docs and [`HistoryStore::search`]).
+    ///
+    /// Only records added *after* this is first called are indexed;
+    /// existing records aren't retroactively tokenized.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`HistoryStore::open`].
+    pub fn open_with_search(db_path: &Path, passphrase: &str) -> Result<Self, VaaniError> {
+        let mut store = Self::open(db_path, passphrase)?;
+        store.index_key = Some(derive_index_key(store.cipher.key_bytes()));
+        Ok(store)
+    }
+
+    /// Insert a new record as the next entry in this device's own
+    /// append-only sequence. Text fields are encrypted before storage.
     ///
     /// Returns the auto-generated row ID.
     pub fn add(&self, record: &NewHistoryRecord<'_>) -> Result<i64, VaaniError> {
         let enc_original = self.cipher.encrypt(record.original_text)?;
         let enc_enhanced = self.cipher.encrypt(record.enhanced_text)?;
+        let host_uuid = ensure_host_identity(&self.conn)?;
+        let idx = self.next_idx_for_host(&host_uuid)?;
 
         self.conn
             .execute(
-                "INSERT INTO history (original_text, enhanced_text, mode, duration_secs)
-                 VALUES (?1, ?2, ?3, ?4)",
+                "INSERT INTO history
+                     (original_text, enhanced_text, mode, duration_secs, host_uuid, idx)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
                 params![
                     enc_original,
                     enc_enhanced,
                     record.mode,
-                    record.duration_secs
+                    record.duration_secs,
+                    host_uuid,
+                    idx
                 ],
             )
             .map_err(|e| VaaniError::Storage(format!("insert failed: {e}")))?;
 
         let id = self.conn.last_insert_rowid();
-        tracing::debug!(id, mode = record.mode, "history record added");
+
+        if let Some(index_key) = &self.index_key {
+            self.index_tokens(id, index_key, record.original_text)?;
+            self.index_tokens(id, index_key, record.enhanced_text)?;
+        }
+
+        tracing::debug!(id, mode = record.mode, idx, "history record added");
         Ok(id)
     }
 
+    /// Find history records whose `original_text` or `enhanced_text`
+    /// contains every word in `query`, via the blind-index tags written
+    /// at insert time. Only rows whose tag set matches are decrypted.
+    ///
+    /// Search reveals token equality and frequency (which rows share
+    /// which hashed words) to anyone with access to the database file,
+    /// though never the plaintext words themselves.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VaaniError::Storage`] if this store wasn't opened with
+    /// [`HistoryStore::open_with_search`], or the query fails.
+    pub fn search(&self, query: &str) -> Result<Vec<HistoryRecord>, VaaniError> {
+        let index_key = self.index_key.as_ref().ok_or_else(|| {
+            VaaniError::Storage(
+                "search is not enabled for this store; open it with open_with_search".to_string(),
+            )
+        })?;
+
+        let tags: Vec<Vec<u8>> = tokenizer::tokenize(query)
+            .iter()
+            .map(|token| token_mac(index_key, token))
+            .collect();
+        if tags.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let placeholders = vec!["?"; tags.len()].join(",");
+        let sql = format!(
+            "SELECT history_id FROM history_tokens
+             WHERE token_hash IN ({placeholders})
+             GROUP BY history_id
+             HAVING COUNT(DISTINCT token_hash) = ?"
+        );
+
+        let mut stmt = self
+            .conn
+            .prepare(&sql)
+            .map_err(|e| VaaniError::Storage(format!("search query prepare failed: {e}")))?;
+
+        let tag_count = tags.len() as i64;
+        let mut query_params: Vec<&dyn ToSql> = tags.iter().map(|tag| tag as &dyn ToSql).collect();
+        query_params.push(&tag_count);
+
+        let ids: Vec<i64> = stmt
+            .query_map(query_params.as_slice(), |row| row.get(0))
+            .map_err(|e| VaaniError::Storage(format!("search query failed: {e}")))?
+            .collect::<rusqlite::Result<_>>()
+            .map_err(|e| VaaniError::Storage(format!("search row read failed: {e}")))?;
+
+        let mut records = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(record) = self.get(id)? {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+
     /// Retrieve the most recent `limit` records, newest first.
     ///
     /// Text fields are decrypted on retrieval.
@@ -168,7 +348,8 @@ impl HistoryStore {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT id, timestamp, original_text, enhanced_text, mode, duration_secs
+                "SELECT id, timestamp, original_text, enhanced_text, mode, duration_secs,
+                        host_uuid, idx
                  FROM history ORDER BY timestamp DESC LIMIT ?1",
             )
             .map_err(|e| VaaniError::Storage(format!("query prepare failed: {e}")))?;
@@ -182,6 +363,8 @@ impl HistoryStore {
                     enhanced_text: row.get(3)?,
                     mode: row.get(4)?,
                     duration_secs: row.get(5)?,
+                    host_uuid: row.get(6)?,
+                    idx: row.get(7)?,
                 })
             })
             .map_err(|e| VaaniError::Storage(format!("query failed: {e}")))?;
@@ -198,7 +381,8 @@ impl HistoryStore {
         let mut stmt = self
             .conn
             .prepare(
-                "SELECT id, timestamp, original_text, enhanced_text, mode, duration_secs
+                "SELECT id, timestamp, original_text, enhanced_text, mode, duration_secs,
+                        host_uuid, idx
                  FROM history WHERE id = ?1",
             )
             .map_err(|e| VaaniError::Storage(format!("query prepare failed: {e}")))?;
@@ -212,6 +396,8 @@ impl HistoryStore {
                     enhanced_text: row.get(3)?,
                     mode: row.get(4)?,
                     duration_secs: row.get(5)?,
+                    host_uuid: row.get(6)?,
+                    idx: row.get(7)?,
                 })
             })
             .map_err(|e| VaaniError::Storage(format!("query failed: {e}")))?;
@@ -225,11 +411,155 @@ impl HistoryStore {
         }
     }
 
+    /// This device's stable host UUID, assigned on first `open`/`add` and
+    /// persisted in the `host` table thereafter.
+    pub fn host_uuid(&self) -> Result<String, VaaniError> {
+        ensure_host_identity(&self.conn)
+    }
+
+    /// `{host_uuid -> highest idx stored locally}` — the input to a sync
+    /// diff against a remote peer's index.
+    pub fn record_index(&self) -> Result<HashMap<String, i64>, VaaniError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT host_uuid, MAX(idx) FROM history GROUP BY host_uuid")
+            .map_err(|e| VaaniError::Storage(format!("query prepare failed: {e}")))?;
+
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| VaaniError::Storage(format!("query failed: {e}")))?;
+
+        rows.map(|r: rusqlite::Result<(String, i64)>| {
+            r.map_err(|e| VaaniError::Storage(format!("row read failed: {e}")))
+        })
+        .collect()
+    }
+
+    /// Every locally-stored record for `host_uuid` with `idx` greater than
+    /// `after_idx`, still encrypted — for uploading to a sync peer.
+    pub fn records_after(
+        &self,
+        host_uuid: &str,
+        after_idx: i64,
+    ) -> Result<Vec<EncryptedHistoryRecord>, VaaniError> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT host_uuid, idx, timestamp, original_text, enhanced_text, mode,
+                        duration_secs
+                 FROM history WHERE host_uuid = ?1 AND idx > ?2 ORDER BY idx ASC",
+            )
+            .map_err(|e| VaaniError::Storage(format!("query prepare failed: {e}")))?;
+
+        let rows = stmt
+            .query_map(params![host_uuid, after_idx], |row| {
+                Ok(EncryptedHistoryRecord {
+                    host_uuid: row.get(0)?,
+                    idx: row.get(1)?,
+                    timestamp: row.get(2)?,
+                    original_text: row.get(3)?,
+                    enhanced_text: row.get(4)?,
+                    mode: row.get(5)?,
+                    duration_secs: row.get(6)?,
+                })
+            })
+            .map_err(|e| VaaniError::Storage(format!("query failed: {e}")))?;
+
+        rows.map(|r| r.map_err(|e| VaaniError::Storage(format!("row read failed: {e}"))))
+            .collect()
+    }
+
+    /// Insert a record downloaded from a sync peer, already encrypted
+    /// under the shared key. A no-op if `(host_uuid, idx)` is already
+    /// present locally, so re-running a sync is always safe.
+    ///
+    /// If this store was opened with [`HistoryStore::open_with_search`],
+    /// the record is also decrypted and indexed so it's findable via
+    /// [`search`](Self::search) just like one inserted locally via
+    /// [`add`](Self::add).
+    pub fn insert_encrypted_record(&self, record: &EncryptedHistoryRecord) -> Result<(), VaaniError> {
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO history
+                     (host_uuid, idx, timestamp, original_text, enhanced_text, mode, duration_secs)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    record.host_uuid,
+                    record.idx,
+                    record.timestamp,
+                    record.original_text,
+                    record.enhanced_text,
+                    record.mode,
+                    record.duration_secs,
+                ],
+            )
+            .map_err(|e| VaaniError::Storage(format!("failed to insert synced record: {e}")))?;
+
+        // `INSERT OR IGNORE` no-ops on a duplicate (host_uuid, idx), in
+        // which case `last_insert_rowid()` would be stale and indexing
+        // under it would corrupt some other row's tags — skip indexing
+        // entirely when nothing was actually inserted.
+        if let Some(index_key) = &self.index_key {
+            if self.conn.changes() > 0 {
+                let id = self.conn.last_insert_rowid();
+                let original_text = self.cipher.decrypt(&record.original_text)?;
+                let enhanced_text = self.cipher.decrypt(&record.enhanced_text)?;
+                self.index_tokens(id, index_key, original_text.expose_secret())?;
+                self.index_tokens(id, index_key, enhanced_text.expose_secret())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Raw key bytes, for exporting as a recovery string via
+    /// [`crate::sync::export_key`].
+    pub(crate) fn key_bytes(&self) -> &[u8; 32] {
+        self.cipher.key_bytes()
+    }
+
+    /// Next `idx` in `host_uuid`'s append-only sequence (0 if it has none
+    /// yet).
+    fn next_idx_for_host(&self, host_uuid: &str) -> Result<i64, VaaniError> {
+        let max_idx: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT MAX(idx) FROM history WHERE host_uuid = ?1",
+                params![host_uuid],
+                |row| row.get(0),
+            )
+            .map_err(|e| VaaniError::Storage(format!("idx query failed: {e}")))?;
+        Ok(max_idx.map(|m| m + 1).unwrap_or(0))
+    }
+
+    /// Tag every distinct word in `text` with a keyed MAC under
+    /// `index_key` and store it against `history_id` for [`search`](Self::search).
+    fn index_tokens(
+        &self,
+        history_id: i64,
+        index_key: &[u8; 32],
+        text: &str,
+    ) -> Result<(), VaaniError> {
+        for token in tokenizer::tokenize(text) {
+            let tag = token_mac(index_key, &token);
+            self.conn
+                .execute(
+                    "INSERT INTO history_tokens (history_id, token_hash) VALUES (?1, ?2)",
+                    params![history_id, tag],
+                )
+                .map_err(|e| VaaniError::Storage(format!("token index insert failed: {e}")))?;
+        }
+        Ok(())
+    }
+
     /// Delete a single record by ID.
     pub fn delete(&self, id: i64) -> Result<(), VaaniError> {
         self.conn
             .execute("DELETE FROM history WHERE id = ?1", params![id])
             .map_err(|e| VaaniError::Storage(format!("delete failed: {e}")))?;
+        self.conn
+            .execute("DELETE FROM history_tokens WHERE history_id = ?1", params![id])
+            .map_err(|e| VaaniError::Storage(format!("token index cleanup failed: {e}")))?;
 
         tracing::debug!(id, "history record deleted");
         Ok(())
@@ -240,6 +570,9 @@ impl HistoryStore {
         self.conn
             .execute("DELETE FROM history", [])
             .map_err(|e| VaaniError::Storage(format!("clear failed: {e}")))?;
+        self.conn
+            .execute("DELETE FROM history_tokens", [])
+            .map_err(|e| VaaniError::Storage(format!("token index cleanup failed: {e}")))?;
 
         tracing::debug!("history cleared");
         Ok(())
@@ -266,6 +599,8 @@ impl HistoryStore {
             enhanced_text: self.cipher.decrypt(&raw.enhanced_text)?,
             mode: raw.mode,
             duration_secs: raw.duration_secs,
+            host_uuid: raw.host_uuid,
+            idx: raw.idx,
         })
     }
 }
@@ -282,9 +617,17 @@ struct RawRow {
     enhanced_text: String,
     mode: String,
     duration_secs: f32,
+    host_uuid: String,
+    idx: i64,
 }
 
-/// Create the history table and index if they don't exist.
+/// Create the history table, its index, and the `store_meta`, `host`,
+/// and `history_tokens` tables if they don't exist, then ensure
+/// `history` carries the `host_uuid`/`idx` columns sync needs (see
+/// [`ensure_history_host_idx_columns`]).
+///
+/// `history_tokens` is always created, even for stores opened without
+/// search enabled, since it simply stays empty in that case.
 fn create_schema(conn: &Connection) -> Result<(), VaaniError> {
     conn.execute_batch(
         "CREATE TABLE IF NOT EXISTS history (
@@ -295,15 +638,141 @@ fn create_schema(conn: &Connection) -> Result<(), VaaniError> {
              mode           TEXT NOT NULL,
              duration_secs  REAL NOT NULL
          );
-         CREATE INDEX IF NOT EXISTS idx_history_timestamp ON history(timestamp DESC);",
+         CREATE INDEX IF NOT EXISTS idx_history_timestamp ON history(timestamp DESC);
+         CREATE TABLE IF NOT EXISTS store_meta (
+             id             INTEGER PRIMARY KEY CHECK (id = 0),
+             salt           BLOB NOT NULL,
+             kdf_params     TEXT NOT NULL,
+             verify_token   TEXT NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS host (
+             id             INTEGER PRIMARY KEY CHECK (id = 0),
+             host_uuid      TEXT NOT NULL,
+             name           TEXT NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS history_tokens (
+             history_id     INTEGER NOT NULL,
+             token_hash     BLOB NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS idx_history_tokens_hash ON history_tokens(token_hash);
+         CREATE INDEX IF NOT EXISTS idx_history_tokens_history_id ON history_tokens(history_id);",
+    )
+    .map_err(|e| VaaniError::Storage(format!("schema creation failed: {e}")))?;
+
+    ensure_history_host_idx_columns(conn)
+}
+
+/// Derive the blind-index key from the master encryption key via
+/// HKDF-SHA256, under a domain-separation label distinct from anything
+/// else derived in this module — so it can tag search tokens but never
+/// decrypt history content.
+fn derive_index_key(master_key: &[u8; 32]) -> Zeroizing<[u8; 32]> {
+    let hkdf = Hkdf::<Sha256>::new(None, master_key);
+    let mut index_key = Zeroizing::new([0u8; 32]);
+    hkdf.expand(INDEX_KEY_INFO, index_key.as_mut_slice())
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    index_key
+}
+
+/// Keyed MAC for one token, truncated to [`TOKEN_TAG_SIZE`] bytes.
+fn token_mac(index_key: &[u8; 32], token: &str) -> Vec<u8> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(index_key).expect("HMAC-SHA256 accepts a 32-byte key");
+    mac.update(token.as_bytes());
+    mac.finalize().into_bytes()[..TOKEN_TAG_SIZE].to_vec()
+}
+
+/// Splits text into the distinct lowercase words used as blind-index
+/// tokens for [`HistoryStore::search`].
+mod tokenizer {
+    use std::collections::BTreeSet;
+
+    /// Lowercase `text` and split on runs of non-alphanumeric characters,
+    /// deduplicating so each distinct word produces exactly one tag.
+    pub(super) fn tokenize(text: &str) -> BTreeSet<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Add `host_uuid`/`idx` to `history` and backfill them for rows created
+/// before per-host sync was introduced, so every row — old or new —
+/// belongs to a host's append-only sequence. A no-op once the columns
+/// already exist.
+fn ensure_history_host_idx_columns(conn: &Connection) -> Result<(), VaaniError> {
+    let has_host_uuid_column = conn
+        .prepare("SELECT 1 FROM pragma_table_info('history') WHERE name = 'host_uuid'")
+        .and_then(|mut stmt| stmt.exists([]))
+        .map_err(|e| VaaniError::Storage(format!("schema introspection failed: {e}")))?;
+
+    if has_host_uuid_column {
+        return Ok(());
+    }
+
+    conn.execute_batch(
+        "ALTER TABLE history ADD COLUMN host_uuid TEXT;
+         ALTER TABLE history ADD COLUMN idx INTEGER;",
+    )
+    .map_err(|e| VaaniError::Storage(format!("failed to add host/idx columns: {e}")))?;
+
+    let local_host_uuid = ensure_host_identity(conn)?;
+
+    let mut stmt = conn
+        .prepare("SELECT id FROM history ORDER BY id ASC")
+        .map_err(|e| VaaniError::Storage(format!("query prepare failed: {e}")))?;
+    let ids: Vec<i64> = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| VaaniError::Storage(format!("query failed: {e}")))?
+        .collect::<rusqlite::Result<_>>()
+        .map_err(|e| VaaniError::Storage(format!("row read failed: {e}")))?;
+
+    for (idx, id) in ids.into_iter().enumerate() {
+        conn.execute(
+            "UPDATE history SET host_uuid = ?1, idx = ?2 WHERE id = ?3",
+            params![local_host_uuid, idx as i64, id],
+        )
+        .map_err(|e| VaaniError::Storage(format!("failed to backfill host/idx: {e}")))?;
+    }
+
+    conn.execute_batch("CREATE UNIQUE INDEX IF NOT EXISTS idx_history_host_idx ON history(host_uuid, idx);")
+        .map_err(|e| VaaniError::Storage(format!("failed to create host/idx index: {e}")))
+}
+
+/// Return this device's stable `host_uuid`, generating and persisting one
+/// (with a placeholder name) on first call.
+fn ensure_host_identity(conn: &Connection) -> Result<String, VaaniError> {
+    let existing: Option<String> = conn
+        .query_row("SELECT host_uuid FROM host WHERE id = 0", [], |row| {
+            row.get(0)
+        })
+        .optional()
+        .map_err(|e| VaaniError::Storage(format!("host identity query failed: {e}")))?;
+
+    if let Some(host_uuid) = existing {
+        return Ok(host_uuid);
+    }
+
+    let host_uuid = Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO host (id, host_uuid, name) VALUES (0, ?1, ?2)",
+        params![host_uuid, "this device"],
     )
-    .map_err(|e| VaaniError::Storage(format!("schema creation failed: {e}")))
+    .map_err(|e| VaaniError::Storage(format!("failed to persist host identity: {e}")))?;
+
+    Ok(host_uuid)
 }
 
 /// Derive a 32-byte encryption key from a passphrase using XOR folding.
 ///
-/// This is a **simple** key-derivation function suitable for development.
-/// For production use, prefer PBKDF2 or Argon2.
+/// This is a **legacy** key-derivation function, kept only so
+/// [`HistoryStore::open`] can recognise and migrate databases created
+/// before Argon2id was introduced. Don't use it for new history stores or
+/// any other new on-disk format — [`crate::keychain::vault`] used to rely
+/// on it too, but now derives its key the same way `HistoryStore` does,
+/// via [`derive_key_argon2`] against a persisted salt.
 pub fn derive_key(passphrase: &str) -> [u8; 32] {
     let mut key = [0u8; 32];
     for (i, &b) in passphrase.as_bytes().iter().enumerate() {
@@ -312,6 +781,194 @@ pub fn derive_key(passphrase: &str) -> [u8; 32] {
     key
 }
 
+/// Argon2id parameters, persisted as `store_meta.kdf_params` so future
+/// opens reproduce the same key even if [`Argon2Params::CURRENT`] changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Argon2Params {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl Argon2Params {
+    /// Parameters used for newly created history stores (and, via
+    /// [`crate::keychain::vault`], new encrypted vault files).
+    pub(crate) const CURRENT: Self = Self {
+        memory_kib: ARGON2_MEMORY_KIB,
+        iterations: ARGON2_ITERATIONS,
+        parallelism: ARGON2_PARALLELISM,
+    };
+
+    /// Serialize as `"m=<kib>,t=<iterations>,p=<parallelism>"`.
+    fn to_kdf_params_string(self) -> String {
+        format!(
+            "m={},t={},p={}",
+            self.memory_kib, self.iterations, self.parallelism
+        )
+    }
+
+    /// Parse the `"m=...,t=...,p=..."` format written by
+    /// [`to_kdf_params_string`](Self::to_kdf_params_string).
+    fn parse(s: &str) -> Result<Self, VaaniError> {
+        let mut memory_kib = None;
+        let mut iterations = None;
+        let mut parallelism = None;
+
+        for field in s.split(',') {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| VaaniError::Storage(format!("malformed kdf_params field: {field}")))?;
+            let value: u32 = value.parse().map_err(|_| {
+                VaaniError::Storage(format!("malformed kdf_params value: {field}"))
+            })?;
+            match key {
+                "m" => memory_kib = Some(value),
+                "t" => iterations = Some(value),
+                "p" => parallelism = Some(value),
+                other => {
+                    return Err(VaaniError::Storage(format!(
+                        "unknown kdf_params field: {other}"
+                    )))
+                }
+            }
+        }
+
+        Ok(Self {
+            memory_kib: memory_kib
+                .ok_or_else(|| VaaniError::Storage("kdf_params missing 'm'".to_string()))?,
+            iterations: iterations
+                .ok_or_else(|| VaaniError::Storage("kdf_params missing 't'".to_string()))?,
+            parallelism: parallelism
+                .ok_or_else(|| VaaniError::Storage("kdf_params missing 'p'".to_string()))?,
+        })
+    }
+}
+
+/// Derive a 32-byte encryption key from `passphrase` and `salt` using
+/// Argon2id with `kdf_params`.
+pub(crate) fn derive_key_argon2(
+    passphrase: &str,
+    salt: &[u8],
+    kdf_params: &Argon2Params,
+) -> Result<[u8; 32], VaaniError> {
+    let params = Argon2LibParams::new(
+        kdf_params.memory_kib,
+        kdf_params.iterations,
+        kdf_params.parallelism,
+        Some(32),
+    )
+    .map_err(|e| VaaniError::Storage(format!("invalid Argon2 parameters: {e}")))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+    let mut key = [0u8; 32];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| VaaniError::Storage(format!("key derivation failed: {e}")))?;
+    Ok(key)
+}
+
+/// Generate a fresh random salt for a new `store_meta` row (or, via
+/// [`crate::keychain::vault`], a new encrypted vault file).
+pub(crate) fn random_salt() -> [u8; SALT_SIZE] {
+    let mut salt = [0u8; SALT_SIZE];
+    rand::thread_rng().fill_bytes(&mut salt);
+    salt
+}
+
+/// The single `store_meta` row, read back verbatim from SQLite.
+struct StoreMeta {
+    salt: Vec<u8>,
+    kdf_params: String,
+    verify_token: String,
+}
+
+/// Read the persisted salt, KDF parameters, and verification token, or
+/// `None` if this database predates Argon2id (or was just created).
+fn read_store_meta(conn: &Connection) -> Result<Option<StoreMeta>, VaaniError> {
+    conn.query_row(
+        "SELECT salt, kdf_params, verify_token FROM store_meta WHERE id = 0",
+        [],
+        |row| {
+            Ok(StoreMeta {
+                salt: row.get(0)?,
+                kdf_params: row.get(1)?,
+                verify_token: row.get(2)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| VaaniError::Storage(format!("store_meta query failed: {e}")))
+}
+
+/// Persist `salt` and `kdf_params`, along with [`VERIFICATION_PLAINTEXT`]
+/// encrypted under `cipher`, so the next `open` can reproduce the same key
+/// and detect a wrong passphrase immediately.
+fn write_store_meta(
+    conn: &Connection,
+    salt: &[u8],
+    kdf_params: Argon2Params,
+    cipher: &EncryptionCipher,
+) -> Result<(), VaaniError> {
+    let verify_token = cipher.encrypt(VERIFICATION_PLAINTEXT)?;
+    conn.execute(
+        "INSERT INTO store_meta (id, salt, kdf_params, verify_token) VALUES (0, ?1, ?2, ?3)",
+        params![salt, kdf_params.to_kdf_params_string(), verify_token],
+    )
+    .map_err(|e| VaaniError::Storage(format!("failed to persist store_meta: {e}")))?;
+    Ok(())
+}
+
+/// Re-encrypt any rows left over from before Argon2id (encrypted under the
+/// legacy XOR-folded key) under `new_cipher`'s key. A fresh, empty
+/// database has no rows to migrate and this is a no-op.
+///
+/// Returns an error if a non-empty legacy database fails to decrypt under
+/// `legacy_cipher`, which means the passphrase is wrong.
+fn migrate_legacy_rows(
+    conn: &Connection,
+    legacy_cipher: &EncryptionCipher,
+    new_cipher: &EncryptionCipher,
+) -> Result<(), VaaniError> {
+    let mut stmt = conn
+        .prepare("SELECT id, original_text, enhanced_text FROM history")
+        .map_err(|e| VaaniError::Storage(format!("query prepare failed: {e}")))?;
+
+    let rows: Vec<(i64, String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .map_err(|e| VaaniError::Storage(format!("query failed: {e}")))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| VaaniError::Storage(format!("row read failed: {e}")))?;
+
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    for (id, enc_original, enc_enhanced) in &rows {
+        let original = legacy_cipher.decrypt(enc_original).map_err(|_| {
+            VaaniError::Storage("incorrect passphrase for this history database".to_string())
+        })?;
+        let enhanced = legacy_cipher.decrypt(enc_enhanced).map_err(|_| {
+            VaaniError::Storage("incorrect passphrase for this history database".to_string())
+        })?;
+
+        conn.execute(
+            "UPDATE history SET original_text = ?1, enhanced_text = ?2 WHERE id = ?3",
+            params![
+                new_cipher.encrypt(original.expose_secret())?,
+                new_cipher.encrypt(enhanced.expose_secret())?,
+                id
+            ],
+        )
+        .map_err(|e| VaaniError::Storage(format!("migration update failed: {e}")))?;
+    }
+
+    tracing::info!(
+        rows = rows.len(),
+        "migrated legacy history rows to an Argon2id-derived key"
+    );
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -322,12 +979,12 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
-    /// Helper: create a store in a temp directory with a test key.
+    /// Helper: create a store in a temp directory with a test passphrase.
     fn test_store() -> (HistoryStore, TempDir) {
         let dir = TempDir::new().expect("failed to create temp dir");
         let db_path = dir.path().join("history.db");
-        let key = derive_key("test-passphrase-for-unit-tests!");
-        let store = HistoryStore::open(&db_path, &key).expect("failed to open store");
+        let store =
+            HistoryStore::open(&db_path, "test-passphrase-for-unit-tests!").expect("open store");
         (store, dir)
     }
 
@@ -347,11 +1004,190 @@ mod tests {
         let db_path = dir.path().join("new.db");
         assert!(!db_path.exists());
 
+        let _store = HistoryStore::open(&db_path, "a passphrase").expect("open should succeed");
+        assert!(db_path.exists());
+    }
+
+    #[test]
+    fn open_with_key_bypasses_the_kdf() {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let db_path = dir.path().join("new.db");
+
         let key = [0u8; 32];
-        let _store = HistoryStore::open(&db_path, &key).expect("open should succeed");
+        let _store = HistoryStore::open_with_key(&db_path, &key).expect("open should succeed");
         assert!(db_path.exists());
     }
 
+    #[test]
+    fn reopen_with_same_passphrase_succeeds() {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let db_path = dir.path().join("history.db");
+
+        let store = HistoryStore::open(&db_path, "correct horse battery staple").expect("open 1");
+        let id = store.add(&sample_record()).expect("add");
+        drop(store);
+
+        let reopened =
+            HistoryStore::open(&db_path, "correct horse battery staple").expect("open 2");
+        let record = reopened.get(id).expect("get").expect("record exists");
+        assert_eq!(record.original_text, "hello world this is a test");
+    }
+
+    #[test]
+    fn reopen_with_wrong_passphrase_is_rejected() {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let db_path = dir.path().join("history.db");
+
+        let store = HistoryStore::open(&db_path, "correct horse battery staple").expect("open 1");
+        store.add(&sample_record()).expect("add");
+        drop(store);
+
+        let result = HistoryStore::open(&db_path, "wrong passphrase");
+        match result.unwrap_err() {
+            VaaniError::Storage(msg) => assert!(msg.contains("incorrect passphrase")),
+            other => panic!("expected Storage error, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn legacy_database_is_migrated_on_open() {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let db_path = dir.path().join("legacy.db");
+        let passphrase = "an old-timer passphrase";
+
+        // Simulate a pre-Argon2id database: a `history` table with rows
+        // encrypted under the legacy XOR-folded key, and no `store_meta`
+        // rows at all.
+        {
+            let conn = Connection::open(&db_path).expect("open raw connection");
+            create_schema(&conn).expect("create schema");
+            let legacy_cipher = EncryptionCipher::new(&derive_key(passphrase));
+            conn.execute(
+                "INSERT INTO history
+                     (original_text, enhanced_text, mode, duration_secs, host_uuid, idx)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    legacy_cipher.encrypt("legacy plaintext").expect("encrypt"),
+                    legacy_cipher.encrypt("Legacy plaintext.").expect("encrypt"),
+                    "professional",
+                    2.0_f32,
+                    "legacy-host",
+                    0_i64,
+                ],
+            )
+            .expect("insert legacy row");
+        }
+
+        let store = HistoryStore::open(&db_path, passphrase).expect("open should migrate");
+        let records = store.recent(10).expect("recent");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].original_text, "legacy plaintext");
+
+        // Re-opening afterwards should now go through the Argon2id path
+        // (store_meta is populated) and still work with the same passphrase.
+        drop(store);
+        let reopened = HistoryStore::open(&db_path, passphrase).expect("reopen after migration");
+        assert_eq!(reopened.count().expect("count"), 1);
+    }
+
+    #[test]
+    fn legacy_database_rejects_wrong_passphrase_on_migration() {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let db_path = dir.path().join("legacy.db");
+
+        {
+            let conn = Connection::open(&db_path).expect("open raw connection");
+            create_schema(&conn).expect("create schema");
+            let legacy_cipher = EncryptionCipher::new(&derive_key("right passphrase"));
+            conn.execute(
+                "INSERT INTO history
+                     (original_text, enhanced_text, mode, duration_secs, host_uuid, idx)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    legacy_cipher.encrypt("secret").expect("encrypt"),
+                    legacy_cipher.encrypt("Secret.").expect("encrypt"),
+                    "professional",
+                    2.0_f32,
+                    "legacy-host",
+                    0_i64,
+                ],
+            )
+            .expect("insert legacy row");
+        }
+
+        let result = HistoryStore::open(&db_path, "wrong passphrase");
+        match result.unwrap_err() {
+            VaaniError::Storage(msg) => assert!(msg.contains("incorrect passphrase")),
+            other => panic!("expected Storage error, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn argon2_params_round_trip_through_string() {
+        let params = Argon2Params::CURRENT;
+        let parsed = Argon2Params::parse(&params.to_kdf_params_string()).expect("parse");
+        assert_eq!(parsed, params);
+    }
+
+    #[test]
+    fn added_records_get_a_per_host_sequential_idx() {
+        let (store, _dir) = test_store();
+
+        let id1 = store.add(&sample_record()).expect("add 1");
+        let id2 = store.add(&sample_record()).expect("add 2");
+
+        let record1 = store.get(id1).expect("get").expect("exists");
+        let record2 = store.get(id2).expect("get").expect("exists");
+
+        assert_eq!(record1.host_uuid, record2.host_uuid);
+        assert_eq!(record1.idx, 0);
+        assert_eq!(record2.idx, 1);
+    }
+
+    #[test]
+    fn record_index_reports_highest_idx_per_host() {
+        let (store, _dir) = test_store();
+        store.add(&sample_record()).expect("add 1");
+        store.add(&sample_record()).expect("add 2");
+
+        let index = store.record_index().expect("record_index");
+        let host_uuid = store.host_uuid().expect("host_uuid");
+
+        assert_eq!(index.get(&host_uuid), Some(&1));
+    }
+
+    #[test]
+    fn records_after_returns_only_newer_entries() {
+        let (store, _dir) = test_store();
+        store.add(&sample_record()).expect("add 1");
+        store.add(&sample_record()).expect("add 2");
+        store.add(&sample_record()).expect("add 3");
+
+        let host_uuid = store.host_uuid().expect("host_uuid");
+        let newer = store.records_after(&host_uuid, 0).expect("records_after");
+
+        assert_eq!(newer.len(), 2);
+        assert_eq!(newer[0].idx, 1);
+        assert_eq!(newer[1].idx, 2);
+    }
+
+    #[test]
+    fn insert_encrypted_record_is_idempotent() {
+        let (store, _dir) = test_store();
+        store.add(&sample_record()).expect("add");
+        let host_uuid = store.host_uuid().expect("host_uuid");
+        let record = store
+            .records_after(&host_uuid, -1)
+            .expect("records_after")
+            .remove(0);
+
+        // Re-applying the same (host_uuid, idx) twice should not duplicate it.
+        store.insert_encrypted_record(&record).expect("insert 1");
+        store.insert_encrypted_record(&record).expect("insert 2");
+
+        assert_eq!(store.count().expect("count"), 1);
+    }
+
     #[test]
     fn add_and_retrieve_record() {
         let (store, _dir) = test_store();
@@ -523,4 +1359,100 @@ mod tests {
 
         assert!(result.is_err(), "decryption with wrong key should fail");
     }
+
+    /// Helper: create a store with search enabled in a temp directory.
+    fn test_search_store() -> (HistoryStore, TempDir) {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let db_path = dir.path().join("history.db");
+        let store = HistoryStore::open_with_search(&db_path, "search-test-passphrase!")
+            .expect("open store");
+        (store, dir)
+    }
+
+    #[test]
+    fn search_is_rejected_without_open_with_search() {
+        let (store, _dir) = test_store();
+        store.add(&sample_record()).expect("add");
+
+        let result = store.search("hello");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn search_finds_a_word_from_either_text_field() {
+        let (store, _dir) = test_search_store();
+        store.add(&sample_record()).expect("add");
+
+        let by_original = store.search("world").expect("search");
+        assert_eq!(by_original.len(), 1);
+
+        let by_enhanced = store.search("Hello").expect("search is case-insensitive");
+        assert_eq!(by_enhanced.len(), 1);
+    }
+
+    #[test]
+    fn search_requires_every_query_token_to_match() {
+        let (store, _dir) = test_search_store();
+        store.add(&sample_record()).expect("add");
+
+        let all_present = store.search("hello world").expect("search");
+        assert_eq!(all_present.len(), 1);
+
+        let one_missing = store.search("hello nonexistent").expect("search");
+        assert!(one_missing.is_empty());
+    }
+
+    #[test]
+    fn search_does_not_index_records_added_before_it_was_enabled() {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let db_path = dir.path().join("history.db");
+
+        {
+            let store = HistoryStore::open(&db_path, "passphrase").expect("open");
+            store.add(&sample_record()).expect("add");
+        }
+
+        let store =
+            HistoryStore::open_with_search(&db_path, "passphrase").expect("reopen with search");
+        assert!(store.search("hello").expect("search").is_empty());
+    }
+
+    #[test]
+    fn insert_encrypted_record_is_indexed_for_search() {
+        // Simulate a record synced in from a peer device: produce it via a
+        // separate store sharing the same passphrase (so the ciphertext
+        // decrypts with this store's key), then hand it to
+        // `insert_encrypted_record` the way `crate::sync` would.
+        let peer_dir = TempDir::new().expect("failed to create temp dir");
+        let peer_store = HistoryStore::open(&peer_dir.path().join("history.db"), "shared-passphrase!")
+            .expect("open peer store");
+        peer_store.add(&sample_record()).expect("add on peer");
+        let peer_host_uuid = peer_store.host_uuid().expect("peer host_uuid");
+        let synced_record = peer_store
+            .records_after(&peer_host_uuid, -1)
+            .expect("records_after")
+            .remove(0);
+
+        let local_dir = TempDir::new().expect("failed to create temp dir");
+        let local_store =
+            HistoryStore::open_with_search(&local_dir.path().join("history.db"), "shared-passphrase!")
+                .expect("open local store with search");
+        local_store
+            .insert_encrypted_record(&synced_record)
+            .expect("insert synced record");
+
+        let results = local_store.search("hello").expect("search");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].original_text, "hello world this is a test");
+    }
+
+    #[test]
+    fn deleting_a_record_removes_its_search_tags() {
+        let (store, _dir) = test_search_store();
+        let id = store.add(&sample_record()).expect("add");
+
+        store.delete(id).expect("delete");
+
+        assert!(store.search("hello").expect("search").is_empty());
+    }
 }
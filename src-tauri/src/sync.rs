@@ -0,0 +1,270 @@
+//! Encrypted multi-device history sync.
+//!
+//! Records are encrypted client-side with the same AES-256-GCM cipher as
+//! [`crate::storage`]; the server only ever sees `{host_uuid, idx,
+//! nonce||ciphertext||tag}` triples, never plaintext or the key. Each
+//! device has a stable `host_uuid` ([`HistoryStore::host_uuid`]) and
+//! assigns its own records a monotonically increasing `idx` starting at
+//! 0 — an array-indexed append-only log per host, rather than a
+//! parent-pointer chain, so [`sync`] is just "upload everything past the
+//! remote's last-seen idx per host, and download the inverse", and
+//! re-running it is naturally idempotent.
+//!
+//! The shared encryption key is distributed out-of-band as a base64
+//! recovery string via [`export_key`]/[`import_key`].
+
+use std::collections::HashMap;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::error::VaaniError;
+use crate::storage::{EncryptedHistoryRecord, HistoryStore};
+
+/// `{host_uuid -> highest idx known}`, as reported by a sync peer or
+/// computed locally via [`HistoryStore::record_index`].
+pub type RecordIndex = HashMap<String, i64>;
+
+/// HTTP client for a sync server.
+pub struct SyncClient {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl SyncClient {
+    /// Create a client for the sync server at `base_url` (no trailing
+    /// slash required).
+    pub fn new(http: reqwest::Client, base_url: impl Into<String>) -> Self {
+        Self {
+            http,
+            base_url: base_url.into(),
+        }
+    }
+
+    /// Fetch the remote's `{host_uuid -> max idx}` map.
+    async fn fetch_remote_index(&self) -> Result<RecordIndex, VaaniError> {
+        let url = format!("{}/index", self.base_url.trim_end_matches('/'));
+        let response = self
+            .http
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| VaaniError::Storage(format!("sync index request failed: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(VaaniError::Storage(format!(
+                "sync index request returned HTTP {status}"
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| VaaniError::Storage(format!("failed to parse sync index response: {e}")))
+    }
+
+    /// Upload `records` (already encrypted) to the remote.
+    async fn upload(&self, records: &[EncryptedHistoryRecord]) -> Result<(), VaaniError> {
+        let url = format!("{}/records", self.base_url.trim_end_matches('/'));
+        let response = self
+            .http
+            .post(&url)
+            .json(records)
+            .send()
+            .await
+            .map_err(|e| VaaniError::Storage(format!("sync upload failed: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(VaaniError::Storage(format!(
+                "sync upload returned HTTP {status}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Ask the remote for every record exceeding `since` (exclusive),
+    /// per host.
+    async fn pull(&self, since: &RecordIndex) -> Result<Vec<EncryptedHistoryRecord>, VaaniError> {
+        let url = format!("{}/records/pull", self.base_url.trim_end_matches('/'));
+        let response = self
+            .http
+            .post(&url)
+            .json(since)
+            .send()
+            .await
+            .map_err(|e| VaaniError::Storage(format!("sync pull request failed: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(VaaniError::Storage(format!(
+                "sync pull request returned HTTP {status}"
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| VaaniError::Storage(format!("failed to parse sync pull response: {e}")))
+    }
+}
+
+/// Summary of a completed [`sync`] pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncSummary {
+    pub uploaded: usize,
+    pub downloaded: usize,
+}
+
+/// Reconcile `store`'s local history with the remote reachable via
+/// `client`: upload every local record past what the remote has seen for
+/// its host, and download the inverse. Each direction is diffed
+/// independently per host, so the exchange is idempotent — re-running it
+/// with nothing new on either side uploads and downloads nothing.
+///
+/// # Errors
+///
+/// Returns [`VaaniError::Storage`] if the remote is unreachable, returns a
+/// non-success status, or the local database can't be read or written.
+pub async fn sync(store: &HistoryStore, client: &SyncClient) -> Result<SyncSummary, VaaniError> {
+    let local_index = store.record_index()?;
+    let remote_index = client.fetch_remote_index().await?;
+
+    let mut to_upload = Vec::new();
+    for (host_uuid, &local_max) in &local_index {
+        let remote_max = remote_index.get(host_uuid).copied().unwrap_or(-1);
+        if local_max > remote_max {
+            to_upload.extend(store.records_after(host_uuid, remote_max)?);
+        }
+    }
+    if !to_upload.is_empty() {
+        client.upload(&to_upload).await?;
+    }
+
+    let downloaded = client.pull(&local_index).await?;
+    for record in &downloaded {
+        store.insert_encrypted_record(record)?;
+    }
+
+    tracing::info!(
+        uploaded = to_upload.len(),
+        downloaded = downloaded.len(),
+        "history sync complete"
+    );
+
+    Ok(SyncSummary {
+        uploaded: to_upload.len(),
+        downloaded: downloaded.len(),
+    })
+}
+
+/// Base64-encode `store`'s 32-byte AES key as a recovery string suitable
+/// for copy-pasting to another device.
+pub fn export_key(store: &HistoryStore) -> String {
+    BASE64.encode(store.key_bytes())
+}
+
+/// Decode a recovery string produced by [`export_key`] back into a raw
+/// 32-byte key, for [`crate::storage::HistoryStore::open_with_key`].
+///
+/// # Errors
+///
+/// Returns [`VaaniError::Storage`] if `recovery_string` isn't valid
+/// base64, or doesn't decode to exactly 32 bytes.
+pub fn import_key(recovery_string: &str) -> Result<[u8; 32], VaaniError> {
+    let bytes = BASE64
+        .decode(recovery_string.trim())
+        .map_err(|e| VaaniError::Storage(format!("invalid recovery string: {e}")))?;
+
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        VaaniError::Storage(format!(
+            "recovery string must decode to exactly 32 bytes, got {}",
+            bytes.len()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::NewHistoryRecord;
+    use tempfile::TempDir;
+
+    fn test_store() -> (HistoryStore, TempDir) {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let db_path = dir.path().join("history.db");
+        let store = HistoryStore::open(&db_path, "sync-test-passphrase").expect("open store");
+        (store, dir)
+    }
+
+    #[test]
+    fn export_key_round_trips_through_import_key() {
+        let (store, _dir) = test_store();
+
+        let recovery_string = export_key(&store);
+        let imported = import_key(&recovery_string).expect("import should succeed");
+
+        assert_eq!(&imported, store.key_bytes());
+    }
+
+    #[test]
+    fn import_key_rejects_invalid_base64() {
+        let result = import_key("not valid base64!!");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn import_key_rejects_wrong_length() {
+        let short = BASE64.encode([0u8; 16]);
+        let result = import_key(&short);
+        match result.unwrap_err() {
+            VaaniError::Storage(msg) => assert!(msg.contains("32 bytes")),
+            other => panic!("expected Storage error, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn sync_summary_defaults_to_zero() {
+        assert_eq!(
+            SyncSummary::default(),
+            SyncSummary {
+                uploaded: 0,
+                downloaded: 0
+            }
+        );
+    }
+
+    #[test]
+    fn record_index_feeds_the_upload_diff() {
+        let (store, _dir) = test_store();
+        store
+            .add(&NewHistoryRecord {
+                original_text: "first",
+                enhanced_text: "First.",
+                mode: "professional",
+                duration_secs: 1.0,
+            })
+            .expect("add");
+        store
+            .add(&NewHistoryRecord {
+                original_text: "second",
+                enhanced_text: "Second.",
+                mode: "professional",
+                duration_secs: 1.0,
+            })
+            .expect("add");
+
+        let host_uuid = store.host_uuid().expect("host_uuid");
+        let local_index = store.record_index().expect("record_index");
+
+        // A remote that has only seen idx 0 should get just the second
+        // record on upload.
+        let remote_max = *local_index.get(&host_uuid).expect("host present") - 1;
+        let diff = store
+            .records_after(&host_uuid, remote_max)
+            .expect("records_after");
+        assert_eq!(diff.len(), 1);
+    }
+}
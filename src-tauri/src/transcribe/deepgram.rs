@@ -0,0 +1,189 @@
+//! Deepgram transcription backend.
+//!
+//! Unlike OpenAI's multipart form, Deepgram's pre-recorded `/listen` endpoint
+//! accepts the raw audio bytes as the request body with a `Content-Type`
+//! header describing the encoding, and returns a deeply nested JSON result
+//! rather than a flat `{ "text": ... }`.
+
+use crate::error::VaaniError;
+
+/// Default Deepgram pre-recorded transcription endpoint.
+const DEFAULT_DEEPGRAM_URL: &str = "https://api.deepgram.com/v1/listen";
+
+/// Typed representation of the (truncated) Deepgram API JSON response —
+/// only the fields needed to extract the top transcript.
+#[derive(serde::Deserialize)]
+struct DeepgramResponse {
+    results: DeepgramResults,
+}
+
+#[derive(serde::Deserialize)]
+struct DeepgramResults {
+    channels: Vec<DeepgramChannel>,
+}
+
+#[derive(serde::Deserialize)]
+struct DeepgramChannel {
+    alternatives: Vec<DeepgramAlternative>,
+}
+
+#[derive(serde::Deserialize)]
+struct DeepgramAlternative {
+    transcript: String,
+}
+
+/// Transcribe audio using the default Deepgram endpoint.
+///
+/// # Errors
+///
+/// Returns [`VaaniError::MissingApiKey`] if the key is empty,
+/// [`VaaniError::NoSpeechDetected`] if Deepgram returns an empty transcript,
+/// or [`VaaniError::Transcribe`] on any HTTP / parsing failure.
+pub async fn transcribe(
+    client: &reqwest::Client,
+    api_key: &str,
+    audio_wav: &[u8],
+    model: &str,
+) -> Result<String, VaaniError> {
+    transcribe_with_url(client, api_key, audio_wav, model, DEFAULT_DEEPGRAM_URL).await
+}
+
+/// Transcribe audio, allowing the caller to override the endpoint URL.
+pub async fn transcribe_with_url(
+    client: &reqwest::Client,
+    api_key: &str,
+    audio_wav: &[u8],
+    model: &str,
+    base_url: &str,
+) -> Result<String, VaaniError> {
+    if api_key.is_empty() {
+        return Err(VaaniError::MissingApiKey("Deepgram".into()));
+    }
+
+    tracing::debug!(
+        url = base_url,
+        model = model,
+        audio_bytes = audio_wav.len(),
+        "sending transcription request"
+    );
+
+    let response = client
+        .post(base_url)
+        .query(&[("model", model)])
+        .header("Authorization", format!("Token {api_key}"))
+        .header("Content-Type", "audio/wav")
+        .body(audio_wav.to_vec())
+        .send()
+        .await
+        .map_err(|e| VaaniError::Transcribe(format!("request failed: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<unreadable body>".into());
+        return Err(VaaniError::Transcribe(format!("HTTP {status}: {body}")));
+    }
+
+    let deepgram: DeepgramResponse = response
+        .json()
+        .await
+        .map_err(|e| VaaniError::Transcribe(format!("failed to parse response: {e}")))?;
+
+    let text = deepgram
+        .results
+        .channels
+        .first()
+        .and_then(|channel| channel.alternatives.first())
+        .map(|alt| alt.transcript.clone())
+        .ok_or_else(|| {
+            VaaniError::Transcribe("response had no channels/alternatives".to_string())
+        })?;
+
+    if text.is_empty() {
+        return Err(VaaniError::NoSpeechDetected);
+    }
+
+    tracing::debug!(chars = text.len(), "transcription complete");
+
+    Ok(text)
+}
+
+/// [`super::SttBackend`] backed by the Deepgram `/listen` API.
+pub struct DeepgramSttBackend {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+}
+
+impl DeepgramSttBackend {
+    pub fn new(api_key: String, model: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key,
+            model,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl super::SttBackend for DeepgramSttBackend {
+    fn name(&self) -> &'static str {
+        "deepgram"
+    }
+
+    async fn transcribe(&self, wav: &[u8], _sample_rate: u32) -> Result<String, VaaniError> {
+        transcribe(&self.client, &self.api_key, wav, &self.model).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SttBackend;
+    use super::*;
+
+    #[tokio::test]
+    async fn empty_api_key_returns_missing_api_key_error() {
+        let client = reqwest::Client::new();
+        let result = transcribe(&client, "", b"fake-wav-data", "nova-2").await;
+
+        match result.unwrap_err() {
+            VaaniError::MissingApiKey(provider) => assert_eq!(provider, "Deepgram"),
+            other => panic!("expected MissingApiKey, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn response_deserializes_nested_transcript() {
+        let json = r#"{
+            "results": {
+                "channels": [
+                    { "alternatives": [ { "transcript": "Hello, world!" } ] }
+                ]
+            }
+        }"#;
+        let resp: DeepgramResponse =
+            serde_json::from_str(json).expect("deserialization should succeed");
+        assert_eq!(
+            resp.results.channels[0].alternatives[0].transcript,
+            "Hello, world!"
+        );
+    }
+
+    #[test]
+    fn response_deserializes_empty_transcript() {
+        let json = r#"{
+            "results": { "channels": [ { "alternatives": [ { "transcript": "" } ] } ] }
+        }"#;
+        let resp: DeepgramResponse =
+            serde_json::from_str(json).expect("deserialization should succeed");
+        assert!(resp.results.channels[0].alternatives[0].transcript.is_empty());
+    }
+
+    #[test]
+    fn backend_name_is_deepgram() {
+        let backend = DeepgramSttBackend::new("key".into(), "nova-2".into());
+        assert_eq!(backend.name(), "deepgram");
+    }
+}
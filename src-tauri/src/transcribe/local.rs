@@ -0,0 +1,47 @@
+//! Stub local/offline transcription backend.
+//!
+//! No on-device speech engine is wired up yet (a future version would bundle
+//! something like `whisper.cpp`); selecting `"local"` today produces a
+//! clear, actionable error instead of silently failing, mirroring
+//! [`crate::keychain`]'s `StubStorage` fallback.
+
+use crate::error::VaaniError;
+
+/// [`super::SttBackend`] placeholder for a future on-device engine.
+pub struct LocalSttBackend;
+
+#[async_trait::async_trait]
+impl super::SttBackend for LocalSttBackend {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn transcribe(&self, _wav: &[u8], _sample_rate: u32) -> Result<String, VaaniError> {
+        Err(VaaniError::Transcribe(
+            "Local transcription is not yet available. Choose \"openai\" or \"deepgram\" as the \
+             STT provider in Settings."
+                .to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SttBackend;
+    use super::*;
+
+    #[tokio::test]
+    async fn local_backend_reports_not_yet_available() {
+        let backend = LocalSttBackend;
+        let err = backend
+            .transcribe(b"fake-wav-data", 16_000)
+            .await
+            .expect_err("local backend has no engine yet");
+        assert!(matches!(err, VaaniError::Transcribe(msg) if msg.contains("not yet available")));
+    }
+
+    #[test]
+    fn backend_name_is_local() {
+        assert_eq!(LocalSttBackend.name(), "local");
+    }
+}
@@ -0,0 +1,114 @@
+//! Pluggable speech-to-text backends.
+//!
+//! [`SttBackend`] is the abstraction `app::VaaniApp` transcribes through;
+//! [`resolve_stt_backend`] picks the concrete implementation named by
+//! `VaaniConfig::stt_provider`, the same config-driven-selection shape as
+//! [`crate::output::clipboard::resolve_clipboard_provider`]. `openai` wraps
+//! the original Whisper multipart API, `deepgram` posts raw WAV to
+//! Deepgram's `/listen` endpoint, and `local` is a stub for a future
+//! on-device engine. `streaming` is a separate live-transcription path
+//! ([`StreamingSession`]) rather than an [`SttBackend`] impl — it streams
+//! interim hypotheses as speech happens instead of returning one result at
+//! the end, so it doesn't fit that trait's request/response shape.
+
+mod deepgram;
+mod local;
+mod openai;
+mod streaming;
+
+pub use deepgram::DeepgramSttBackend;
+pub use local::LocalSttBackend;
+pub use openai::{
+    transcribe, transcribe_with_url, translate, translate_with_url, OpenAiSttBackend,
+    Transcription, WhisperSegment,
+};
+pub use streaming::StreamingSession;
+
+use crate::error::VaaniError;
+
+/// Names accepted by the `stt_provider` config field.
+pub const STT_PROVIDERS: &[&str] = &["openai", "deepgram", "local"];
+
+/// Names accepted by the `stt_task` config field.
+///
+/// Only `"openai"` currently has a translation endpoint; other providers
+/// honor `"transcribe"` only (see `app::VaaniApp::process_audio`).
+pub const STT_TASKS: &[&str] = &["transcribe", "translate"];
+
+/// Abstraction over a speech-to-text backend.
+#[async_trait::async_trait]
+pub trait SttBackend: Send + Sync {
+    /// Human-readable name of this backend (e.g. `"openai"`, `"deepgram"`).
+    fn name(&self) -> &'static str;
+
+    /// Transcribe `wav`-encoded audio captured at `sample_rate` and return
+    /// the recognized text.
+    async fn transcribe(&self, wav: &[u8], sample_rate: u32) -> Result<String, VaaniError>;
+}
+
+/// Resolve an [`SttBackend`] from the `stt_provider` config field.
+///
+/// `api_key` is the key resolved via `app::resolve_api_key` for the chosen
+/// provider; `"local"` ignores it since it runs fully offline.
+///
+/// Returns `VaaniError::Config` if `provider_name` is unrecognised, or
+/// `VaaniError::MissingApiKey` if `"openai"`/`"deepgram"` is selected
+/// without a resolvable key.
+pub fn resolve_stt_backend(
+    provider_name: &str,
+    model: &str,
+    api_key: Option<String>,
+) -> Result<Box<dyn SttBackend>, VaaniError> {
+    match provider_name {
+        "openai" => {
+            let api_key =
+                api_key.ok_or_else(|| VaaniError::MissingApiKey("OpenAI".to_string()))?;
+            Ok(Box::new(OpenAiSttBackend::new(api_key, model.to_string())))
+        }
+        "deepgram" => {
+            let api_key =
+                api_key.ok_or_else(|| VaaniError::MissingApiKey("Deepgram".to_string()))?;
+            Ok(Box::new(DeepgramSttBackend::new(api_key, model.to_string())))
+        }
+        "local" => Ok(Box::new(LocalSttBackend)),
+        other => Err(VaaniError::Config(format!(
+            "Unknown stt_provider '{other}'. Valid values: {}",
+            STT_PROVIDERS.join(", ")
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_unknown_provider_is_config_error() {
+        match resolve_stt_backend("carrier-pigeon", "whisper-1", Some("key".into())) {
+            Err(VaaniError::Config(msg)) => {
+                assert!(msg.contains("carrier-pigeon"));
+                assert!(msg.contains("openai"));
+            }
+            other => panic!("expected Config error, got backend: {}", other.is_ok()),
+        }
+    }
+
+    #[test]
+    fn resolve_openai_without_key_is_missing_api_key() {
+        let result = resolve_stt_backend("openai", "whisper-1", None);
+        assert!(matches!(result, Err(VaaniError::MissingApiKey(provider)) if provider == "OpenAI"));
+    }
+
+    #[test]
+    fn resolve_deepgram_without_key_is_missing_api_key() {
+        let result = resolve_stt_backend("deepgram", "nova-2", None);
+        assert!(matches!(result, Err(VaaniError::MissingApiKey(provider)) if provider == "Deepgram"));
+    }
+
+    #[test]
+    fn resolve_local_ignores_missing_key() {
+        let backend =
+            resolve_stt_backend("local", "whisper-1", None).expect("local backend always resolves");
+        assert_eq!(backend.name(), "local");
+    }
+}
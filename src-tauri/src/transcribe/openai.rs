@@ -0,0 +1,387 @@
+//! OpenAI Whisper transcription backend.
+//!
+//! The primary entry point is [`transcribe()`], which posts WAV audio to the
+//! Whisper endpoint and returns the recognized text. A lower-level
+//! [`transcribe_with_url()`] variant accepts a custom base URL for testing and
+//! returns the full [`Transcription`], optionally requesting Whisper's
+//! `verbose_json` format for per-segment timing and confidence. [`translate()`]
+//! mirrors `transcribe()` but posts to Whisper's `/translations` endpoint,
+//! always returning English text regardless of the spoken language.
+//! [`OpenAiSttBackend`] adapts these free functions to [`super::SttBackend`].
+
+use crate::error::VaaniError;
+use reqwest::multipart;
+
+/// Default OpenAI Whisper transcription endpoint.
+const DEFAULT_WHISPER_URL: &str = "https://api.openai.com/v1/audio/transcriptions";
+
+/// Default OpenAI Whisper translation endpoint.
+///
+/// Unlike [`DEFAULT_WHISPER_URL`], this endpoint always returns English
+/// text regardless of the spoken language.
+const DEFAULT_TRANSLATE_URL: &str = "https://api.openai.com/v1/audio/translations";
+
+/// Typed representation of the Whisper API's plain `json` response.
+#[derive(serde::Deserialize)]
+struct WhisperResponse {
+    text: String,
+}
+
+/// Typed representation of the Whisper API's `verbose_json` response.
+#[derive(serde::Deserialize)]
+struct WhisperVerboseResponse {
+    text: String,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    #[allow(dead_code)]
+    duration: Option<f64>,
+    #[serde(default)]
+    segments: Vec<WhisperSegment>,
+}
+
+/// One `verbose_json` segment, trimmed to the fields callers actually need.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct WhisperSegment {
+    pub start: f64,
+    pub end: f64,
+    pub avg_logprob: f64,
+    pub no_speech_prob: f64,
+}
+
+/// A completed transcription, with the richer fields `verbose_json` unlocks.
+///
+/// `language` and `segments` are only populated when `transcribe_with_url`
+/// was asked for verbose output; otherwise `language` is `None` and
+/// `segments` is empty.
+#[derive(Debug, Clone)]
+pub struct Transcription {
+    pub text: String,
+    pub language: Option<String>,
+    pub segments: Vec<WhisperSegment>,
+}
+
+/// Transcribe audio using the default OpenAI Whisper endpoint.
+///
+/// # Arguments
+///
+/// * `client` - A reusable `reqwest::Client` (connection pooling, timeouts, etc.).
+/// * `api_key` - OpenAI API key. Must not be empty.
+/// * `audio_wav` - Raw WAV-encoded audio bytes.
+/// * `model` - Whisper model identifier (e.g. `"whisper-1"`).
+///
+/// # Errors
+///
+/// Returns [`VaaniError::MissingApiKey`] if the key is empty,
+/// [`VaaniError::NoSpeechDetected`] if Whisper returns empty text, or
+/// [`VaaniError::Transcribe`] on any HTTP / parsing failure.
+pub async fn transcribe(
+    client: &reqwest::Client,
+    api_key: &str,
+    audio_wav: &[u8],
+    model: &str,
+) -> Result<String, VaaniError> {
+    transcribe_with_url(client, api_key, audio_wav, model, DEFAULT_WHISPER_URL, false)
+        .await
+        .map(|t| t.text)
+}
+
+/// Transcribe audio, allowing the caller to override the endpoint URL and
+/// request Whisper's richer `verbose_json` format.
+///
+/// This is the implementation behind [`transcribe()`]. Accepting a custom URL
+/// makes it possible to point at a local mock server in integration tests.
+/// When `verbose` is `false`, the plain `json` format is requested and the
+/// returned [`Transcription`] has `language: None` and empty `segments`.
+pub async fn transcribe_with_url(
+    client: &reqwest::Client,
+    api_key: &str,
+    audio_wav: &[u8],
+    model: &str,
+    base_url: &str,
+    verbose: bool,
+) -> Result<Transcription, VaaniError> {
+    if api_key.is_empty() {
+        return Err(VaaniError::MissingApiKey("OpenAI".into()));
+    }
+
+    tracing::debug!(
+        url = base_url,
+        model = model,
+        audio_bytes = audio_wav.len(),
+        verbose,
+        "sending transcription request"
+    );
+
+    let file_part = multipart::Part::bytes(audio_wav.to_vec())
+        .file_name("recording.wav")
+        .mime_str("audio/wav")
+        .map_err(|e| VaaniError::Transcribe(format!("failed to build mime type: {e}")))?;
+
+    let mut form = multipart::Form::new()
+        .part("file", file_part)
+        .text("model", model.to_owned());
+    if verbose {
+        form = form.text("response_format", "verbose_json");
+    }
+
+    let response = client
+        .post(base_url)
+        .header("Authorization", format!("Bearer {api_key}"))
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|e| VaaniError::Transcribe(format!("request failed: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<unreadable body>".into());
+        return Err(VaaniError::Transcribe(format!("HTTP {status}: {body}")));
+    }
+
+    let transcription = if verbose {
+        let whisper: WhisperVerboseResponse = response
+            .json()
+            .await
+            .map_err(|e| VaaniError::Transcribe(format!("failed to parse response: {e}")))?;
+        Transcription {
+            text: whisper.text,
+            language: whisper.language,
+            segments: whisper.segments,
+        }
+    } else {
+        let whisper: WhisperResponse = response
+            .json()
+            .await
+            .map_err(|e| VaaniError::Transcribe(format!("failed to parse response: {e}")))?;
+        Transcription {
+            text: whisper.text,
+            language: None,
+            segments: Vec::new(),
+        }
+    };
+
+    if transcription.text.is_empty() {
+        return Err(VaaniError::NoSpeechDetected);
+    }
+
+    tracing::debug!(chars = transcription.text.len(), "transcription complete");
+
+    Ok(transcription)
+}
+
+/// Translate audio into English using the default OpenAI Whisper endpoint.
+///
+/// Like [`transcribe()`], but posts to Whisper's `/translations` endpoint:
+/// whatever language is spoken, the returned text is English.
+///
+/// # Errors
+///
+/// Returns [`VaaniError::MissingApiKey`] if the key is empty,
+/// [`VaaniError::NoSpeechDetected`] if Whisper returns empty text, or
+/// [`VaaniError::Transcribe`] on any HTTP / parsing failure.
+pub async fn translate(
+    client: &reqwest::Client,
+    api_key: &str,
+    audio_wav: &[u8],
+    model: &str,
+) -> Result<String, VaaniError> {
+    translate_with_url(client, api_key, audio_wav, model, DEFAULT_TRANSLATE_URL).await
+}
+
+/// Translate audio, allowing the caller to override the endpoint URL.
+///
+/// This is the implementation behind [`translate()`]. Accepting a custom URL
+/// makes it possible to point at a local mock server in integration tests.
+pub async fn translate_with_url(
+    client: &reqwest::Client,
+    api_key: &str,
+    audio_wav: &[u8],
+    model: &str,
+    base_url: &str,
+) -> Result<String, VaaniError> {
+    if api_key.is_empty() {
+        return Err(VaaniError::MissingApiKey("OpenAI".into()));
+    }
+
+    tracing::debug!(
+        url = base_url,
+        model = model,
+        audio_bytes = audio_wav.len(),
+        "sending translation request"
+    );
+
+    let file_part = multipart::Part::bytes(audio_wav.to_vec())
+        .file_name("recording.wav")
+        .mime_str("audio/wav")
+        .map_err(|e| VaaniError::Transcribe(format!("failed to build mime type: {e}")))?;
+
+    let form = multipart::Form::new()
+        .part("file", file_part)
+        .text("model", model.to_owned());
+
+    let response = client
+        .post(base_url)
+        .header("Authorization", format!("Bearer {api_key}"))
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|e| VaaniError::Transcribe(format!("request failed: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<unreadable body>".into());
+        return Err(VaaniError::Transcribe(format!("HTTP {status}: {body}")));
+    }
+
+    let whisper: WhisperResponse = response
+        .json()
+        .await
+        .map_err(|e| VaaniError::Transcribe(format!("failed to parse response: {e}")))?;
+
+    if whisper.text.is_empty() {
+        return Err(VaaniError::NoSpeechDetected);
+    }
+
+    tracing::debug!(chars = whisper.text.len(), "translation complete");
+
+    Ok(whisper.text)
+}
+
+/// [`super::SttBackend`] backed by the OpenAI Whisper API.
+pub struct OpenAiSttBackend {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+}
+
+impl OpenAiSttBackend {
+    pub fn new(api_key: String, model: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key,
+            model,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl super::SttBackend for OpenAiSttBackend {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    async fn transcribe(&self, wav: &[u8], _sample_rate: u32) -> Result<String, VaaniError> {
+        transcribe(&self.client, &self.api_key, wav, &self.model).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::SttBackend;
+
+    // ---- API-key validation ----
+
+    #[tokio::test]
+    async fn empty_api_key_returns_missing_api_key_error() {
+        let client = reqwest::Client::new();
+        let result = transcribe(&client, "", b"fake-wav-data", "whisper-1").await;
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        match &err {
+            VaaniError::MissingApiKey(provider) => {
+                assert_eq!(provider, "OpenAI");
+            }
+            other => panic!("expected MissingApiKey, got: {other:?}"),
+        }
+    }
+
+    // ---- Error variant names compile and are matchable ----
+
+    #[test]
+    fn error_variants_are_constructible() {
+        // Verify that the specific variants we rely on exist and can be constructed.
+        let _transcribe = VaaniError::Transcribe("test".into());
+        let _missing = VaaniError::MissingApiKey("OpenAI".into());
+        let _no_speech = VaaniError::NoSpeechDetected;
+    }
+
+    // ---- WhisperResponse deserialization ----
+
+    #[test]
+    fn whisper_response_deserializes_from_json() {
+        let json = r#"{"text": "Hello, world!"}"#;
+        let resp: WhisperResponse =
+            serde_json::from_str(json).expect("deserialization should succeed");
+        assert_eq!(resp.text, "Hello, world!");
+    }
+
+    #[test]
+    fn whisper_response_deserializes_empty_text() {
+        let json = r#"{"text": ""}"#;
+        let resp: WhisperResponse =
+            serde_json::from_str(json).expect("deserialization should succeed");
+        assert!(resp.text.is_empty());
+    }
+
+    // ---- WhisperVerboseResponse deserialization ----
+
+    #[test]
+    fn whisper_verbose_response_deserializes_segments_and_language() {
+        let json = r#"{
+            "text": "Hello, world!",
+            "language": "english",
+            "duration": 1.5,
+            "segments": [
+                {
+                    "id": 0, "seek": 0, "start": 0.0, "end": 1.5,
+                    "text": "Hello, world!", "tokens": [1, 2, 3],
+                    "temperature": 0.0, "avg_logprob": -0.2,
+                    "compression_ratio": 1.1, "no_speech_prob": 0.01
+                }
+            ]
+        }"#;
+        let resp: WhisperVerboseResponse =
+            serde_json::from_str(json).expect("deserialization should succeed");
+        assert_eq!(resp.text, "Hello, world!");
+        assert_eq!(resp.language.as_deref(), Some("english"));
+        assert_eq!(resp.segments.len(), 1);
+        assert_eq!(resp.segments[0].start, 0.0);
+        assert_eq!(resp.segments[0].end, 1.5);
+        assert_eq!(resp.segments[0].avg_logprob, -0.2);
+        assert_eq!(resp.segments[0].no_speech_prob, 0.01);
+    }
+
+    #[test]
+    fn whisper_verbose_response_tolerates_missing_language() {
+        let json = r#"{"text": "hi", "segments": []}"#;
+        let resp: WhisperVerboseResponse =
+            serde_json::from_str(json).expect("deserialization should succeed");
+        assert_eq!(resp.language, None);
+        assert!(resp.segments.is_empty());
+    }
+
+    #[test]
+    fn backend_name_is_openai() {
+        let backend = OpenAiSttBackend::new("key".into(), "whisper-1".into());
+        assert_eq!(backend.name(), "openai");
+    }
+
+    // ---- translate() ----
+
+    #[tokio::test]
+    async fn translate_with_empty_api_key_returns_missing_api_key_error() {
+        let client = reqwest::Client::new();
+        let result = translate(&client, "", b"fake-wav-data", "whisper-1").await;
+
+        assert!(matches!(result, Err(VaaniError::MissingApiKey(provider)) if provider == "OpenAI"));
+    }
+}
@@ -0,0 +1,245 @@
+//! Deepgram streaming transcription session.
+//!
+//! Unlike [`super::openai`]/[`super::deepgram`]'s request/response backends,
+//! a streaming session holds a long-lived WebSocket connection alongside the
+//! recording itself: raw PCM16 frames are pushed to Deepgram's streaming
+//! `/listen` endpoint as they're captured, and interim/final transcripts
+//! come back over the same socket. The socket lifecycle runs on a
+//! `tokio::spawn`ed task — both `tokio-tungstenite` and [`AudioBuffer`] are
+//! `Send`, so unlike [`crate::audio::mic_test`]'s dedicated OS thread for
+//! the `!Send` cpal stream, no thread isolation is needed here. A timer
+//! drains newly-captured samples off [`AudioBuffer::take_samples`], and
+//! incoming hypotheses are delivered through a callback shaped like
+//! [`crate::enhance::enhance_streaming`]'s token callback, so the
+//! orchestrator can wire it straight into `StateMachine::emit_partial`.
+
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio::sync::oneshot;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::audio::capture::AudioBuffer;
+use crate::error::VaaniError;
+
+/// Default Deepgram real-time streaming endpoint.
+const DEEPGRAM_STREAMING_URL: &str = "wss://api.deepgram.com/v1/listen";
+
+/// How often the session polls [`AudioBuffer`] for newly-captured samples
+/// and pushes them as a PCM16 frame.
+const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Deepgram's streaming result payload — only the fields needed to extract
+/// the top transcript and finality.
+#[derive(serde::Deserialize)]
+struct DeepgramStreamingResult {
+    channel: DeepgramStreamingChannel,
+    #[serde(default)]
+    is_final: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct DeepgramStreamingChannel {
+    alternatives: Vec<DeepgramStreamingAlternative>,
+}
+
+#[derive(serde::Deserialize)]
+struct DeepgramStreamingAlternative {
+    transcript: String,
+}
+
+/// A running streaming-transcription session.
+///
+/// Produced by [`StreamingSession::start`]; call [`finish`](Self::finish) to
+/// flush the socket and collect the last final transcript once recording
+/// stops.
+pub struct StreamingSession {
+    stop: oneshot::Sender<()>,
+    task: tokio::task::JoinHandle<Result<String, VaaniError>>,
+}
+
+impl StreamingSession {
+    /// Opens a Deepgram streaming connection for `model`/`sample_rate` audio
+    /// and starts feeding it PCM16 frames drained from `audio_buffer` every
+    /// [`FRAME_INTERVAL`]. `on_partial` is called with `(transcript,
+    /// is_final)` for every hypothesis Deepgram reports.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VaaniError::MissingApiKey`] if `api_key` is empty, or
+    /// [`VaaniError::Transcribe`] if the WebSocket handshake fails.
+    pub async fn start(
+        audio_buffer: AudioBuffer,
+        api_key: String,
+        model: String,
+        sample_rate: u32,
+        on_partial: impl Fn(&str, bool) + Send + 'static,
+    ) -> Result<Self, VaaniError> {
+        if api_key.is_empty() {
+            return Err(VaaniError::MissingApiKey("Deepgram".to_string()));
+        }
+
+        let url = format!(
+            "{DEEPGRAM_STREAMING_URL}?model={model}&encoding=linear16&sample_rate={sample_rate}&channels=1"
+        );
+
+        let mut request = url
+            .into_client_request()
+            .map_err(|e| VaaniError::Transcribe(format!("invalid streaming URL: {e}")))?;
+        request.headers_mut().insert(
+            "Authorization",
+            format!("Token {api_key}")
+                .parse()
+                .map_err(|_| VaaniError::Transcribe("invalid API key".to_string()))?,
+        );
+
+        let (ws_stream, _) = tokio_tungstenite::connect_async(request)
+            .await
+            .map_err(|e| VaaniError::Transcribe(format!("failed to connect: {e}")))?;
+
+        let (stop_tx, stop_rx) = oneshot::channel();
+        let task = tokio::spawn(run_session(ws_stream, audio_buffer, on_partial, stop_rx));
+
+        Ok(Self {
+            stop: stop_tx,
+            task,
+        })
+    }
+
+    /// Signals the session to stop pushing new audio, sends Deepgram's
+    /// `CloseStream` control message, and waits for the socket to drain.
+    /// Returns the last final transcript received.
+    pub async fn finish(self) -> Result<String, VaaniError> {
+        // The receiving end is dropped if the task already exited (e.g. the
+        // socket closed on its own); that's not a failure we need to report.
+        let _ = self.stop.send(());
+        self.task
+            .await
+            .map_err(|e| VaaniError::Transcribe(format!("streaming task panicked: {e}")))?
+    }
+}
+
+/// Drives one session's socket: concurrently pushes audio frames, reads
+/// hypotheses, and watches for the stop signal.
+async fn run_session(
+    ws_stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    audio_buffer: AudioBuffer,
+    on_partial: impl Fn(&str, bool) + Send + 'static,
+    mut stop_rx: oneshot::Receiver<()>,
+) -> Result<String, VaaniError> {
+    let (mut write, mut read) = ws_stream.split();
+    let mut frame_timer = tokio::time::interval(FRAME_INTERVAL);
+    let mut last_final = String::new();
+    let mut closing = false;
+
+    loop {
+        tokio::select! {
+            _ = frame_timer.tick(), if !closing => {
+                let samples = audio_buffer.take_samples();
+                if !samples.is_empty() {
+                    let frame = encode_pcm16(&samples);
+                    if write.send(WsMessage::Binary(frame)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            incoming = read.next() => {
+                match incoming {
+                    Some(Ok(WsMessage::Text(text))) => {
+                        if let Some((transcript, is_final)) = parse_streaming_result(&text) {
+                            if !transcript.is_empty() {
+                                on_partial(&transcript, is_final);
+                                if is_final {
+                                    last_final = transcript;
+                                }
+                            }
+                        }
+                    }
+                    Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => break,
+                    _ => {}
+                }
+            }
+            _ = &mut stop_rx, if !closing => {
+                closing = true;
+                let _ = write
+                    .send(WsMessage::Text(r#"{"type":"CloseStream"}"#.to_string()))
+                    .await;
+            }
+        }
+    }
+
+    Ok(last_final)
+}
+
+/// Convert f32 samples to little-endian PCM16 bytes — the same [-1.0, 1.0]
+/// to `i16` conversion [`crate::audio::processing::encode_wav`] uses for its
+/// sample data, without the surrounding WAV header Deepgram's streaming
+/// endpoint doesn't expect.
+fn encode_pcm16(samples: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(samples.len() * 2);
+    for &sample in samples {
+        let clamped = sample.clamp(-1.0, 1.0);
+        let as_i16 = (clamped * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&as_i16.to_le_bytes());
+    }
+    bytes
+}
+
+/// Parse a Deepgram streaming result message into `(transcript, is_final)`.
+/// Returns `None` for non-result messages (e.g. `Metadata`) or malformed JSON.
+fn parse_streaming_result(text: &str) -> Option<(String, bool)> {
+    let result: DeepgramStreamingResult = serde_json::from_str(text).ok()?;
+    let transcript = result.channel.alternatives.first()?.transcript.clone();
+    Some((transcript, result.is_final))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_pcm16_converts_full_scale_samples() {
+        let bytes = encode_pcm16(&[1.0, -1.0, 0.0]);
+        assert_eq!(bytes.len(), 6);
+        assert_eq!(i16::from_le_bytes([bytes[0], bytes[1]]), i16::MAX);
+        assert_eq!(i16::from_le_bytes([bytes[2], bytes[3]]), -i16::MAX);
+        assert_eq!(i16::from_le_bytes([bytes[4], bytes[5]]), 0);
+    }
+
+    #[test]
+    fn encode_pcm16_clamps_out_of_range_samples() {
+        let bytes = encode_pcm16(&[2.0, -2.0]);
+        assert_eq!(i16::from_le_bytes([bytes[0], bytes[1]]), i16::MAX);
+        assert_eq!(i16::from_le_bytes([bytes[2], bytes[3]]), -i16::MAX);
+    }
+
+    #[test]
+    fn parse_streaming_result_extracts_interim_transcript() {
+        let json = r#"{"channel":{"alternatives":[{"transcript":"hel"}]},"is_final":false}"#;
+        let (transcript, is_final) = parse_streaming_result(json).expect("should parse");
+        assert_eq!(transcript, "hel");
+        assert!(!is_final);
+    }
+
+    #[test]
+    fn parse_streaming_result_extracts_final_transcript() {
+        let json = r#"{"channel":{"alternatives":[{"transcript":"hello world"}]},"is_final":true}"#;
+        let (transcript, is_final) = parse_streaming_result(json).expect("should parse");
+        assert_eq!(transcript, "hello world");
+        assert!(is_final);
+    }
+
+    #[test]
+    fn parse_streaming_result_ignores_non_result_messages() {
+        let json = r#"{"type":"Metadata","request_id":"abc"}"#;
+        assert!(parse_streaming_result(json).is_none());
+    }
+
+    #[test]
+    fn parse_streaming_result_ignores_malformed_json() {
+        assert!(parse_streaming_result("not json").is_none());
+    }
+}
@@ -3,8 +3,13 @@
 //! Vaani runs as a menu bar (system tray) app with no main window.
 //! The tray icon and menu reflect the current app state.
 
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use tauri::image::Image;
 use tauri::menu::{Menu, MenuItem};
-use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
 use tauri::{AppHandle, Emitter};
 
 use crate::state::AppState;
@@ -14,8 +19,9 @@ const MENU_TOGGLE: &str = "toggle_recording";
 const MENU_PREFERENCES: &str = "preferences";
 const MENU_QUIT: &str = "quit";
 
-/// Sets up the system tray icon and menu.
-pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+/// Sets up the system tray icon and menu, and returns the [`TrayAnimator`]
+/// that drives its recording-in-progress blink.
+pub fn setup_tray(app: &AppHandle) -> Result<TrayAnimator, Box<dyn std::error::Error>> {
     let toggle = MenuItem::with_id(app, MENU_TOGGLE, "Start Recording", true, None::<&str>)?;
     let preferences =
         MenuItem::with_id(app, MENU_PREFERENCES, "Preferences...", true, None::<&str>)?;
@@ -23,12 +29,13 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
 
     let menu = Menu::with_items(app, &[&toggle, &preferences, &quit])?;
 
-    TrayIconBuilder::new()
-        .icon(
-            app.default_window_icon()
-                .cloned()
-                .unwrap_or_else(|| tauri::image::Image::new(&[], 0, 0)),
-        )
+    let icon = app
+        .default_window_icon()
+        .cloned()
+        .unwrap_or_else(|| tauri::image::Image::new(&[], 0, 0));
+
+    let tray = TrayIconBuilder::new()
+        .icon(icon.clone())
         .menu(&menu)
         .tooltip("Vaani — Voice to Text")
         .on_menu_event(move |app, event| {
@@ -69,7 +76,105 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .build(app)?;
 
     tracing::info!("System tray initialized");
-    Ok(())
+    TrayAnimator::spawn(tray, icon).map_err(Into::into)
+}
+
+/// Commands accepted by the tray-blink worker thread.
+enum BlinkCommand {
+    Start { interval_ms: u64 },
+    Stop,
+}
+
+/// Drives the tray icon's recording-in-progress blink.
+///
+/// `setup_tray` would otherwise discard the [`TrayIcon`] handle returned by
+/// `TrayIconBuilder::build`; this owns it instead, on a dedicated worker
+/// thread that alternates between the solid icon and a dimmed copy of it at
+/// a configurable interval. Cloning is cheap — every clone talks to the same
+/// worker thread, mirroring [`crate::audio::mic_test::MicTestHandle`].
+#[derive(Clone)]
+pub struct TrayAnimator {
+    commands: mpsc::Sender<BlinkCommand>,
+}
+
+impl TrayAnimator {
+    /// Spawns the blink worker thread, which takes over all further icon
+    /// updates for `tray`.
+    fn spawn(tray: TrayIcon, solid: Image<'static>) -> Result<Self, std::io::Error> {
+        let dim = dim_icon(&solid);
+        let (tx, rx) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("vaani-tray-blink".into())
+            .spawn(move || blink_worker_loop(rx, tray, solid, dim))?;
+
+        Ok(Self { commands: tx })
+    }
+
+    /// Starts (or restarts, e.g. after an interval change) the blink
+    /// animation.
+    pub fn start(&self, interval_ms: u32) {
+        let _ = self.commands.send(BlinkCommand::Start {
+            interval_ms: interval_ms as u64,
+        });
+    }
+
+    /// Stops the blink animation and restores the solid icon.
+    pub fn stop(&self) {
+        let _ = self.commands.send(BlinkCommand::Stop);
+    }
+}
+
+/// Returns a dimmed copy of `icon`, used as the "off" phase of the blink.
+/// Scales down the alpha channel rather than the color channels, so it reads
+/// as a faded version of the same glyph rather than a different one.
+fn dim_icon(icon: &Image<'static>) -> Image<'static> {
+    let dimmed: Vec<u8> = icon
+        .rgba()
+        .chunks_exact(4)
+        .flat_map(|px| [px[0], px[1], px[2], (px[3] as f32 * 0.25) as u8])
+        .collect();
+    Image::new_owned(dimmed, icon.width(), icon.height())
+}
+
+/// Runs on the dedicated tray-blink thread. Owns the `TrayIcon` for the
+/// lifetime of the thread, alternating `solid`/`dim` on a timer while a
+/// blink is active, and blocking for the next command while idle —
+/// mirroring `audio::mic_test::worker_loop`'s idle/polling split.
+fn blink_worker_loop(
+    commands: mpsc::Receiver<BlinkCommand>,
+    tray: TrayIcon,
+    solid: Image<'static>,
+    dim: Image<'static>,
+) {
+    let mut interval: Option<Duration> = None;
+    let mut showing_solid = true;
+
+    loop {
+        let received = match interval {
+            Some(d) => commands.recv_timeout(d),
+            None => commands.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+        };
+
+        match received {
+            Ok(BlinkCommand::Start { interval_ms }) => {
+                interval = Some(Duration::from_millis(interval_ms.max(50)));
+                showing_solid = true;
+                let _ = tray.set_icon(Some(solid.clone()));
+            }
+            Ok(BlinkCommand::Stop) => {
+                interval = None;
+                showing_solid = true;
+                let _ = tray.set_icon(Some(solid.clone()));
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                showing_solid = !showing_solid;
+                let icon = if showing_solid { &solid } else { &dim };
+                let _ = tray.set_icon(Some(icon.clone()));
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
 }
 
 /// Returns the tray title string for a given app state.
@@ -77,6 +182,8 @@ pub fn title_for_state(state: AppState) -> &'static str {
     match state {
         AppState::Idle => "Vaani",
         AppState::Recording => "Vaani - Recording...",
+        AppState::Paused => "Vaani - Paused",
+        AppState::Streaming => "Vaani - Recording...",
         AppState::Processing => "Vaani - Processing...",
     }
 }
@@ -86,6 +193,8 @@ pub fn toggle_label_for_state(state: AppState) -> &'static str {
     match state {
         AppState::Idle => "Start Recording",
         AppState::Recording => "Stop Recording",
+        AppState::Paused => "Resume Recording",
+        AppState::Streaming => "Stop Recording",
         AppState::Processing => "Processing...",
     }
 }
@@ -104,6 +213,11 @@ mod tests {
         assert!(title_for_state(AppState::Recording).contains("Recording"));
     }
 
+    #[test]
+    fn title_for_paused() {
+        assert!(title_for_state(AppState::Paused).contains("Paused"));
+    }
+
     #[test]
     fn title_for_processing() {
         assert!(title_for_state(AppState::Processing).contains("Processing"));
@@ -122,6 +236,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn toggle_label_paused_says_resume() {
+        assert_eq!(
+            toggle_label_for_state(AppState::Paused),
+            "Resume Recording"
+        );
+    }
+
     #[test]
     fn toggle_label_processing_says_processing() {
         assert!(toggle_label_for_state(AppState::Processing).contains("Processing"));
@@ -132,4 +254,17 @@ mod tests {
         // Verify MODES is accessible from tray context
         assert_eq!(crate::config::MODES.len(), 5);
     }
+
+    #[test]
+    fn dim_icon_scales_down_alpha_only() {
+        let solid = Image::new_owned(vec![10, 20, 30, 255, 1, 2, 3, 128], 2, 1);
+        let dim = dim_icon(&solid);
+
+        assert_eq!(dim.width(), 2);
+        assert_eq!(dim.height(), 1);
+        assert_eq!(&dim.rgba()[0..3], &[10, 20, 30]);
+        assert_eq!(dim.rgba()[3], (255.0_f32 * 0.25) as u8);
+        assert_eq!(&dim.rgba()[4..7], &[1, 2, 3]);
+        assert_eq!(dim.rgba()[7], (128.0_f32 * 0.25) as u8);
+    }
 }
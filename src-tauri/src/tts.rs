@@ -0,0 +1,152 @@
+//! OpenAI text-to-speech read-back of the enhanced dictation result.
+//!
+//! When `VaaniConfig::speak_result` is enabled, [`synthesize_speech()`]
+//! posts the final enhanced text to OpenAI's `/v1/audio/speech` endpoint and
+//! [`speak()`] plays the returned MP3 bytes through the same `rodio`
+//! pipeline [`crate::sounds`] uses for effect playback.
+
+use crate::error::VaaniError;
+use crate::sounds::play_bytes;
+
+/// Default OpenAI text-to-speech endpoint.
+const DEFAULT_SPEECH_URL: &str = "https://api.openai.com/v1/audio/speech";
+
+/// Request body sent to the OpenAI text-to-speech endpoint.
+#[derive(Debug, serde::Serialize)]
+struct SpeechRequest<'a> {
+    model: &'a str,
+    voice: &'a str,
+    input: &'a str,
+    response_format: &'a str,
+}
+
+/// Synthesize `text` to speech using the default OpenAI endpoint, returning
+/// the raw MP3 bytes.
+///
+/// # Errors
+///
+/// Returns [`VaaniError::MissingApiKey`] if `api_key` is empty,
+/// [`VaaniError::Tts`] if `text` is empty or the API returns an error, and
+/// [`VaaniError::Http`] on network failures.
+pub async fn synthesize_speech(
+    client: &reqwest::Client,
+    api_key: &str,
+    text: &str,
+    model: &str,
+    voice: &str,
+) -> Result<Vec<u8>, VaaniError> {
+    synthesize_speech_with_url(client, DEFAULT_SPEECH_URL, api_key, text, model, voice).await
+}
+
+/// Synthesize speech, allowing the caller to override the endpoint URL.
+///
+/// This is the implementation behind [`synthesize_speech()`]. Accepting a
+/// custom URL makes it possible to point at a local mock server in
+/// integration tests.
+pub async fn synthesize_speech_with_url(
+    client: &reqwest::Client,
+    url: &str,
+    api_key: &str,
+    text: &str,
+    model: &str,
+    voice: &str,
+) -> Result<Vec<u8>, VaaniError> {
+    if api_key.is_empty() {
+        return Err(VaaniError::MissingApiKey("OpenAI".into()));
+    }
+    if text.trim().is_empty() {
+        return Err(VaaniError::Tts("input text is empty".into()));
+    }
+
+    let body = SpeechRequest {
+        model,
+        voice,
+        input: text,
+        response_format: "mp3",
+    };
+
+    tracing::debug!(url = url, model = model, voice = voice, "sending tts request");
+
+    let response = client
+        .post(url)
+        .header("Authorization", format!("Bearer {api_key}"))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| VaaniError::Tts(format!("request failed: {e}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<unreadable body>".into());
+        return Err(VaaniError::Tts(format!("HTTP {status}: {body}")));
+    }
+
+    let audio = response
+        .bytes()
+        .await
+        .map_err(|e| VaaniError::Tts(format!("failed to read response body: {e}")))?;
+
+    tracing::debug!(bytes = audio.len(), "tts synthesis complete");
+
+    Ok(audio.to_vec())
+}
+
+/// Synthesize `text` and play it back through [`crate::sounds::play_bytes`].
+///
+/// Playback is asynchronous (fire-and-forget, like sound effects) so the
+/// caller isn't blocked waiting for the audio to finish.
+pub async fn speak(
+    client: &reqwest::Client,
+    api_key: &str,
+    text: &str,
+    model: &str,
+    voice: &str,
+) -> Result<(), VaaniError> {
+    let audio = synthesize_speech(client, api_key, text, model, voice).await?;
+    play_bytes(audio, "tts")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn missing_api_key_returns_error() {
+        let client = reqwest::Client::new();
+        let result = synthesize_speech(&client, "", "hello", "tts-1", "alloy").await;
+
+        match result.unwrap_err() {
+            VaaniError::MissingApiKey(provider) => assert_eq!(provider, "OpenAI"),
+            other => panic!("expected MissingApiKey, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_text_returns_error() {
+        let client = reqwest::Client::new();
+        let result = synthesize_speech(&client, "sk-test", "   ", "tts-1", "alloy").await;
+
+        match result.unwrap_err() {
+            VaaniError::Tts(msg) => assert!(msg.contains("empty")),
+            other => panic!("expected Tts error, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn speech_request_serializes_expected_fields() {
+        let req = SpeechRequest {
+            model: "tts-1",
+            voice: "alloy",
+            input: "hello world",
+            response_format: "mp3",
+        };
+        let json = serde_json::to_value(&req).expect("serialization should succeed");
+        assert_eq!(json["model"], "tts-1");
+        assert_eq!(json["voice"], "alloy");
+        assert_eq!(json["input"], "hello world");
+        assert_eq!(json["response_format"], "mp3");
+    }
+}
@@ -1,9 +1,17 @@
-//! Startup update check against GitHub Releases API.
+//! Startup update check against GitHub Releases API, plus a self-update
+//! subsystem built on top of it.
 //!
-//! Compares the running version against the latest release on GitHub. This is
-//! a lightweight, non-blocking check that runs once at startup.
+//! [`check_for_update`] compares the running version against the latest
+//! release on GitHub. This is a lightweight, non-blocking check that runs
+//! once at startup. [`download_and_apply_update`] takes the resulting
+//! [`UpdateStatus`], downloads the release asset matching this platform,
+//! verifies it against a published `*.sha256` checksum, and atomically
+//! swaps it in for the running executable.
+
+use std::io::Write;
 
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use tracing::{debug, info};
 
 use crate::error::VaaniError;
@@ -14,11 +22,20 @@ const GITHUB_REPO: &str = "anthropics/vaani";
 /// Current application version from Cargo.toml.
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// One asset attached to a GitHub release.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
 /// Response shape from the GitHub Releases API (only fields we need).
 #[derive(Debug, Deserialize)]
 struct GitHubRelease {
     tag_name: String,
     html_url: String,
+    #[serde(default)]
+    assets: Vec<ReleaseAsset>,
 }
 
 /// Result of an update check.
@@ -28,14 +45,20 @@ pub struct UpdateStatus {
     pub latest: String,
     pub update_available: bool,
     pub release_url: String,
+    pub assets: Vec<ReleaseAsset>,
 }
 
 /// Check for updates by querying the GitHub Releases API.
 ///
+/// `include_prereleases` controls whether a `-rc`/`-beta`/etc. tag counts as
+/// an available update; when `false` (the stable channel default), such a
+/// release is reported but `update_available` stays `false`.
+///
 /// Returns `Ok(None)` if the check fails gracefully (no network, rate-limited,
 /// etc.) — update checks should never block or crash the app.
 pub async fn check_for_update(
     client: &reqwest::Client,
+    include_prereleases: bool,
 ) -> Result<Option<UpdateStatus>, VaaniError> {
     let url = format!("https://api.github.com/repos/{GITHUB_REPO}/releases/latest");
 
@@ -71,7 +94,8 @@ pub async fn check_for_update(
     };
 
     let latest = release.tag_name.trim_start_matches('v').to_string();
-    let update_available = is_newer(&latest, CURRENT_VERSION);
+    let update_available = is_newer(&latest, CURRENT_VERSION)
+        && (include_prereleases || !is_prerelease(&latest));
 
     if update_available {
         info!(
@@ -93,29 +117,292 @@ pub async fn check_for_update(
         latest,
         update_available,
         release_url: release.html_url,
+        assets: release.assets,
     }))
 }
 
-/// Simple semver comparison: returns true if `latest` is newer than `current`.
-fn is_newer(latest: &str, current: &str) -> bool {
-    let parse =
-        |v: &str| -> Vec<u32> { v.split('.').filter_map(|s| s.parse::<u32>().ok()).collect() };
-
-    let l = parse(latest);
-    let c = parse(current);
-
-    // Compare component by component
-    for i in 0..l.len().max(c.len()) {
-        let lv = l.get(i).copied().unwrap_or(0);
-        let cv = c.get(i).copied().unwrap_or(0);
-        match lv.cmp(&cv) {
-            std::cmp::Ordering::Greater => return true,
-            std::cmp::Ordering::Less => return false,
-            std::cmp::Ordering::Equal => continue,
+// ── Self-update ──────────────────────────────────────────────────────────────
+
+/// This platform's target-triple suffix, as published in Vaani's release
+/// asset names (e.g. `vaani-x86_64-apple-darwin`).
+fn platform_asset_suffix() -> &'static str {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "aarch64") => "aarch64-apple-darwin",
+        ("macos", "x86_64") => "x86_64-apple-darwin",
+        ("linux", "x86_64") => "x86_64-unknown-linux-gnu",
+        ("linux", "aarch64") => "aarch64-unknown-linux-gnu",
+        ("windows", "x86_64") => "x86_64-pc-windows-msvc",
+        _ => "unsupported-platform",
+    }
+}
+
+/// Find the release asset matching this platform and its `*.sha256`
+/// checksum sidecar, if both are published.
+fn find_platform_asset(assets: &[ReleaseAsset]) -> Option<(&ReleaseAsset, &ReleaseAsset)> {
+    let suffix = platform_asset_suffix();
+    let binary = assets
+        .iter()
+        .find(|a| a.name.contains(suffix) && !a.name.ends_with(".sha256"))?;
+    let checksum = assets.iter().find(|a| a.name == format!("{}.sha256", binary.name))?;
+    Some((binary, checksum))
+}
+
+/// Download the release asset matching this platform (from `status.assets`),
+/// verify it against its published `*.sha256` checksum, and atomically
+/// replace the running executable.
+///
+/// `on_progress` is called with the download fraction in `[0.0, 1.0]` as
+/// bytes arrive, or left at `0.0` throughout if the server doesn't send a
+/// `content-length`. The previous binary is kept alongside the new one with
+/// a `.bak` extension for manual rollback; a checksum mismatch or I/O
+/// failure is caught before the swap, so it never corrupts the running
+/// executable.
+///
+/// # Errors
+///
+/// Returns [`VaaniError::Update`] if no release asset matches this
+/// platform, the download fails, the checksum doesn't match, or the atomic
+/// swap fails.
+pub async fn download_and_apply_update(
+    client: &reqwest::Client,
+    status: &UpdateStatus,
+    mut on_progress: impl FnMut(f32) + Send,
+) -> Result<(), VaaniError> {
+    let (binary, checksum) = find_platform_asset(&status.assets).ok_or_else(|| {
+        VaaniError::Update(format!(
+            "No release asset found for this platform ({})",
+            platform_asset_suffix()
+        ))
+    })?;
+    let binary_name = binary.name.clone();
+    let binary_url = binary.browser_download_url.clone();
+    let checksum_url = checksum.browser_download_url.clone();
+
+    let expected_checksum = download_checksum(client, &checksum_url).await?;
+
+    let current_exe = std::env::current_exe()
+        .map_err(|e| VaaniError::Update(format!("Failed to locate running executable: {e}")))?;
+    let exe_dir = current_exe
+        .parent()
+        .ok_or_else(|| VaaniError::Update("Running executable has no parent directory".into()))?;
+
+    let mut temp_file = tempfile::NamedTempFile::new_in(exe_dir)
+        .map_err(|e| VaaniError::Update(format!("Failed to create temp file for download: {e}")))?;
+
+    let downloaded_digest = stream_download_to_file(
+        client,
+        &binary_url,
+        temp_file.as_file_mut(),
+        &mut on_progress,
+    )
+    .await?;
+
+    if !downloaded_digest.eq_ignore_ascii_case(&expected_checksum) {
+        return Err(VaaniError::Update(format!(
+            "Checksum mismatch for {binary_name}: expected {expected_checksum}, got {downloaded_digest}"
+        )));
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = temp_file.as_file().metadata().map_err(|e| {
+            VaaniError::Update(format!("Failed to read downloaded file metadata: {e}"))
+        })?;
+        let mut perms = metadata.permissions();
+        perms.set_mode(0o755);
+        temp_file.as_file().set_permissions(perms).map_err(|e| {
+            VaaniError::Update(format!("Failed to mark downloaded file executable: {e}"))
+        })?;
+    }
+
+    let backup_path = current_exe.with_extension("bak");
+    std::fs::rename(&current_exe, &backup_path).map_err(|e| {
+        VaaniError::Update(format!(
+            "Failed to back up the running executable to {}: {e}",
+            backup_path.display()
+        ))
+    })?;
+
+    if let Err(e) = temp_file.persist(&current_exe) {
+        // Roll back so a failed swap never leaves the app unable to start.
+        let _ = std::fs::rename(&backup_path, &current_exe);
+        return Err(VaaniError::Update(format!(
+            "Failed to install the downloaded update: {e}"
+        )));
+    }
+
+    info!(
+        version = %status.latest,
+        backup = %backup_path.display(),
+        "Update installed; previous binary kept for rollback"
+    );
+    Ok(())
+}
+
+/// Download the `*.sha256` sidecar asset and extract the hex digest. GitHub
+/// releases commonly publish these in `sha256sum` output format
+/// (`<hex>  <filename>`), so only the first whitespace-separated token is
+/// taken.
+async fn download_checksum(client: &reqwest::Client, url: &str) -> Result<String, VaaniError> {
+    let body = client
+        .get(url)
+        .header("User-Agent", format!("Vaani/{CURRENT_VERSION}"))
+        .send()
+        .await
+        .map_err(|e| VaaniError::Update(format!("Failed to download checksum: {e}")))?
+        .text()
+        .await
+        .map_err(|e| VaaniError::Update(format!("Failed to read checksum body: {e}")))?;
+
+    body.split_whitespace()
+        .next()
+        .map(|s| s.to_lowercase())
+        .ok_or_else(|| VaaniError::Update("Checksum asset was empty".to_string()))
+}
+
+/// Stream `url`'s body into `file`, hashing as it arrives and reporting
+/// download progress (a `[0.0, 1.0]` fraction of `content-length`, when
+/// known) through `on_progress`. Returns the hex SHA-256 digest.
+async fn stream_download_to_file(
+    client: &reqwest::Client,
+    url: &str,
+    file: &mut std::fs::File,
+    on_progress: &mut impl FnMut(f32),
+) -> Result<String, VaaniError> {
+    let mut response = client
+        .get(url)
+        .header("User-Agent", format!("Vaani/{CURRENT_VERSION}"))
+        .send()
+        .await
+        .map_err(|e| VaaniError::Update(format!("Failed to download update: {e}")))?;
+
+    let total_bytes = response.content_length();
+    let mut downloaded: u64 = 0;
+    let mut hasher = Sha256::new();
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| VaaniError::Update(format!("Update download interrupted: {e}")))?
+    {
+        file.write_all(&chunk)
+            .map_err(|e| VaaniError::Update(format!("Failed to write downloaded update: {e}")))?;
+        hasher.update(&chunk);
+        downloaded += chunk.len() as u64;
+
+        if let Some(total) = total_bytes {
+            if total > 0 {
+                on_progress((downloaded as f32 / total as f32).min(1.0));
+            }
+        }
+    }
+
+    let digest = hasher.finalize();
+    Ok(digest.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// One dot-separated pre-release identifier, e.g. the `rc` and `1` in
+/// `-rc.1`. Per semver, numeric identifiers compare numerically and are
+/// always lower-precedence than alphanumeric ones.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PreReleaseIdentifier {
+    Numeric(u64),
+    Alphanumeric(String),
+}
+
+impl From<&str> for PreReleaseIdentifier {
+    fn from(ident: &str) -> Self {
+        if !ident.is_empty() && ident.bytes().all(|b| b.is_ascii_digit()) {
+            if let Ok(n) = ident.parse::<u64>() {
+                return PreReleaseIdentifier::Numeric(n);
+            }
+        }
+        PreReleaseIdentifier::Alphanumeric(ident.to_string())
+    }
+}
+
+/// A parsed `major.minor.patch[-prerelease][+build]` version. Build
+/// metadata is parsed away and never affects ordering, per the semver spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SemVer {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre_release: Vec<PreReleaseIdentifier>,
+}
+
+impl SemVer {
+    /// Parse a version string, normalizing a leading `v` (as in the git-tag
+    /// convention `v1.2.3`) and discarding build metadata. Unparseable or
+    /// missing numeric components default to `0` rather than failing —
+    /// update checks should degrade gracefully, not error out, on a
+    /// malformed tag name.
+    fn parse(version: &str) -> Self {
+        let version = version.trim().trim_start_matches('v');
+        let version = version.split('+').next().unwrap_or(version);
+        let (core, pre_release) = match version.split_once('-') {
+            Some((core, pre)) => (core, pre),
+            None => (version, ""),
+        };
+
+        let mut parts = core.split('.');
+        let mut next_component = || parts.next().and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+        let major = next_component();
+        let minor = next_component();
+        let patch = next_component();
+
+        let pre_release = if pre_release.is_empty() {
+            Vec::new()
+        } else {
+            pre_release.split('.').map(PreReleaseIdentifier::from).collect()
+        };
+
+        SemVer {
+            major,
+            minor,
+            patch,
+            pre_release,
         }
     }
 
-    false
+    fn is_prerelease(&self) -> bool {
+        !self.pre_release.is_empty()
+    }
+}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.is_prerelease(), other.is_prerelease()) {
+                // A pre-release has *lower* precedence than the same version
+                // without one (e.g. `1.0.0-rc.1` < `1.0.0`).
+                (true, true) => self.pre_release.cmp(&other.pre_release),
+                (true, false) => std::cmp::Ordering::Less,
+                (false, true) => std::cmp::Ordering::Greater,
+                (false, false) => std::cmp::Ordering::Equal,
+            })
+    }
+}
+
+/// Proper semver precedence comparison: returns true if `latest` is newer
+/// than `current`. Handles a `v` prefix, ignores build metadata, and orders
+/// pre-release tags below their corresponding release (see [`SemVer::cmp`]).
+fn is_newer(latest: &str, current: &str) -> bool {
+    SemVer::parse(latest) > SemVer::parse(current)
+}
+
+/// True if `version`'s tag carries a pre-release segment (e.g. `-rc.1`,
+/// `-beta`).
+fn is_prerelease(version: &str) -> bool {
+    SemVer::parse(version).is_prerelease()
 }
 
 // ── Tests ────────────────────────────────────────────────────────────────────
@@ -150,11 +437,54 @@ mod tests {
     }
 
     #[test]
-    fn is_newer_handles_different_lengths() {
-        assert!(is_newer("0.1.0.1", "0.1.0"));
+    fn is_newer_ignores_trailing_components_past_patch() {
+        // Not valid semver; the 4th component is simply not part of a
+        // major.minor.patch comparison.
+        assert!(!is_newer("0.1.0.1", "0.1.0"));
         assert!(!is_newer("0.1.0", "0.1.0.1"));
     }
 
+    #[test]
+    fn is_newer_normalizes_v_prefix() {
+        assert!(is_newer("v0.2.0", "0.1.0"));
+        assert!(is_newer("0.2.0", "v0.1.0"));
+        assert!(!is_newer("v0.1.0", "v0.1.0"));
+    }
+
+    #[test]
+    fn is_newer_prerelease_is_lower_than_release() {
+        assert!(!is_newer("0.2.0-rc.1", "0.2.0"));
+        assert!(is_newer("0.2.0", "0.2.0-rc.1"));
+    }
+
+    #[test]
+    fn is_newer_compares_prerelease_identifiers_numerically() {
+        assert!(is_newer("0.2.0-rc.2", "0.2.0-rc.1"));
+        assert!(!is_newer("0.2.0-rc.2", "0.2.0-rc.10"));
+    }
+
+    #[test]
+    fn is_newer_numeric_prerelease_identifier_is_lower_than_alphanumeric() {
+        assert!(is_newer("0.2.0-beta", "0.2.0-1"));
+    }
+
+    #[test]
+    fn is_newer_more_prerelease_fields_is_higher_precedence() {
+        assert!(is_newer("0.2.0-rc.1.1", "0.2.0-rc.1"));
+    }
+
+    #[test]
+    fn is_newer_ignores_build_metadata() {
+        assert!(!is_newer("0.2.0+build5", "0.2.0+build1"));
+    }
+
+    #[test]
+    fn is_prerelease_detects_prerelease_tags() {
+        assert!(is_prerelease("0.2.0-rc.1"));
+        assert!(!is_prerelease("0.2.0"));
+        assert!(!is_prerelease("v0.2.0+build5"));
+    }
+
     #[test]
     fn current_version_is_set() {
         assert!(!CURRENT_VERSION.is_empty());
@@ -179,8 +509,44 @@ mod tests {
             latest: "0.2.0".into(),
             update_available: true,
             release_url: "https://example.com".into(),
+            assets: vec![],
         };
         let debug_str = format!("{status:?}");
         assert!(debug_str.contains("update_available: true"));
     }
+
+    // ---- Self-update ----
+
+    fn asset(name: &str) -> ReleaseAsset {
+        ReleaseAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{name}"),
+        }
+    }
+
+    #[test]
+    fn find_platform_asset_matches_binary_and_checksum() {
+        let suffix = platform_asset_suffix();
+        let assets = vec![
+            asset(&format!("vaani-{suffix}")),
+            asset(&format!("vaani-{suffix}.sha256")),
+            asset("vaani-some-other-platform"),
+        ];
+        let (binary, checksum) = find_platform_asset(&assets).expect("should find a match");
+        assert_eq!(binary.name, format!("vaani-{suffix}"));
+        assert_eq!(checksum.name, format!("vaani-{suffix}.sha256"));
+    }
+
+    #[test]
+    fn find_platform_asset_returns_none_without_checksum_sidecar() {
+        let suffix = platform_asset_suffix();
+        let assets = vec![asset(&format!("vaani-{suffix}"))];
+        assert!(find_platform_asset(&assets).is_none());
+    }
+
+    #[test]
+    fn find_platform_asset_returns_none_when_nothing_matches() {
+        let assets = vec![asset("vaani-totally-unrelated")];
+        assert!(find_platform_asset(&assets).is_none());
+    }
 }